@@ -0,0 +1,60 @@
+//! Render benchmarks for [`StyledList`] over large lists.
+//!
+//! Run with `cargo bench --bench styled_list`. Each case renders a list into a 40x20 buffer, for
+//! list sizes of 10², 10⁴ and 10⁶ items with the selection at the start, middle and end of the
+//! list.
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use extra_widgets::styled_list::{ItemDisplay, ListItem, ListState, StyledList};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::StatefulWidget,
+};
+
+fn items(n: usize) -> Vec<ListItem<'static>> {
+    (0..n)
+        .map(|i| ListItem::new(format!("item number {}", i)))
+        .collect()
+}
+
+fn render(items: Vec<ListItem<'static>>, display: ItemDisplay, state: &mut ListState) -> Buffer {
+    let area = Rect::new(0, 0, 40, 20);
+    let mut buf = Buffer::empty(area);
+    let list = StyledList::new(items)
+        .default_style(Style::default().bg(Color::Black))
+        .selected_style(Style::default().bg(Color::Blue))
+        .item_display(display);
+    list.render(area, &mut buf, state);
+    buf
+}
+
+fn styled_list(c: &mut Criterion) {
+    for display in [ItemDisplay::Basic, ItemDisplay::Separated] {
+        let mut group = c.benchmark_group(format!("styled_list/{:?}", display));
+        for n in [100, 10_000, 1_000_000] {
+            // the large lists take long enough per render that fewer samples do
+            if n >= 1_000_000 {
+                group.sample_size(10);
+            }
+            // The items are built outside of the timed section, as an app would typically keep
+            // them around.
+            let source = items(n);
+            for (name, selected) in [("start", 0), ("middle", n / 2), ("end", n - 1)] {
+                let mut state = ListState::new(n);
+                state.select(selected);
+                group.bench_function(BenchmarkId::new(name, n), |b| {
+                    b.iter_batched(
+                        || source.clone(),
+                        |items| render(items, display, &mut state),
+                        BatchSize::LargeInput,
+                    )
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, styled_list);
+criterion_main!(benches);
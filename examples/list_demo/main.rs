@@ -9,13 +9,15 @@ use crossterm::{
 use ratatui::{
     backend::{Backend, CrosstermBackend},
     layout::{Alignment, Constraint, Direction, Layout, Margin},
-    style::{Color, Modifier, Style},
-    text::{Span, Spans},
+    style::{Color, Style},
     widgets::{Block, BorderType, Borders, Paragraph},
     Frame, Terminal,
 };
 
-use extra_widgets::styled_list::{ItemDisplay, ListItem, ListState, StyledList};
+use extra_widgets::{
+    bold, key_hints,
+    styled_list::{ItemDisplay, ListItem, ListState, StyledList},
+};
 
 mod demos;
 
@@ -91,10 +93,10 @@ fn main() -> Result<(), Box<dyn Error>> {
 
         if let Event::Key(key) = event::read()? {
             match key.code {
-                KeyCode::Char(c) if c == 'j' => {
+                KeyCode::Char('j') => {
                     state.move_down();
                 }
-                KeyCode::Char(c) if c == 'k' => {
+                KeyCode::Char('k') => {
                     state.move_up();
                 }
                 KeyCode::Char(c) if c == 'h' || c == 'l' => {
@@ -136,17 +138,8 @@ fn draw<B: Backend>(state: &mut AppState, f: &mut Frame<B>) {
     let main_area = chunks[2];
 
     // draw top bar
-    let top_text = Spans::from(vec![
-        Span::styled("Controls:", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(" 'h' - "),
-        Span::styled("left,", Style::default().add_modifier(Modifier::ITALIC)),
-        Span::raw(" 'j' - "),
-        Span::styled("down,", Style::default().add_modifier(Modifier::ITALIC)),
-        Span::raw(" 'k' - "),
-        Span::styled("up,", Style::default().add_modifier(Modifier::ITALIC)),
-        Span::raw(" 'l' - "),
-        Span::styled("right", Style::default().add_modifier(Modifier::ITALIC)),
-    ]);
+    let mut top_text = key_hints![("h", "left"), ("j", "down"), ("k", "up"), ("l", "right")];
+    top_text.0.insert(0, bold!("Controls: "));
     let top_text = Paragraph::new(top_text).alignment(Alignment::Center);
     f.render_widget(top_text, bar_area);
 
@@ -0,0 +1,43 @@
+//! Use the windowing from the styled list to draw custom lines.
+//!
+//! This draws a window of a long list of lines into a buffer, and prints the buffer. A real widget
+//! would keep `window_first` in its state between renders, so the window only moves when the
+//! selection would go out of view.
+use extra_widgets::styled_list::raw::{selection_scroll, DisplayLine};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+};
+
+fn main() {
+    let selected = 12;
+    let area = Rect::new(0, 0, 20, 5);
+
+    // One line per entry, with the selected entry flagged so the window keeps it in view
+    let lines = (0..30).map(|i| {
+        let mut line = DisplayLine::new(format!("entry {}", i));
+        if i == selected {
+            line.must_display = true;
+            line.style = Style::default().bg(Color::Blue);
+        }
+        line
+    });
+
+    let mut window_first = 0;
+    let mut buf = Buffer::empty(area);
+    for (row, line) in selection_scroll(lines, area.height as usize, &mut window_first).enumerate()
+    {
+        let y = area.y + row as u16;
+        buf.set_style(Rect::new(area.x, y, area.width, 1), line.style);
+        buf.set_spans(area.x, y, &line.line, area.width);
+    }
+
+    println!("window starts at line {}", window_first);
+    for y in 0..area.height {
+        let row: String = (0..area.width)
+            .map(|x| buf.get(x, y).symbol.as_str())
+            .collect();
+        println!("|{}|", row);
+    }
+}
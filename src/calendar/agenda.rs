@@ -0,0 +1,317 @@
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+use ratatui::{buffer::Buffer, layout::Rect, text::Spans, widgets::StatefulWidget};
+use time::Date;
+
+use super::{Calendar, CalendarEvents, CalendarState, DateStyler};
+use crate::styled_list::{ListItem, ListState, StyledList};
+
+/// The list of entries drawn by an [`Agenda`].
+pub type EntryList<'a> = StyledList<'a, Vec<ListItem<'a>>>;
+
+/// Closure producing the entries for a date.
+type EntriesFn<'a> = Box<dyn Fn(Date) -> Vec<ListItem<'a>> + 'a>;
+
+/// Closure configuring the list of entries.
+type ListFn<'a> = Box<dyn Fn(EntryList<'a>) -> EntryList<'a> + 'a>;
+
+/// The half of an [`Agenda`] that has the keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum AgendaPane {
+    /// The calendar, where keys move the focused date.
+    #[default]
+    Calendar,
+    /// The entries of the focused date, where keys move the entry cursor.
+    Entries,
+}
+
+/// A [`Calendar`] with the entries of its focused date listed beside it, in a
+/// [`StyledList`](crate::styled_list::StyledList). Available when both the `calendar` and
+/// `styled_list` features are enabled.
+///
+/// The entries are produced for the focused date at each render, by the function passed to
+/// [`new`](Self::new). The calendar is drawn on the left of the area, in
+/// [`calendar_width`](Self::calendar_width) columns, and the entries fill the rest.
+///
+/// Both halves share an [`AgendaState`], which also tracks which half has the keyboard focus.
+/// The agenda doesn't handle keys itself: apps check [`AgendaState::pane`] to decide whether a key
+/// moves the calendar or the entries.
+pub struct Agenda<'a, S: DateStyler> {
+    calendar: Calendar<'a, S>,
+    entries: EntriesFn<'a>,
+    list: Option<ListFn<'a>>,
+    empty: Option<Spans<'a>>,
+    calendar_width: u16,
+    spacing: u16,
+}
+
+impl<'a, S: DateStyler> Agenda<'a, S> {
+    /// Display `calendar`, and the entries returned by `entries` for its focused date.
+    pub fn new<F>(calendar: Calendar<'a, S>, entries: F) -> Self
+    where
+        F: Fn(Date) -> Vec<ListItem<'a>> + 'a,
+    {
+        Self {
+            calendar,
+            entries: Box::new(entries),
+            list: None,
+            empty: None,
+            calendar_width: 21,
+            spacing: 2,
+        }
+    }
+
+    /// Display `calendar`, and the labels of the `events` on its focused date, in their styles.
+    /// The calendar is usually given the same events, to style their dates.
+    pub fn with_events(calendar: Calendar<'a, S>, events: &'a CalendarEvents) -> Self {
+        Self::new(calendar, move |date| {
+            events
+                .events_on(date)
+                .iter()
+                .map(|event| ListItem::new(event.label.as_str()).style(event.style))
+                .collect()
+        })
+    }
+
+    /// Configure the list of entries, e.g. its styles and block. The closure is called with the
+    /// list at each render, and returns the list to draw.
+    pub fn list<F>(mut self, f: F) -> Self
+    where
+        F: Fn(EntryList<'a>) -> EntryList<'a> + 'a,
+    {
+        self.list = Some(Box::new(f));
+        self
+    }
+
+    /// The text drawn instead of the list when the focused date has no entries. Nothing is drawn
+    /// by default.
+    pub fn empty<T>(mut self, text: T) -> Self
+    where
+        T: Into<Spans<'a>>,
+    {
+        self.empty = Some(text.into());
+        self
+    }
+
+    /// The number of columns used by the calendar, including its block. Defaults to 21, the width
+    /// of a month.
+    pub fn calendar_width(mut self, width: u16) -> Self {
+        self.calendar_width = width;
+        self
+    }
+
+    /// The number of blank columns between the calendar and the entries. Defaults to 2.
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+}
+
+impl<'a, S: DateStyler> StatefulWidget for Agenda<'a, S> {
+    type State = AgendaState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let width = std::cmp::min(self.calendar_width, area.width);
+        let left = Rect { width, ..area };
+        StatefulWidget::render(self.calendar, left, buf, &mut state.calendar);
+
+        // the entry cursor starts at the top for each date
+        let date = state.calendar.focused();
+        let items = (self.entries)(date);
+        if state.entries_date != Some(date) {
+            state.entries_date = Some(date);
+            state.entries.select(0);
+        }
+        state.entry_count = items.len();
+
+        let offset = std::cmp::min(width.saturating_add(self.spacing), area.width);
+        let right = Rect {
+            x: area.x + offset,
+            width: area.width - offset,
+            ..area
+        };
+        if items.is_empty() {
+            if let Some(empty) = &self.empty {
+                if right.area() > 0 {
+                    buf.set_spans(right.x, right.y, empty, right.width);
+                }
+            }
+            return;
+        }
+        state.entries.resize(items.len());
+        let list = StyledList::new(items);
+        let list = match &self.list {
+            Some(f) => f(list),
+            None => list,
+        };
+        StatefulWidget::render(list, right, buf, &mut state.entries);
+    }
+}
+
+/// State for an [`Agenda`]: the [`CalendarState`] of the calendar, the [`ListState`] of the
+/// entries, and which of them has the keyboard focus.
+///
+/// The entry cursor moves back to the first entry when the focused date changes.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct AgendaState {
+    calendar: CalendarState,
+    entries: ListState,
+    pane: AgendaPane,
+    /// The date the entries were listed for by the last render.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    entries_date: Option<Date>,
+    /// The number of entries listed by the last render.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    entry_count: usize,
+}
+
+impl AgendaState {
+    /// Create a state with `focused` as the focused date, and the calendar having the keyboard
+    /// focus.
+    pub fn new(focused: Date) -> Self {
+        Self {
+            calendar: CalendarState::new(focused),
+            entries: ListState::new(1),
+            pane: AgendaPane::default(),
+            entries_date: None,
+            entry_count: 0,
+        }
+    }
+
+    /// Get the state of the calendar.
+    pub fn calendar(&self) -> &CalendarState {
+        &self.calendar
+    }
+
+    /// Get the state of the calendar, e.g. to move the focused date.
+    pub fn calendar_mut(&mut self) -> &mut CalendarState {
+        &mut self.calendar
+    }
+
+    /// Get the state of the list of entries.
+    pub fn entries(&self) -> &ListState {
+        &self.entries
+    }
+
+    /// Get the state of the list of entries, e.g. to move the entry cursor.
+    pub fn entries_mut(&mut self) -> &mut ListState {
+        &mut self.entries
+    }
+
+    /// Get the index of the entry under the cursor, or `None` if the focused date had no entries
+    /// at the last render.
+    pub fn selected_entry(&self) -> Option<usize> {
+        (self.entry_count > 0).then(|| self.entries.selected())
+    }
+
+    /// Get the half of the agenda with the keyboard focus.
+    pub fn pane(&self) -> AgendaPane {
+        self.pane
+    }
+
+    /// Give the keyboard focus to `pane`.
+    pub fn focus_pane(&mut self, pane: AgendaPane) {
+        self.pane = pane;
+    }
+
+    /// Move the keyboard focus to the other half of the agenda, e.g. when tab is pressed.
+    pub fn toggle_pane(&mut self) {
+        self.pane = match self.pane {
+            AgendaPane::Calendar => AgendaPane::Entries,
+            AgendaPane::Entries => AgendaPane::Calendar,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Style};
+    use time::macros::date;
+
+    use super::*;
+    use crate::calendar::{testing::render_widget_with, CalendarEventStore};
+
+    fn agenda<'a>() -> Agenda<'a, CalendarEventStore> {
+        let calendar = Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default());
+        Agenda::new(calendar, |date| match date.day() {
+            6 => vec![ListItem::new("Dentist"), ListItem::new("Lunch")],
+            _ => vec![],
+        })
+        .empty("Nothing")
+        .list(|l| l.selected_style(Style::default().bg(Color::Blue)))
+    }
+
+    #[test]
+    fn entries() {
+        let mut state = AgendaState::new(date!(2023 - 04 - 06));
+        state.entries_mut().select(1);
+        let snapshot = render_widget_with(agenda(), &mut state, 30, 6);
+        assert_eq!(
+            snapshot.lines(),
+            [
+                "                    1  Dentist",
+                "  2  3  4  5  6  7  8  Lunch  ",
+                "  9 10 11 12 13 14 15         ",
+                " 16 17 18 19 20 21 22         ",
+                " 23 24 25 26 27 28 29         ",
+                " 30                           ",
+            ]
+        );
+        // a new date starts at the first entry
+        assert_eq!(snapshot.buffer().get(23, 0).bg, Color::Blue);
+        assert_eq!(state.selected_entry(), Some(0));
+
+        state.entries_mut().next();
+        let snapshot = render_widget_with(agenda(), &mut state, 30, 6);
+        assert_eq!(state.selected_entry(), Some(1));
+        assert_eq!(snapshot.buffer().get(23, 1).bg, Color::Blue);
+
+        state.calendar_mut().move_days(1);
+        let lines = render_widget_with(agenda(), &mut state, 30, 6).lines();
+        assert_eq!(state.selected_entry(), None);
+        assert_eq!(&lines[0][20..], "1  Nothing");
+        assert_eq!(&lines[1][20..], "8         ");
+
+        // too narrow for the entries
+        for width in [0, 10, 22] {
+            render_widget_with(agenda(), &mut state, width, 6);
+        }
+    }
+
+    #[test]
+    fn events() {
+        let red = Style::default().fg(Color::Red);
+        let mut events = CalendarEvents::new();
+        events.add(date!(2023 - 04 - 06), "Dentist", red);
+        events.add(date!(2023 - 04 - 06), "Lunch", Style::default());
+        let calendar = Calendar::new(date!(2023 - 04 - 01), &events).show_today(false);
+        let mut state = AgendaState::new(date!(2023 - 04 - 06));
+        let agenda = Agenda::with_events(calendar, &events);
+        let snapshot = render_widget_with(agenda, &mut state, 30, 2);
+        assert_eq!(
+            snapshot.lines(),
+            [
+                "                    1  Dentist",
+                "  2  3  4  5  6  7  8  Lunch  ",
+            ]
+        );
+        // the date and the entry are in the event's style
+        assert_eq!(snapshot.buffer().get(13, 1).fg, Color::Red);
+        assert_eq!(snapshot.buffer().get(23, 0).fg, Color::Red);
+    }
+
+    #[test]
+    fn panes() {
+        let mut state = AgendaState::new(date!(2023 - 04 - 06));
+        assert_eq!(state.pane(), AgendaPane::Calendar);
+        state.toggle_pane();
+        assert_eq!(state.pane(), AgendaPane::Entries);
+        state.toggle_pane();
+        assert_eq!(state.pane(), AgendaPane::Calendar);
+        state.focus_pane(AgendaPane::Entries);
+        assert_eq!(state.pane(), AgendaPane::Entries);
+    }
+}
@@ -0,0 +1,72 @@
+use ratatui::text::Span;
+use time::Date;
+
+/// Provides short annotations for dates, such as moon phases, weather icons or a `✈` on travel
+/// days, drawn by a [`Calendar`](super::Calendar) added with its
+/// [`annotator`](super::Calendar::annotator) method.
+///
+/// A calendar can have several annotators. They are asked in the order they were added, and the
+/// first to annotate a date is the one drawn, so earlier annotators take priority over later ones.
+///
+/// Closures returning an `Option<Span<'static>>` for a date are annotators.
+pub trait Annotator {
+    /// Get the annotation of `date`, if it has one. The span's style is patched over the day's.
+    fn annotate(&self, date: Date) -> Option<Span<'_>>;
+}
+
+impl<F> Annotator for F
+where
+    F: Fn(Date) -> Option<Span<'static>>,
+{
+    fn annotate(&self, date: Date) -> Option<Span<'_>> {
+        self(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Style};
+    use time::macros::date;
+
+    use super::*;
+    use crate::calendar::{testing::render_month, Calendar, CalendarEventStore};
+
+    /// Annotates the 6th with a plane, and every 5th day with a star.
+    fn calendar<'a>() -> Calendar<'a, CalendarEventStore> {
+        Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+            .show_today(false)
+            .annotator(|d: Date| (d.day() == 6).then(|| Span::raw("✈")))
+            .annotator(|d: Date| {
+                d.day()
+                    .is_multiple_of(5)
+                    .then(|| Span::styled("**", Style::default().fg(Color::Yellow)))
+            })
+    }
+
+    #[test]
+    fn below() {
+        // two lines for each week
+        let snapshot = render_month(calendar().stretch(true), 21, 12);
+        assert_eq!(
+            snapshot.lines()[..4],
+            [
+                "                    1",
+                "                     ",
+                "  2  3  4  5  6  7  8",
+                "          ** ✈       ",
+            ]
+        );
+        assert_eq!(snapshot.style_at(10, 3).fg, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn inline() {
+        // wide cells have room after the number, and annotations are cut off at their edge
+        let lines = render_month(calendar().stretch(true), 42, 2).lines();
+        assert_eq!(lines[1], "   2     3     4     5 *   6 ✈   7     8  ");
+
+        // normal cells don't
+        let lines = render_month(calendar(), 21, 2).lines();
+        assert_eq!(lines[1], "  2  3  4  5  6  7  8");
+    }
+}
@@ -0,0 +1,38 @@
+use ratatui::{
+    style::Style,
+    text::{Span, Spans},
+};
+use time::Date;
+
+/// What a [cell renderer](super::Calendar::cell_renderer) knows about the day it draws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellContext {
+    /// Is the day focused?
+    pub focused: bool,
+    /// Is the day selected?
+    pub selected: bool,
+    /// Is the day today?
+    pub today: bool,
+    /// Is the day disabled? See [`Calendar::is_enabled`](super::Calendar::is_enabled).
+    pub disabled: bool,
+    /// Is the day in a month surrounding the displayed one?
+    pub surrounding: bool,
+    /// The number of columns of the cell.
+    pub width: u16,
+    /// The style the calendar gives the day, with all its styles patched together.
+    pub style: Style,
+}
+
+/// Draw a day as the calendar does by default: its day of the month in the cell's style, right
+/// aligned in narrow cells and centered in wider ones. Custom cell renderers can return this for
+/// the days they don't change.
+pub fn default_cell(date: Date, cx: CellContext) -> Spans<'static> {
+    let day = format!("{:2}", date.day());
+    let width = cx.width as usize;
+    let text = if width > 2 {
+        format!("{:^width$}", day)
+    } else {
+        format!("{:>width$}", day)
+    };
+    Span::styled(text, cx.style).into()
+}
@@ -0,0 +1,266 @@
+use std::{borrow::Cow, iter};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Span, Spans},
+    widgets::StatefulWidget,
+};
+use time::{Date, Duration};
+
+use super::{week_of, Calendar, CalendarState, DateStyler, COMPACT, NORMAL};
+
+/// Display the weeks of a [`Calendar`] as one scrolling strip, rather than a month at a time.
+///
+/// Weeks follow each other without breaks, so a week can hold the end of one month and the start
+/// of the next. The month's name is drawn on its own row above the week containing its 1st, in the
+/// calendar's [`show_month`](Calendar::show_month) style. These rows are only labels: the focus
+/// moves between the weeks around them.
+///
+/// The view is driven by a [`CalendarState`], and scrolls a week at a time to keep the focused week
+/// in view, so [`move_weeks`](CalendarState::move_weeks) and the other navigation methods scroll
+/// smoothly across months. The top of the view is kept in the state between renders, and only
+/// moves when the focus would otherwise be out of view.
+///
+/// Days are styled as by the calendar, with every day treated as part of the displayed month.
+/// The calendar's weekday header is drawn above the strip, and stays put as it scrolls.
+/// [`date_at`](CalendarState::date_at) doesn't find dates drawn by this widget.
+pub struct ContinuousCalendar<'a, S: DateStyler> {
+    calendar: Calendar<'a, S>,
+}
+
+impl<'a, S: DateStyler> ContinuousCalendar<'a, S> {
+    /// Display the weeks of `calendar`.
+    pub fn new(calendar: Calendar<'a, S>) -> Self {
+        Self { calendar }
+    }
+}
+
+/// A row of the strip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row {
+    /// The name of the month starting on this date.
+    Label(Date),
+    /// The week starting on this date.
+    Week(Date),
+}
+
+/// The 1st of the month in the week starting on `week`, if there is one.
+fn month_start(week: Date) -> Option<Date> {
+    (0..7)
+        .filter_map(|i| week.checked_add(Duration::days(i)))
+        .find(|d| d.day() == 1)
+}
+
+/// The rows from the week starting on `top`. The label above `top` is included if `label_top` is
+/// set.
+fn rows(top: Date, label_top: bool) -> impl Iterator<Item = Row> {
+    iter::successors(Some(top), |week| week.checked_add(Duration::WEEK)).flat_map(move |week| {
+        let first = month_start(week).filter(|_| week != top || label_top);
+        first.map(Row::Label).into_iter().chain([Row::Week(week)])
+    })
+}
+
+/// The earliest week from which the rows to the end of the week starting on `focus`, with their
+/// labels, fit in `height` rows. This is `focus` itself if it doesn't fit with its label.
+fn fitting_top(focus: Date, height: usize) -> Date {
+    let mut rows = 0;
+    iter::successors(Some(focus), |week| week.checked_sub(Duration::WEEK))
+        .take_while(|week| {
+            rows += 1 + usize::from(month_start(*week).is_some());
+            rows <= height
+        })
+        .last()
+        .unwrap_or(focus)
+}
+
+/// The number of rows from the week starting on `top` to the end of the week starting on `focus`.
+fn rows_to(top: Date, focus: Date) -> usize {
+    rows(top, true)
+        .position(|row| row == Row::Week(focus))
+        .map_or(0, |n| n + 1)
+}
+
+impl<'a, S: DateStyler> StatefulWidget for ContinuousCalendar<'a, S> {
+    type State = CalendarState;
+
+    fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let cal = &mut self.calendar;
+        cal.display_date = state.focused;
+        state.focused_holiday = cal.holiday(state.focused).map(Cow::into_owned);
+        state.bounds = cal.state_bounds();
+        state.today = Some(cal.today_date());
+        // the days aren't laid out as a month, so they can't be found by position
+        state.set_cells(Vec::new());
+
+        let area = cal.render_frame(area, buf);
+        let layout = if cal.compact || area.width < NORMAL.width() {
+            COMPACT
+        } else {
+            NORMAL
+        };
+        let area = cal.draw_weekdays(area, buf, layout, cal.weekday_rows());
+
+        // scroll down just far enough for the focused week to fit
+        let height = usize::from(area.height);
+        let focus = week_of(state.focused, cal.week_start);
+        let top = state
+            .scroll
+            .map(|top| week_of(top, cal.week_start))
+            .filter(|top| *top <= focus)
+            .map_or(focus, |top| top.max(fitting_top(focus, height)));
+        state.scroll = Some(top);
+        // drop the label above the focused week if only the week fits
+        let label_top = rows_to(top, focus) <= height;
+
+        let mut last = None;
+        for (y, row) in (area.y..area.bottom()).zip(rows(top, label_top)) {
+            match row {
+                Row::Label(first) => {
+                    cal.display_date = first;
+                    let label = Span::styled(cal.month_title(), cal.show_month.unwrap_or_default());
+                    buf.set_spans(area.x, y, &Spans::from(label), area.width);
+                }
+                Row::Week(start) => {
+                    last = Some(start);
+                    let mut line = Spans(Vec::with_capacity(14));
+                    let days = iter::successors(Some(start), |d| d.next_day()).take(7);
+                    for (i, day) in days.enumerate() {
+                        let gap = match i {
+                            0 => Style::default(),
+                            _ => cal.default_bg(),
+                        };
+                        line.0.push(Span::styled(layout.gap_str(), gap));
+                        // every day is drawn as part of the displayed month
+                        cal.display_date = day;
+                        let span = cal.format_date(day, Some(state));
+                        line.0
+                            .push(Span::styled(span.content.into_owned(), span.style));
+                    }
+                    buf.set_spans(area.x, y, &line, area.width);
+                }
+            }
+        }
+        state.visible = last
+            .filter(|_| area.width > 0)
+            .map(|last| top..=last.checked_add(Duration::days(6)).unwrap_or(Date::MAX));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+    use time::macros::date;
+
+    use super::*;
+    use crate::calendar::{
+        testing::{render_widget_with, Snapshot},
+        CalendarEventStore,
+    };
+
+    fn snapshot(state: &mut CalendarState, height: u16) -> Snapshot {
+        let cal = Calendar::new(date!(2000 - 01 - 01), CalendarEventStore::default())
+            .focused_style(Style::default().bg(Color::Blue));
+        render_widget_with(ContinuousCalendar::new(cal), state, 21, height)
+    }
+
+    fn render(state: &mut CalendarState, height: u16) -> Vec<String> {
+        snapshot(state, height).lines()
+    }
+
+    #[test]
+    fn scrolling() {
+        let mut state = CalendarState::new(date!(2023 - 03 - 20));
+        assert_eq!(
+            render(&mut state, 4),
+            [
+                " 19 20 21 22 23 24 25",
+                "April 2023           ",
+                " 26 27 28 29 30 31  1",
+                "  2  3  4  5  6  7  8",
+            ]
+        );
+        assert_eq!(
+            state.visible_range(),
+            Some(date!(2023 - 03 - 19)..=date!(2023 - 04 - 08))
+        );
+
+        // moving within the view doesn't scroll
+        state.move_weeks(2);
+        assert_eq!(render(&mut state, 4)[0], " 19 20 21 22 23 24 25");
+        // moving past the end scrolls a week at a time, taking the label with the week
+        state.move_weeks(1);
+        assert_eq!(
+            render(&mut state, 4),
+            [
+                "April 2023           ",
+                " 26 27 28 29 30 31  1",
+                "  2  3  4  5  6  7  8",
+                "  9 10 11 12 13 14 15",
+            ]
+        );
+        // moving up scrolls to the focused week, with its label
+        state.move_weeks(-1);
+        assert_eq!(render(&mut state, 4)[0], "April 2023           ");
+        state.move_weeks(-2);
+        assert_eq!(render(&mut state, 4)[0], " 19 20 21 22 23 24 25");
+        state.move_weeks(-3);
+        assert_eq!(
+            render(&mut state, 4)[..2],
+            ["March 2023           ", " 26 27 28  1  2  3  4"]
+        );
+    }
+
+    #[test]
+    fn fitting_top() {
+        // the same as scrolling a week at a time from an earlier top until the focus fits
+        let start = date!(2023 - 01 - 01);
+        for weeks in 0..60 {
+            let focus = start + Duration::weeks(weeks);
+            for height in 0..12 {
+                let mut top = start;
+                while top < focus && rows_to(top, focus) > height {
+                    top += Duration::WEEK;
+                }
+                let fitting = super::fitting_top(focus, height);
+                assert_eq!(start.max(fitting), top, "{focus} in {height} rows");
+            }
+        }
+
+        // a long jump down scrolls straight to the focus
+        let mut state = CalendarState::new(date!(2023 - 03 - 20));
+        render(&mut state, 4);
+        state.jump_to(date!(2123 - 04 - 14));
+        assert_eq!(
+            render(&mut state, 4),
+            [
+                "April 2123           ",
+                " 28 29 30 31  1  2  3",
+                "  4  5  6  7  8  9 10",
+                " 11 12 13 14 15 16 17",
+            ]
+        );
+    }
+
+    #[test]
+    fn focused_week_is_shown() {
+        // too short for the label and the week
+        let mut state = CalendarState::new(date!(2023 - 04 - 01));
+        assert_eq!(render(&mut state, 1), [" 26 27 28 29 30 31  1"]);
+        state.move_days(1);
+        assert_eq!(render(&mut state, 1), ["  2  3  4  5  6  7  8"]);
+        assert_eq!(render(&mut state, 0), Vec::<String>::new());
+        assert_eq!(state.visible_range(), None);
+
+        // the focus is styled in every month
+        state.focus(date!(2023 - 03 - 31));
+        let snapshot = snapshot(&mut state, 2);
+        assert_eq!(
+            snapshot.lines(),
+            ["April 2023           ", " 26 27 28 29 30 31  1"]
+        );
+        assert_eq!(snapshot.buffer().get(16, 1).bg, Color::Blue);
+        assert_eq!(state.date_at(16, 1), None);
+    }
+}
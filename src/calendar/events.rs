@@ -0,0 +1,215 @@
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    collections::{btree_map, BTreeMap},
+    iter::Flatten,
+};
+
+use ratatui::style::Style;
+use time::Date;
+
+use super::DateStyler;
+
+/// An event in [`CalendarEvents`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct CalendarEvent {
+    /// The date of the event.
+    pub date: Date,
+    /// The text listed for the event, e.g. by an [`Agenda`](super::Agenda).
+    pub label: String,
+    /// The style of the event's date in a calendar, and of its entry in an agenda.
+    pub style: Style,
+}
+
+/// Labelled events on dates, kept between frames rather than rebuilt for each render.
+///
+/// The events are a [`DateStyler`], so they can be drawn by a [`Calendar`](super::Calendar),
+/// styling each date with the styles of its events patched in the order they were added. With
+/// the `styled_list` feature, [`Agenda::with_events`](super::Agenda::with_events) lists the events
+/// of the focused date.
+///
+/// With the `serde` feature, the events are serialized as a list, so apps can save them as they
+/// are.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(from = "Vec<CalendarEvent>", into = "Vec<CalendarEvent>")
+)]
+pub struct CalendarEvents {
+    events: BTreeMap<Date, Vec<CalendarEvent>>,
+}
+
+impl CalendarEvents {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an event on `date`, after any events already on it.
+    pub fn add<L>(&mut self, date: Date, label: L, style: Style)
+    where
+        L: Into<String>,
+    {
+        let event = CalendarEvent {
+            date,
+            label: label.into(),
+            style,
+        };
+        self.events.entry(date).or_default().push(event);
+    }
+
+    /// Remove the first event on `date` labelled `label`, returning it.
+    pub fn remove(&mut self, date: Date, label: &str) -> Option<CalendarEvent> {
+        let events = self.events.get_mut(&date)?;
+        let i = events.iter().position(|event| event.label == label)?;
+        let event = events.remove(i);
+        if events.is_empty() {
+            self.events.remove(&date);
+        }
+        Some(event)
+    }
+
+    /// Get the events on `date`, in the order they were added.
+    pub fn events_on(&self, date: Date) -> &[CalendarEvent] {
+        self.events.get(&date).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterate over the events, by date.
+    pub fn iter(&self) -> impl Iterator<Item = &CalendarEvent> + '_ {
+        self.events.values().flatten()
+    }
+
+    /// Get the number of events.
+    pub fn len(&self) -> usize {
+        self.events.values().map(Vec::len).sum()
+    }
+
+    /// Are there no events?
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl DateStyler for CalendarEvents {
+    fn get_style(&self, date: Date) -> Style {
+        self.events_on(date)
+            .iter()
+            .fold(Style::default(), |style, event| style.patch(event.style))
+    }
+}
+
+impl Extend<CalendarEvent> for CalendarEvents {
+    fn extend<T: IntoIterator<Item = CalendarEvent>>(&mut self, iter: T) {
+        for event in iter {
+            self.events.entry(event.date).or_default().push(event);
+        }
+    }
+}
+
+impl FromIterator<CalendarEvent> for CalendarEvents {
+    fn from_iter<T: IntoIterator<Item = CalendarEvent>>(iter: T) -> Self {
+        let mut events = Self::default();
+        events.extend(iter);
+        events
+    }
+}
+
+impl From<Vec<CalendarEvent>> for CalendarEvents {
+    fn from(events: Vec<CalendarEvent>) -> Self {
+        events.into_iter().collect()
+    }
+}
+
+impl From<CalendarEvents> for Vec<CalendarEvent> {
+    fn from(events: CalendarEvents) -> Self {
+        events.into_iter().collect()
+    }
+}
+
+impl IntoIterator for CalendarEvents {
+    type Item = CalendarEvent;
+    type IntoIter = Flatten<btree_map::IntoValues<Date, Vec<CalendarEvent>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.into_values().flatten()
+    }
+}
+
+impl<'a> IntoIterator for &'a CalendarEvents {
+    type Item = &'a CalendarEvent;
+    type IntoIter = Flatten<btree_map::Values<'a, Date, Vec<CalendarEvent>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.values().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Modifier};
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn events() {
+        let red = Style::default().fg(Color::Red);
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let mut events = CalendarEvents::new();
+        events.add(date!(2023 - 04 - 12), "Dentist", red);
+        events.add(date!(2023 - 04 - 06), "Lunch", Style::default());
+        events.add(date!(2023 - 04 - 12), "Meeting", bold);
+        assert_eq!(events.len(), 3);
+
+        let labels = |events: &CalendarEvents| {
+            events
+                .iter()
+                .map(|event| event.label.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        assert_eq!(labels(&events), "Lunch,Dentist,Meeting");
+        assert_eq!(events.events_on(date!(2023 - 04 - 12)).len(), 2);
+        assert_eq!(events.events_on(date!(2023 - 04 - 13)), []);
+        assert_eq!(events.get_style(date!(2023 - 04 - 12)), red.patch(bold));
+
+        assert_eq!(events.remove(date!(2023 - 04 - 12), "Lunch"), None);
+        let removed = events.remove(date!(2023 - 04 - 06), "Lunch").unwrap();
+        assert_eq!(removed.date, date!(2023 - 04 - 06));
+        assert_eq!(labels(&events), "Dentist,Meeting");
+        // dates without events are dropped
+        assert!(!events.events.contains_key(&date!(2023 - 04 - 06)));
+
+        // round trips through the list it is serialized as
+        let list = Vec::from(events.clone());
+        assert_eq!(CalendarEvents::from(list), events);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let red = Style::default().fg(Color::Red);
+        let mut events = CalendarEvents::new();
+        events.add(date!(2023 - 04 - 12), "Dentist", red);
+        events.add(date!(2023 - 04 - 06), "Lunch", Style::default());
+        events.add(date!(2023 - 04 - 12), "Meeting", Style::default());
+
+        // a list of the events by date, in the order they were added on each date
+        let json = serde_json::to_value(&events).unwrap();
+        let event = |date: Date, label: &str, style: Style| serde_json::json!({ "date": date, "label": label, "style": style });
+        assert_eq!(
+            json,
+            serde_json::json!([
+                event(date!(2023 - 04 - 06), "Lunch", Style::default()),
+                event(date!(2023 - 04 - 12), "Dentist", red),
+                event(date!(2023 - 04 - 12), "Meeting", Style::default()),
+            ])
+        );
+        assert_eq!(
+            serde_json::from_value::<CalendarEvents>(json).unwrap(),
+            events
+        );
+    }
+}
@@ -0,0 +1,282 @@
+use std::borrow::Cow;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{StatefulWidget, Widget},
+};
+use time::Date;
+
+use super::{state::add_months, Calendar, CalendarState, Cells, DateStyler};
+
+/// The width of a month.
+const MONTH_WIDTH: u16 = 21;
+/// The number of week rows kept for each month, so months in a row line up.
+const WEEK_ROWS: u16 = 6;
+
+/// Display several consecutive months of a [`Calendar`] in a grid, e.g. a quarter or a year.
+///
+/// Every month is drawn with the calendar's settings, including its month and weekday headers.
+/// The calendar's block is drawn around the whole grid, and the month headers stay on their own
+/// lines whatever the [`HeaderMode`](super::HeaderMode).
+///
+/// The months shown are a page of `months` months containing the displayed date. Pages are aligned
+/// to the start of the year when `months` divides 12, so 3 months shows a quarter and 12 months
+/// shows a year. Rendered with a [`CalendarState`], the page containing the focused date is shown,
+/// and only the month containing the focus draws it. The dates drawn can be found by
+/// [`date_at`](CalendarState::date_at) and [`cell_rect`](CalendarState::cell_rect).
+///
+/// Months are laid out left to right, in up to [`columns`](Self::columns) columns. When the area is
+/// too narrow for all the columns, fewer are used, and months that don't fit in the height are
+/// skipped rather than drawn partially.
+pub struct MonthsGrid<'a, S: DateStyler> {
+    calendar: Calendar<'a, S>,
+    months: u8,
+    columns: u8,
+    column_spacing: u16,
+    row_spacing: u16,
+}
+
+impl<'a, S: DateStyler> MonthsGrid<'a, S> {
+    /// Display `months` months of `calendar`.
+    pub fn new(calendar: Calendar<'a, S>, months: u8) -> Self {
+        Self {
+            calendar,
+            months,
+            columns: 3,
+            column_spacing: 2,
+            row_spacing: 1,
+        }
+    }
+
+    /// The number of months in each row of the grid. Defaults to 3.
+    pub fn columns(mut self, n: u8) -> Self {
+        self.columns = n;
+        self
+    }
+
+    /// The number of blank columns between months side by side, and blank rows between rows of
+    /// months. Defaults to 2 columns and 1 row.
+    pub fn spacing(mut self, columns: u16, rows: u16) -> Self {
+        self.column_spacing = columns;
+        self.row_spacing = rows;
+        self
+    }
+
+    /// The first day of the first month on the page containing `date`.
+    fn first_month(&self, date: Date) -> Option<Date> {
+        let index = date.month() as i32 - 1;
+        let first = index - index.rem_euclid(self.months.max(1).into());
+        add_months(date.replace_day(1).ok()?, first - index)
+    }
+
+    fn render_grid(
+        mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: Option<&CalendarState>,
+    ) -> Vec<Cells> {
+        let area = self.calendar.render_frame(area, buf);
+        let mut cells = Vec::new();
+        let Some(mut month) = self.first_month(self.calendar.display_date) else {
+            return cells;
+        };
+
+        let month_height =
+            self.calendar.show_month.is_some() as u16 + self.calendar.weekday_rows() + WEEK_ROWS;
+        let column_step = MONTH_WIDTH.saturating_add(self.column_spacing);
+        let row_step = month_height.saturating_add(self.row_spacing);
+        // the spacing isn't needed after the last column, and u32 leaves room to add it
+        let spacing = u32::from(self.column_spacing);
+        let fit = (u32::from(area.width) + spacing) / (u32::from(MONTH_WIDTH) + spacing);
+        let columns = std::cmp::min(self.columns.into(), fit) as u16;
+        if columns == 0 {
+            return cells;
+        }
+
+        for i in 0..u16::from(self.months) {
+            let x = (i % columns).saturating_mul(column_step);
+            let y = (i / columns).saturating_mul(row_step);
+            if y.saturating_add(month_height) > area.height {
+                break;
+            }
+            let rect = Rect::new(area.x + x, area.y + y, MONTH_WIDTH, month_height);
+            self.calendar.display_date = month;
+            cells.push(self.calendar.draw_month(rect, buf, state));
+            match add_months(month, 1) {
+                Some(next) => month = next,
+                None => break,
+            }
+        }
+        cells
+    }
+}
+
+impl<'a, S: DateStyler> Widget for MonthsGrid<'a, S> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.render_grid(area, buf, None);
+    }
+}
+
+impl<'a, S: DateStyler> StatefulWidget for MonthsGrid<'a, S> {
+    type State = CalendarState;
+
+    fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        self.calendar.display_date = state.focused;
+        state.focused_holiday = self.calendar.holiday(state.focused).map(Cow::into_owned);
+        state.bounds = self.calendar.state_bounds();
+        state.today = Some(self.calendar.today_date());
+        let cells = self.render_grid(area, buf, Some(state));
+        state.set_cells(cells);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Style};
+    use time::{macros::date, Month};
+
+    use super::*;
+    use crate::calendar::{
+        testing::{render_month, render_month_with, render_widget, render_widget_with},
+        CalendarEventStore, StaticHolidays,
+    };
+
+    fn calendar(date: Date) -> Calendar<'static, CalendarEventStore> {
+        Calendar::new(date, CalendarEventStore::default())
+            .show_month(Style::default())
+            .focused_style(Style::default().bg(Color::Blue))
+            .show_today(false)
+    }
+
+    /// Join the lines of months drawn side by side, `gap` apart.
+    fn side_by_side(months: &[Vec<String>], gap: &str) -> Vec<String> {
+        (0..months[0].len())
+            .map(|y| {
+                let line: Vec<&str> = months.iter().map(|m| m[y].as_str()).collect();
+                line.join(gap)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn quarter() {
+        let mut state = CalendarState::new(date!(2023 - 05 - 10));
+        let grid = MonthsGrid::new(calendar(date!(2023 - 01 - 01)), 3);
+        let grid = render_widget_with(grid, &mut state, 67, 7);
+
+        // the same as each month drawn on its own
+        let months = [
+            render_month(calendar(date!(2023 - 04 - 01)), 21, 7),
+            render_month_with(calendar(date!(2023 - 01 - 01)), &mut state, 21, 7),
+            render_month(calendar(date!(2023 - 06 - 01)), 21, 7),
+        ];
+        let lines: Vec<_> = months.iter().map(|m| m.lines()).collect();
+        assert_eq!(grid.lines(), side_by_side(&lines, "  "));
+        let legend = [('b', Style::default().bg(Color::Blue))];
+        let styles: Vec<_> = months.iter().map(|m| m.style_map(&legend)).collect();
+        assert_eq!(grid.style_map(&legend), side_by_side(&styles, ".."));
+    }
+
+    #[test]
+    fn cell_rect() {
+        let mut state = CalendarState::new(date!(2023 - 05 - 10));
+        let grid = MonthsGrid::new(
+            calendar(date!(2023 - 01 - 01)).show_surrounding(Style::default()),
+            3,
+        );
+        render_widget_with(grid, &mut state, 67, 7);
+
+        assert_eq!(
+            state.cell_rect(date!(2023 - 05 - 10)),
+            Some(Rect::new(33, 2, 2, 1))
+        );
+        // drawn in April and May, and found in its own month
+        assert_eq!(
+            state.cell_rect(date!(2023 - 04 - 30)),
+            Some(Rect::new(1, 6, 2, 1))
+        );
+        assert_eq!(state.cell_rect(date!(2023 - 07 - 10)), None);
+        // all the months, with their surrounding days
+        assert_eq!(
+            state.visible_range(),
+            Some(date!(2023 - 03 - 26)..=date!(2023 - 07 - 01))
+        );
+    }
+
+    #[test]
+    fn paging() {
+        let grid = MonthsGrid::new(calendar(date!(2023 - 01 - 01)), 3);
+        let mut state = CalendarState::new(date!(2023 - 02 - 14));
+        assert_eq!(
+            grid.first_month(state.focused()),
+            Some(date!(2023 - 01 - 01))
+        );
+        state.move_months(3);
+        assert_eq!(
+            grid.first_month(state.focused()),
+            Some(date!(2023 - 04 - 01))
+        );
+        state.prev_year();
+        assert_eq!(
+            grid.first_month(state.focused()),
+            Some(date!(2022 - 04 - 01))
+        );
+    }
+
+    #[test]
+    fn focused_holiday() {
+        let mut holidays = StaticHolidays::default();
+        holidays.add(date!(2023 - 12 - 25), "Christmas Day");
+        let grid = || {
+            let cal = Calendar::new(date!(2023 - 01 - 01), CalendarEventStore::default())
+                .holidays(&holidays);
+            MonthsGrid::new(cal, 3)
+        };
+
+        let mut state = CalendarState::new(date!(2023 - 12 - 25));
+        render_widget_with(grid(), &mut state, 67, 7);
+        assert_eq!(state.focused_holiday(), Some("Christmas Day"));
+
+        state.move_days(1);
+        render_widget_with(grid(), &mut state, 67, 7);
+        assert_eq!(state.focused_holiday(), None);
+    }
+
+    #[test]
+    fn huge_spacing() {
+        let grid = || MonthsGrid::new(calendar(date!(2023 - 01 - 01)), 4);
+        // one month fits, and the others are out of reach
+        let lines = render_widget(grid().spacing(u16::MAX, u16::MAX), 50, 16).lines();
+        let january = render_month(calendar(date!(2023 - 01 - 01)), 21, 7).lines();
+        for (line, month) in lines.iter().zip(&january) {
+            assert_eq!(line, &format!("{month:50}"));
+        }
+        assert!(lines[7..].iter().all(|line| line.trim().is_empty()));
+
+        // nothing fits in a narrow area
+        let lines = render_widget(grid().spacing(u16::MAX, 0), 20, 7).lines();
+        assert!(lines.iter().all(|line| line.trim().is_empty()));
+    }
+
+    #[test]
+    fn year_skips_what_does_not_fit() {
+        // room for 2 columns and 2 rows
+        let grid = MonthsGrid::new(calendar(date!(2023 - 07 - 04)), 12).columns(4);
+        let lines = render_widget(grid, 50, 16).lines();
+
+        let month = |m| {
+            let date = Date::from_calendar_date(2023, m, 1).unwrap();
+            render_month(calendar(date), 21, 7).lines()
+        };
+        let mut expected = side_by_side(&[month(Month::January), month(Month::February)], "  ");
+        expected.push(String::new());
+        expected.extend(side_by_side(
+            &[month(Month::March), month(Month::April)],
+            "  ",
+        ));
+        expected.push(String::new());
+        let expected: Vec<_> = expected.iter().map(|l| format!("{l:50}")).collect();
+        assert_eq!(lines, expected);
+    }
+}
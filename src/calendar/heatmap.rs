@@ -0,0 +1,126 @@
+use ratatui::style::{Color, Style};
+use time::Date;
+
+use super::{Calendar, DateStyler};
+
+/// A list of colors that values from 0.0 to 1.0 are spread across, for a [`Heatmap`].
+///
+/// The first stop is used for 0.0, the last for 1.0, and the others are evenly spaced between.
+/// Between two [`Color::Rgb`] stops the color is interpolated linearly, and between any other
+/// colors (which can't be mixed) the nearest stop is used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorRamp(Vec<Color>);
+
+impl ColorRamp {
+    /// Create a ramp through `stops`, in order.
+    pub fn new<I>(stops: I) -> Self
+    where
+        I: IntoIterator<Item = Color>,
+    {
+        Self(stops.into_iter().collect())
+    }
+
+    /// Get the color for `value`. Values are clamped to the range 0.0 to 1.0. Returns `None` if
+    /// the ramp has no stops, or `value` is NaN.
+    pub fn color(&self, value: f32) -> Option<Color> {
+        if value.is_nan() {
+            return None;
+        }
+        let last = self.0.len().checked_sub(1)?;
+        let pos = value.clamp(0.0, 1.0) * last as f32;
+        let i = std::cmp::min(pos as usize, last);
+        let frac = pos - i as f32;
+        let (from, to) = (self.0[i], self.0[std::cmp::min(i + 1, last)]);
+        Some(match (from, to) {
+            (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+                let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * frac).round() as u8;
+                Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+            }
+            _ if frac < 0.5 => from,
+            _ => to,
+        })
+    }
+}
+
+/// A [`DateStyler`] that sets the background of each date to a color from a [`ColorRamp`], by the
+/// date's value. Dates without a value aren't styled.
+pub struct Heatmap<F> {
+    values: F,
+    ramp: ColorRamp,
+}
+
+impl<F> Heatmap<F>
+where
+    F: Fn(Date) -> Option<f32>,
+{
+    /// Color dates by the value returned by `values`, from 0.0 to 1.0.
+    pub fn new(values: F, ramp: ColorRamp) -> Self {
+        Self { values, ramp }
+    }
+}
+
+impl<F> DateStyler for Heatmap<F>
+where
+    F: Fn(Date) -> Option<f32>,
+{
+    fn get_style(&self, date: Date) -> Style {
+        match (self.values)(date).and_then(|v| self.ramp.color(v)) {
+            Some(c) => Style::default().bg(c),
+            None => Style::default(),
+        }
+    }
+}
+
+impl<'a, F> Calendar<'a, Heatmap<F>>
+where
+    F: Fn(Date) -> Option<f32>,
+{
+    /// Construct a calendar for the `display_date`, with the dates colored by a [`Heatmap`] of
+    /// `values` across the `ramp`. The today, selected and focused styles are patched over the
+    /// heatmap colors.
+    pub fn heatmap(display_date: Date, values: F, ramp: ColorRamp) -> Self {
+        Self::new(display_date, Heatmap::new(values, ramp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn ramp() {
+        let rgb = ColorRamp::new([Color::Rgb(0, 0, 0), Color::Rgb(200, 100, 50)]);
+        assert_eq!(rgb.color(0.0), Some(Color::Rgb(0, 0, 0)));
+        assert_eq!(rgb.color(0.5), Some(Color::Rgb(100, 50, 25)));
+        assert_eq!(rgb.color(2.0), Some(Color::Rgb(200, 100, 50)));
+        assert_eq!(rgb.color(-1.0), Some(Color::Rgb(0, 0, 0)));
+        assert_eq!(rgb.color(f32::NAN), None);
+
+        let named = ColorRamp::new([Color::Black, Color::Green, Color::Rgb(0, 255, 0)]);
+        assert_eq!(named.color(0.2), Some(Color::Black));
+        assert_eq!(named.color(0.3), Some(Color::Green));
+        assert_eq!(named.color(0.75), Some(Color::Rgb(0, 255, 0)));
+
+        assert_eq!(ColorRamp::new([]).color(0.5), None);
+        assert_eq!(ColorRamp::new([Color::Red]).color(0.5), Some(Color::Red));
+    }
+
+    #[test]
+    fn calendar() {
+        let ramp = ColorRamp::new([Color::Rgb(0, 0, 0), Color::Rgb(0, 200, 0)]);
+        let values = |d: Date| (d.day() <= 2).then(|| d.day() as f32 / 2.0);
+        let cal = Calendar::heatmap(date!(2023 - 04 - 01), values, ramp)
+            .today(date!(2023 - 04 - 02))
+            .today_style(Style::default().bg(Color::Red));
+
+        let area = Rect::new(0, 0, 21, 6);
+        let mut buf = Buffer::empty(area);
+        cal.render(area, &mut buf);
+        assert_eq!(buf.get(19, 0).bg, Color::Rgb(0, 100, 0));
+        assert_eq!(buf.get(1, 1).bg, Color::Red);
+        assert_eq!(buf.get(4, 1).bg, Color::Reset);
+    }
+}
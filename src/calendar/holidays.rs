@@ -0,0 +1,70 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use time::Date;
+
+/// Provides the names of holidays for a [`Calendar`](super::Calendar), which styles them with its
+/// `holiday_style`.
+pub trait HolidayProvider {
+    /// Get the name of the holiday on `date`, if it is one.
+    fn holiday(&self, date: Date) -> Option<Cow<'_, str>>;
+}
+
+/// A simple [`HolidayProvider`] based on a [`HashMap`] of dates to names.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StaticHolidays(pub HashMap<Date, String>);
+
+impl StaticHolidays {
+    /// Add a holiday named `name` on `date`, replacing any holiday already on that date.
+    pub fn add<S: Into<String>>(&mut self, date: Date, name: S) {
+        let _ = self.0.insert(date, name.into());
+    }
+}
+
+impl HolidayProvider for StaticHolidays {
+    fn holiday(&self, date: Date) -> Option<Cow<'_, str>> {
+        self.0.get(&date).map(|name| Cow::Borrowed(name.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{
+        buffer::Buffer,
+        layout::Rect,
+        style::{Color, Style},
+        widgets::StatefulWidget,
+    };
+    use time::macros::date;
+
+    use super::*;
+    use crate::calendar::{Calendar, CalendarEventStore, CalendarState};
+
+    #[test]
+    fn holidays() {
+        let mut holidays = StaticHolidays::default();
+        holidays.add(date!(2023 - 12 - 25), "Christmas Day");
+        let red = Style::default().fg(Color::Red);
+        let cal = || {
+            Calendar::new(date!(2023 - 12 - 01), CalendarEventStore::default())
+                .holidays(&holidays)
+                .holiday_style(red)
+        };
+
+        let area = Rect::new(0, 0, 21, 6);
+        let mut buf = Buffer::empty(area);
+        let mut state = CalendarState::new(date!(2023 - 12 - 25));
+        StatefulWidget::render(cal(), area, &mut buf, &mut state);
+        assert_eq!(state.focused_holiday(), Some("Christmas Day"));
+        // December 25th 2023 is a Monday
+        assert_eq!(buf.get(4, 4).fg, Color::Red);
+
+        assert!(cal().is_enabled(date!(2023 - 12 - 25)));
+        assert!(!cal()
+            .disable_holidays(true)
+            .is_enabled(date!(2023 - 12 - 25)));
+
+        state.move_days(1);
+        StatefulWidget::render(cal(), area, &mut buf, &mut state);
+        assert_eq!(state.focused_holiday(), None);
+    }
+}
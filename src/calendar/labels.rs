@@ -0,0 +1,103 @@
+use time::Weekday;
+use unicode_width::UnicodeWidthStr;
+
+/// The weekday names drawn in the header of a [`Calendar`](super::Calendar), added with its
+/// [`labels`](super::Calendar::labels) method.
+///
+/// Each weekday has three forms, and the calendar picks the one that fits its days: the long form
+/// when days are at least 4 columns wide, e.g. when [stretched](super::Calendar::stretch), the
+/// short form in normal days, and the narrow form in the [`compact`](super::Calendar::compact)
+/// layout. A form wider than the days falls back to a shorter one, so labels never spill into the
+/// next column. Widths are measured in columns, so double width labels, e.g. `日`, stay aligned.
+///
+/// The default labels are English, like `Mon`, `Mo` and `M`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarLabels {
+    /// The long, short and narrow forms of each weekday, from Monday.
+    weekdays: [[String; 3]; 7],
+}
+
+impl Default for CalendarLabels {
+    fn default() -> Self {
+        let weekdays = [
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+            Weekday::Sunday,
+        ]
+        .map(|day| {
+            let name = day.to_string();
+            [3, 2, 1].map(|n| name[..n].to_string())
+        });
+        Self { weekdays }
+    }
+}
+
+impl CalendarLabels {
+    /// Create the English labels.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `long`, `short` and `narrow` forms of the name of `day`.
+    pub fn weekday<L, S, N>(mut self, day: Weekday, long: L, short: S, narrow: N) -> Self
+    where
+        L: Into<String>,
+        S: Into<String>,
+        N: Into<String>,
+    {
+        self.weekdays[usize::from(day.number_days_from_monday())] =
+            [long.into(), short.into(), narrow.into()];
+        self
+    }
+
+    /// The name of `day` for days `width` columns wide, cut to the width if even the narrow form
+    /// doesn't fit.
+    pub(super) fn weekday_label(&self, day: Weekday, width: u16, compact: bool) -> String {
+        let forms = &self.weekdays[usize::from(day.number_days_from_monday())];
+        let first = match width {
+            _ if compact => 2,
+            4.. => 0,
+            _ => 1,
+        };
+        let width = usize::from(width);
+        if let Some(form) = forms[first..].iter().find(|form| form.width() <= width) {
+            return form.clone();
+        }
+        let mut label = String::new();
+        for c in forms[2].chars() {
+            let mut next = label.clone();
+            next.push(c);
+            if next.width() > width {
+                break;
+            }
+            label = next;
+        }
+        label
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forms() {
+        let labels = CalendarLabels::new().weekday(Weekday::Sunday, "日曜", "日", "日");
+        assert_eq!(labels.weekday_label(Weekday::Monday, 4, false), "Mon");
+        assert_eq!(labels.weekday_label(Weekday::Monday, 2, false), "Mo");
+        assert_eq!(labels.weekday_label(Weekday::Monday, 2, true), "M");
+        // double width forms are measured in columns
+        assert_eq!(labels.weekday_label(Weekday::Sunday, 4, false), "日曜");
+        assert_eq!(labels.weekday_label(Weekday::Sunday, 3, false), "日");
+        assert_eq!(labels.weekday_label(Weekday::Sunday, 1, false), "");
+        // falling back when a form is too wide
+        let wide = CalendarLabels::new().weekday(Weekday::Friday, "Friday", "Fri", "Fr");
+        assert_eq!(wide.weekday_label(Weekday::Friday, 5, false), "Fri");
+        assert_eq!(wide.weekday_label(Weekday::Friday, 2, true), "Fr");
+        assert_eq!(wide.weekday_label(Weekday::Friday, 1, true), "F");
+    }
+}
@@ -0,0 +1,225 @@
+use std::{borrow::Cow, iter};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{StatefulWidget, Widget},
+};
+use time::Date;
+
+use super::{state::add_months, Calendar, CalendarState, Cells, DateStyler};
+
+/// Display consecutive months of a [`Calendar`] side by side, driven by one [`CalendarState`],
+/// e.g. this month and the next in a booking form.
+///
+/// The area is split into `months` panels of equal width, each drawing a month with the calendar's
+/// settings. The calendar's block is drawn around all the panels, and the month headers stay on
+/// their own lines whatever the [`HeaderMode`](super::HeaderMode).
+///
+/// The panels follow the focus: it stays in whichever panel contains it, and when it moves past
+/// the last panel or before the first, all the panels shift so it is in the last or first panel.
+/// The first month shown is kept in the state between renders. Only the panel of the focused
+/// month draws the focus, and a range is highlighted across all the panels.
+///
+/// [`date_at`](CalendarState::date_at) finds dates in every panel, and
+/// [`panel_areas`](Self::panel_areas) gives the area of each panel for other hit-testing.
+pub struct LinkedCalendars<'a, S: DateStyler> {
+    calendar: Calendar<'a, S>,
+    months: u8,
+    spacing: u16,
+}
+
+impl<'a, S: DateStyler> LinkedCalendars<'a, S> {
+    /// Display `months` consecutive months of `calendar`.
+    pub fn new(calendar: Calendar<'a, S>, months: u8) -> Self {
+        Self {
+            calendar,
+            months,
+            spacing: 2,
+        }
+    }
+
+    /// The number of blank columns between panels. Defaults to 2.
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// The areas of the panels, from the first month to the last, when rendered in `area`.
+    /// Panels too narrow to draw anything are left out.
+    pub fn panel_areas(&self, area: Rect) -> Vec<Rect> {
+        let area = match &self.calendar.block {
+            Some(block) => block.inner(area),
+            None => area,
+        };
+        let n = u16::from(self.months);
+        let gaps = self.spacing.saturating_mul(n.saturating_sub(1));
+        let width = match area.width.saturating_sub(gaps).checked_div(n) {
+            Some(width) if width > 0 => width,
+            _ => return Vec::new(),
+        };
+        (0..n)
+            .map(|i| Rect {
+                x: area.x + i * (width + self.spacing),
+                width,
+                ..area
+            })
+            .collect()
+    }
+
+    fn render_panels(
+        mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        first: Date,
+        state: Option<&CalendarState>,
+    ) -> Vec<Cells> {
+        let panels = self.panel_areas(area);
+        self.calendar.render_frame(area, buf);
+        let months = iter::successors(Some(first), |month| add_months(*month, 1));
+        panels
+            .into_iter()
+            .zip(months)
+            .map(|(rect, month)| {
+                self.calendar.display_date = month;
+                self.calendar.draw_month(rect, buf, state)
+            })
+            .collect()
+    }
+}
+
+/// The first month to show in `months` panels, so the month of `focused` is in one of them,
+/// moving the panels from starting at `prev` as little as possible.
+fn first_month(prev: Option<Date>, focused: Date, months: u8) -> Option<Date> {
+    let focus = focused.replace_day(1).ok()?;
+    let last = i32::from(months.max(1)) - 1;
+    match prev {
+        Some(first) if first > focus => Some(focus),
+        Some(first) if add_months(first, last).is_some_and(|l| l >= focus) => Some(first),
+        // put the focus in the last panel, or the first when starting out
+        Some(_) => add_months(focus, -last).or(Some(focus)),
+        None => Some(focus),
+    }
+}
+
+impl<'a, S: DateStyler> Widget for LinkedCalendars<'a, S> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if let Ok(first) = self.calendar.display_date.replace_day(1) {
+            self.render_panels(area, buf, first, None);
+        }
+    }
+}
+
+impl<'a, S: DateStyler> StatefulWidget for LinkedCalendars<'a, S> {
+    type State = CalendarState;
+
+    fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        self.calendar.display_date = state.focused;
+        state.focused_holiday = self.calendar.holiday(state.focused).map(Cow::into_owned);
+        state.bounds = self.calendar.state_bounds();
+        state.today = Some(self.calendar.today_date());
+        state.first_panel = first_month(state.first_panel, state.focused, self.months);
+        let cells = match state.first_panel {
+            Some(first) => self.render_panels(area, buf, first, Some(state)),
+            None => Vec::new(),
+        };
+        state.set_cells(cells);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Style};
+    use time::macros::date;
+
+    use super::*;
+    use crate::calendar::{testing::render_widget_with, CalendarEventStore};
+
+    fn calendar() -> Calendar<'static, CalendarEventStore> {
+        Calendar::new(date!(2000 - 01 - 01), CalendarEventStore::default())
+            .show_month(Style::default())
+            .focused_style(Style::default().bg(Color::Blue))
+            .range_style(Style::default().bg(Color::Green))
+            .show_today(false)
+    }
+
+    #[test]
+    fn panels_follow_the_focus() {
+        let linked = || LinkedCalendars::new(calendar(), 2);
+        let mut state = CalendarState::new(date!(2023 - 04 - 28));
+        assert_eq!(
+            render_widget_with(linked(), &mut state, 44, 7).lines(),
+            [
+                "      April 2023              May 2023      ",
+                "                    1       1  2  3  4  5  6",
+                "  2  3  4  5  6  7  8    7  8  9 10 11 12 13",
+                "  9 10 11 12 13 14 15   14 15 16 17 18 19 20",
+                " 16 17 18 19 20 21 22   21 22 23 24 25 26 27",
+                " 23 24 25 26 27 28 29   28 29 30 31         ",
+                " 30                                         ",
+            ]
+        );
+        assert_eq!(state.date_at(16, 5), Some(date!(2023 - 04 - 28)));
+        assert_eq!(state.date_at(34, 5), Some(date!(2023 - 05 - 31)));
+        assert_eq!(state.date_at(21, 5), None);
+
+        // moving into the next panel doesn't shift the panels
+        state.move_weeks(1);
+        assert_eq!(
+            first_month(state.first_panel, state.focused(), 2),
+            Some(date!(2023 - 04 - 01))
+        );
+        // moving past the last panel shifts them to end with the focus
+        state.move_months(1);
+        let lines = render_widget_with(linked(), &mut state, 44, 7).lines();
+        assert_eq!(state.first_panel, Some(date!(2023 - 05 - 01)));
+        assert_eq!(lines[0], "       May 2023               June 2023     ");
+        // moving before the first panel shifts them to start with the focus
+        state.move_months(-3);
+        assert_eq!(
+            first_month(state.first_panel, state.focused(), 2),
+            Some(date!(2023 - 03 - 01))
+        );
+    }
+
+    #[test]
+    fn range_across_panels() {
+        let mut state = CalendarState::new(date!(2023 - 04 - 29));
+        let linked = || LinkedCalendars::new(calendar(), 2);
+        render_widget_with(linked(), &mut state, 44, 7);
+        state.begin_range();
+        state.move_days(3);
+        let snapshot = render_widget_with(linked(), &mut state, 44, 7);
+        let bg = |x, y| snapshot.buffer().get(x, y).bg;
+        assert_eq!(bg(19, 5), Color::Green);
+        assert_eq!(bg(1, 6), Color::Green);
+        assert_eq!(bg(28, 1), Color::Green);
+        // only the panel of the focused month draws the focus
+        assert_eq!(bg(31, 1), Color::Blue);
+        assert_eq!(bg(16, 5), Color::Reset);
+    }
+
+    #[test]
+    fn panel_areas() {
+        let linked = LinkedCalendars::new(calendar(), 3).spacing(1);
+        assert_eq!(
+            linked.panel_areas(Rect::new(1, 2, 65, 8)),
+            [
+                Rect::new(1, 2, 21, 8),
+                Rect::new(23, 2, 21, 8),
+                Rect::new(45, 2, 21, 8)
+            ]
+        );
+        assert_eq!(linked.panel_areas(Rect::new(0, 0, 2, 8)), []);
+        assert_eq!(
+            LinkedCalendars::new(calendar(), 0).panel_areas(Rect::new(0, 0, 40, 8)),
+            []
+        );
+
+        // small areas are filled, without panicking
+        let mut state = CalendarState::new(date!(2023 - 04 - 29));
+        for (w, h) in [(0, 0), (1, 1), (10, 2), (30, 5)] {
+            render_widget_with(LinkedCalendars::new(calendar(), 3), &mut state, w, h);
+        }
+    }
+}
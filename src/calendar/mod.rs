@@ -7,48 +7,335 @@
 //!
 //! [`Calendar`] has several controls for what should be displayed
 //!
-//! **Note:** this calendar operates on dates from the [time crate](https://crates.io/crates/time)
-//! In the future this may be changed to [chrono](https://crates.io/crates/chrono)
-use std::collections::HashMap;
+//! The calendar can be rendered on its own as a [`Widget`], or with a [`CalendarState`] as a
+//! [`StatefulWidget`]. With a state, the calendar displays the month containing the state's focused
+//! date, and highlights the focused and selected dates with the `focused_style` and
+//! `selected_style`. Both are drawn by the same code, so without a state a month looks just the
+//! same, apart from the focus and selection, e.g. for a dashboard.
+//!
+//! Today's date is highlighted with the `today_style`. Styles for a date are patched together, so
+//! attributes of later styles replace those of earlier ones, in this order:
+//! 1. the `default_style`
+//! 2. the style of the day's column, from `weekday_styles`
+//! 3. the `weekend_style`, for days on the weekend
+//! 4. the `show_surrounding` style, for days outside the displayed month
+//! 5. the style from the [`DateStyler`]
+//! 6. the style from the [`relative_highlight`](Calendar::relative_highlight)
+//! 7. the `holiday_style`, for dates named by the [`HolidayProvider`]
+//! 8. the `week_highlight_style`, for dates in the focused week
+//! 9. the `today_style`
+//! 10. the `disabled_style`, for disabled dates (see [`Calendar::is_enabled`])
+//! 11. the `range_style`, for dates in the selected range
+//! 12. the `reference_style`, for the state's [`reference`](CalendarState::reference) date
+//! 13. the `selected_style`
+//! 14. the `focused_style`
+//!
+//! Each of these after the `default_style` is a [`StyleLayer`], and
+//! [`Calendar::style_priority`] changes the order they are patched in, e.g. so today's date stands
+//! out in a selected range.
+//!
+//! **Note:** this calendar operates on dates from the [time crate](https://crates.io/crates/time).
+//! With the `chrono` feature, [chrono](https://crates.io/crates/chrono) dates can be used as well:
+//! see `to_date` and the `_naive` methods of [`Calendar`] and [`CalendarState`].
+use std::{borrow::Cow, collections::HashMap, iter};
+
+#[cfg(feature = "styled_list")]
+mod agenda;
+mod annotate;
+mod cell;
+mod continuous;
+mod events;
+mod grid;
+mod heatmap;
+mod holidays;
+mod labels;
+mod linked;
+#[cfg(feature = "chrono")]
+mod naive;
+mod parse;
+mod picker;
+mod state;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod week_number;
+mod week_start;
+mod year;
 
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::Style,
     text::{Span, Spans},
-    widgets::{Block, Widget},
+    widgets::{Block, StatefulWidget, Widget},
 };
 
-use time::{Date, Duration, OffsetDateTime};
+use time::{Date, Duration, OffsetDateTime, UtcOffset, Weekday};
+use unicode_width::UnicodeWidthStr;
+
+#[cfg(feature = "styled_list")]
+pub use agenda::{Agenda, AgendaPane, AgendaState, EntryList};
+pub use annotate::Annotator;
+pub use cell::{default_cell, CellContext};
+pub use continuous::ContinuousCalendar;
+pub use events::{CalendarEvent, CalendarEvents};
+pub use grid::MonthsGrid;
+pub use heatmap::{ColorRamp, Heatmap};
+pub use holidays::{HolidayProvider, StaticHolidays};
+pub use labels::CalendarLabels;
+pub use linked::LinkedCalendars;
+#[cfg(feature = "chrono")]
+pub use naive::{from_date, to_date, OutOfRange};
+pub use parse::{parse_date, parse_date_with, DateOrder, ParseDateError};
+pub use picker::{DatePicker, DatePickerState, PickResult};
+pub use state::{BoundBehavior, CalendarChange, CalendarClick, CalendarState, Jump, Navigation};
+use state::{Bounds, Cells};
+pub use week_number::WeekNumbering;
+pub use week_start::WeekStart;
+pub use year::YearView;
+
+/// Where the month header of a [`Calendar`] is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeaderMode {
+    /// On its own line, above the weekdays.
+    #[default]
+    Line,
+    /// As the title of the calendar's [`block`](Calendar::block), saving a line. Without a block,
+    /// the header is drawn on its own line.
+    BlockTitle,
+}
+
+/// A style a [`Calendar`] patches onto the days it applies to. The layers are patched in the order
+/// they are listed here, over the `default_style`, unless it is changed with
+/// [`Calendar::style_priority`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StyleLayer {
+    /// The style of the day's column, from [`weekday_styles`](Calendar::weekday_styles).
+    Weekday,
+    /// The [`weekend_style`](Calendar::weekend_style), for days on the weekend.
+    Weekend,
+    /// The [`show_surrounding`](Calendar::show_surrounding) style, for days outside the displayed
+    /// month.
+    Surrounding,
+    /// The style from the [`DateStyler`], e.g. of the day's events.
+    Event,
+    /// The style from the [`relative_highlight`](Calendar::relative_highlight).
+    Relative,
+    /// The [`holiday_style`](Calendar::holiday_style), for dates named by the
+    /// [`HolidayProvider`].
+    Holiday,
+    /// The [`week_highlight_style`](Calendar::week_highlight_style), for dates in the focused
+    /// week.
+    WeekHighlight,
+    /// The [`today_style`](Calendar::today_style).
+    Today,
+    /// The [`disabled_style`](Calendar::disabled_style), for disabled dates.
+    Disabled,
+    /// The [`range_style`](Calendar::range_style), for dates in the selected range.
+    Range,
+    /// The [`reference_style`](Calendar::reference_style), for the state's reference date.
+    Reference,
+    /// The [`selected_style`](Calendar::selected_style).
+    Selected,
+    /// The [`focused_style`](Calendar::focused_style).
+    Focused,
+}
+
+impl StyleLayer {
+    /// The layers in the order they are patched by default.
+    const ORDER: [StyleLayer; 13] = [
+        StyleLayer::Weekday,
+        StyleLayer::Weekend,
+        StyleLayer::Surrounding,
+        StyleLayer::Event,
+        StyleLayer::Relative,
+        StyleLayer::Holiday,
+        StyleLayer::WeekHighlight,
+        StyleLayer::Today,
+        StyleLayer::Disabled,
+        StyleLayer::Range,
+        StyleLayer::Reference,
+        StyleLayer::Selected,
+        StyleLayer::Focused,
+    ];
+}
+
+/// How today's date is marked by a [`Calendar`], besides its
+/// [`today_style`](Calendar::today_style), so it stands out on terminals without colors.
+///
+/// The marks go in the spaces around the day's number, and in the gaps between days. When there
+/// isn't room for them, e.g. after the last day of a week in an area as wide as the month, today
+/// isn't marked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TodayMarker {
+    /// Only the style.
+    #[default]
+    None,
+    /// Brackets around the day, e.g. `[17]`.
+    Brackets,
+    /// A symbol after the day, e.g. `17*`.
+    Suffix(char),
+    /// A symbol before the day, e.g. `*17`.
+    Prefix(char),
+}
+
+impl TodayMarker {
+    /// The characters before and after the day.
+    fn chars(self) -> (Option<char>, Option<char>) {
+        match self {
+            TodayMarker::None => (None, None),
+            TodayMarker::Brackets => (Some('['), Some(']')),
+            TodayMarker::Suffix(c) => (None, Some(c)),
+            TodayMarker::Prefix(c) => (Some(c), None),
+        }
+    }
+}
 
 /// Display a month calendar for the month containing `display_date`
+///
+/// When the area is too short for all the weeks of the month, the month name is dropped first,
+/// then the weekday separator and names. If the weeks still don't fit, only those around the
+/// focused date of the [`CalendarState`] are drawn, scrolling a week at a time as the focus moves,
+/// and [`CalendarState::visible_range`] gives the dates drawn. Without a state, the
+/// first weeks of the month are drawn.
 pub struct Calendar<'a, S: DateStyler> {
     display_date: Date,
     events: S,
     show_surrounding: Option<Style>,
-    show_weekday: Option<Style>,
+    show_weekday: bool,
+    labels: CalendarLabels,
+    weekday_header_style: Style,
+    weekday_separator: Option<char>,
     show_month: Option<Style>,
+    header_mode: HeaderMode,
+    header_arrows: bool,
     default_style: Style,
+    focused_style: Style,
+    selected_style: Style,
+    range_style: Style,
+    reference_style: Style,
+    reference_marker: Option<char>,
+    today_style: Style,
+    show_today: bool,
+    today: Option<Date>,
+    clock: Option<ClockFn<'a>>,
+    today_marker: TodayMarker,
+    week_start: Weekday,
+    weekend: u8,
+    weekend_style: Style,
+    weekday_styles: [Option<Style>; 7],
+    min_date: Option<Date>,
+    max_date: Option<Date>,
+    bound_behavior: BoundBehavior,
+    disabled: Option<Box<dyn Fn(Date) -> bool + 'a>>,
+    selectable: Option<Box<dyn Fn(Date) -> bool + 'a>>,
+    disabled_style: Style,
+    holidays: Option<&'a dyn HolidayProvider>,
+    holiday_style: Style,
+    disable_holidays: bool,
+    markers: Option<Box<dyn Fn(Date) -> Option<char> + 'a>>,
+    marker_style: Style,
+    annotators: Vec<Box<dyn Annotator + 'a>>,
+    counts: Option<Box<dyn Fn(Date) -> usize + 'a>>,
+    count_format: Option<CountFormatFn<'a>>,
+    count_style: Style,
+    relative: Option<(Date, RelativeFn<'a>)>,
+    show_ordinal: bool,
+    ordinal_style: Style,
+    week_highlight_style: Style,
+    week_numbers: Option<Style>,
+    week_numbering: WeekNumbering<'a>,
+    cell_renderer: Option<CellFn<'a>>,
+    cell_height: u16,
+    cell_content: Option<ContentFn<'a>>,
+    style_order: [StyleLayer; 13],
+    compact: bool,
+    stretch: bool,
     block: Option<Block<'a>>,
 }
 
+/// Closure styling a day from its distance to a reference date.
+type RelativeFn<'a> = Box<dyn Fn(i64) -> Option<Style> + 'a>;
+
+/// Closure telling the time, for today's date.
+type ClockFn<'a> = Box<dyn Fn() -> OffsetDateTime + 'a>;
+
+/// Closure formatting the number of entries of a day.
+type CountFormatFn<'a> = Box<dyn Fn(usize) -> String + 'a>;
+
+/// Closure drawing a day.
+type CellFn<'a> = Box<dyn Fn(Date, CellContext) -> Spans<'a> + 'a>;
+
+/// Closure giving the lines below a day's number.
+type ContentFn<'a> = Box<dyn Fn(Date, u16) -> Vec<Spans<'a>> + 'a>;
+
 impl<'a, S: DateStyler> Calendar<'a, S> {
-    /// Construct a calendar for the `display_date` and highlight the `events`
+    /// Construct a calendar for the `display_date` and highlight the `events`, e.g. a
+    /// [`CalendarEvents`], a [`CalendarEventStore`] or a `HashMap<Date, Style>`, or a reference
+    /// to one.
     pub fn new(display_date: Date, events: S) -> Self {
         Self {
             display_date,
             events,
             show_surrounding: None,
-            show_weekday: None,
+            show_weekday: false,
+            labels: CalendarLabels::default(),
+            weekday_header_style: Style::default(),
+            weekday_separator: None,
             show_month: None,
+            header_mode: HeaderMode::Line,
+            header_arrows: false,
             default_style: Style::default(),
+            focused_style: Style::default(),
+            selected_style: Style::default(),
+            range_style: Style::default(),
+            reference_style: Style::default(),
+            reference_marker: None,
+            today_style: Style::default(),
+            show_today: true,
+            today: None,
+            clock: None,
+            today_marker: TodayMarker::None,
+            week_start: Weekday::Sunday,
+            weekend: weekday_bit(Weekday::Saturday) | weekday_bit(Weekday::Sunday),
+            weekend_style: Style::default(),
+            weekday_styles: [None; 7],
+            min_date: None,
+            max_date: None,
+            bound_behavior: BoundBehavior::default(),
+            disabled: None,
+            selectable: None,
+            disabled_style: Style::default(),
+            holidays: None,
+            holiday_style: Style::default(),
+            disable_holidays: false,
+            markers: None,
+            marker_style: Style::default(),
+            annotators: Vec::new(),
+            counts: None,
+            count_format: None,
+            count_style: Style::default(),
+            relative: None,
+            show_ordinal: false,
+            ordinal_style: Style::default(),
+            week_highlight_style: Style::default(),
+            week_numbers: None,
+            week_numbering: WeekNumbering::Iso,
+            cell_renderer: None,
+            cell_height: 1,
+            cell_content: None,
+            style_order: StyleLayer::ORDER,
+            compact: false,
+            stretch: false,
             block: None,
         }
     }
 
     /// Fill the calendar slots for days not in the current month also, this causes each line to be
     /// completely filled. If there is an event style for a date, this style will be patched with
-    /// the event's style
+    /// the event's style, and the today, selected and focused styles are patched over both.
+    ///
+    /// Surrounding days can be focused and selected like any other. Since the calendar displays the
+    /// month of the focused date, moving the focus onto a surrounding day switches to its month.
     pub fn show_surrounding(mut self, style: Style) -> Self {
         self.show_surrounding = Some(style);
         self
@@ -56,28 +343,632 @@ impl<'a, S: DateStyler> Calendar<'a, S> {
 
     /// Display a header containing weekday abbreviations
     pub fn show_weekdays(mut self, style: Style) -> Self {
-        self.show_weekday = Some(style);
+        self.show_weekday = true;
+        self.weekday_header_style = style;
+        self
+    }
+
+    /// Set the weekday names of the header, e.g. to translate them. The form of each name that
+    /// fits the width of the days is drawn, see [`CalendarLabels`].
+    pub fn labels(mut self, labels: CalendarLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// The style of the weekday header, without changing whether it is displayed. The header is
+    /// styled on its own, apart from the days below it, with the
+    /// [`weekday_styles`](Self::weekday_styles) and [`weekend_style`](Self::weekend_style) of each
+    /// column patched over it.
+    pub fn weekday_header_style(mut self, s: Style) -> Self {
+        self.weekday_header_style = s;
+        self
+    }
+
+    /// Don't display the weekday header, e.g. to fit a month in as few rows as possible.
+    pub fn hide_weekdays(mut self) -> Self {
+        self.show_weekday = false;
         self
     }
 
+    /// Draw a rule of `symbol` below the weekday header, in the header's style, to set it apart
+    /// from the days. The rule takes a row, and is only drawn with the header.
+    pub fn weekday_separator(mut self, symbol: char) -> Self {
+        self.weekday_separator = Some(symbol);
+        self
+    }
+
+    /// The number of rows taken by the weekday header and its separator.
+    fn weekday_rows(&self) -> u16 {
+        match (self.show_weekday, self.weekday_separator) {
+            (false, _) => 0,
+            (true, None) => 1,
+            (true, Some(_)) => 2,
+        }
+    }
+
+    /// Draw the first `rows` lines of the weekday header and its separator at the top of `area`,
+    /// returning the rest of it.
+    fn draw_weekdays(&self, mut area: Rect, buf: &mut Buffer, layout: Layout, rows: u16) -> Rect {
+        if !self.show_weekday || rows == 0 || area.height == 0 {
+            return area;
+        }
+        let style = self.weekday_header_style;
+        let mut days = Spans(Vec::with_capacity(14));
+        let mut day = self.week_start;
+        for _ in 0..7 {
+            let name = self.labels.weekday_label(day, layout.cell, layout.gap == 0);
+            days.0.push(Span::styled(layout.gap_str(), style));
+            days.0.push(Span::styled(
+                layout.align(&name),
+                style.patch(self.column_style(day)),
+            ));
+            day = day.next();
+        }
+        buf.set_spans(area.x, area.y, &days, area.width);
+        area.y += 1;
+        area.height -= 1;
+
+        if let (Some(symbol), true) = (self.weekday_separator, rows > 1 && area.height > 0) {
+            let width = std::cmp::min(layout.width(), area.width);
+            let rule = symbol.to_string().repeat(width.into());
+            buf.set_stringn(area.x, area.y, rule, width.into(), style);
+            area.y += 1;
+            area.height -= 1;
+        }
+        area
+    }
+
     /// Display a header containing the month and year
     pub fn show_month(mut self, style: Style) -> Self {
         self.show_month = Some(style);
         self
     }
 
+    /// Set where the header from [`show_month`](Self::show_month) is drawn.
+    pub fn header_mode(mut self, mode: HeaderMode) -> Self {
+        self.header_mode = mode;
+        self
+    }
+
+    /// Draw arrows for the previous and next months either side of the month name, as in
+    /// `◂ June 2025 ▸`. Clicks on them are found by [`CalendarState::click`]. When the name and
+    /// arrows don't fit over the days, the arrows collapse to single characters at the edges, and
+    /// the name is cut to fit between them.
+    ///
+    /// The arrows are only drawn with a [`show_month`](Self::show_month) header on its own line,
+    /// not with [`HeaderMode::BlockTitle`].
+    pub fn header_arrows(mut self, arrows: bool) -> Self {
+        self.header_arrows = arrows;
+        self
+    }
+
     /// How to render otherwise unstyled dates
     pub fn default_style(mut self, s: Style) -> Self {
         self.default_style = s;
         self
     }
 
+    /// The style patched onto the focused date when rendering with a [`CalendarState`].
+    pub fn focused_style(mut self, s: Style) -> Self {
+        self.focused_style = s;
+        self
+    }
+
+    /// The style patched onto the days of the week containing the focused date when rendering
+    /// with a [`CalendarState`], including the days of surrounding months if they are shown. The
+    /// today, selected and focused styles are patched over it.
+    pub fn week_highlight_style(mut self, s: Style) -> Self {
+        self.week_highlight_style = s;
+        self
+    }
+
+    /// The style patched onto the selected dates when rendering with a [`CalendarState`]. The
+    /// focused style is patched over this when a selected date is focused.
+    pub fn selected_style(mut self, s: Style) -> Self {
+        self.selected_style = s;
+        self
+    }
+
+    /// The style patched onto the dates of the selected range when rendering with a
+    /// [`CalendarState`]. While a range is being chosen, it is previewed from its start to the
+    /// focused date.
+    pub fn range_style(mut self, s: Style) -> Self {
+        self.range_style = s;
+        self
+    }
+
+    /// The style patched onto the [`reference`](CalendarState::reference) date of the state, e.g.
+    /// the original date of an appointment being moved. The selected and focused styles are
+    /// patched over this.
+    pub fn reference_style(mut self, s: Style) -> Self {
+        self.reference_style = s;
+        self
+    }
+
+    /// Draw `c` beside the [`reference`](CalendarState::reference) date of the state, in the
+    /// [`reference_style`](Self::reference_style), so it is still marked when it is selected or
+    /// focused. It is drawn like the [`markers`](Self::markers), in their place, and left out
+    /// when there is no room for it.
+    pub fn reference_marker(mut self, c: char) -> Self {
+        self.reference_marker = Some(c);
+        self
+    }
+
+    /// The style patched onto today's date.
+    pub fn today_style(mut self, s: Style) -> Self {
+        self.today_style = s;
+        self
+    }
+
+    /// Highlight today's date with the [`today_style`](Self::today_style). This is on by default.
+    pub fn show_today(mut self, show: bool) -> Self {
+        self.show_today = show;
+        self
+    }
+
+    /// Look up today's date in the time zone `offset` from UTC, instead of the local time zone,
+    /// e.g. to render calendars for users in other time zones.
+    pub fn now_offset(self, offset: UtcOffset) -> Self {
+        self.clock(move || OffsetDateTime::now_utc().to_offset(offset))
+    }
+
+    /// Look up today's date from the time returned by `clock`, in the time's offset, instead of
+    /// the system clock in the local time zone. The clock is called once per render. A date set
+    /// with [`today`](Self::today) takes priority.
+    pub fn clock<F>(mut self, clock: F) -> Self
+    where
+        F: Fn() -> OffsetDateTime + 'a,
+    {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    /// Mark today's date with brackets or a symbol, as well as the
+    /// [`today_style`](Self::today_style). The marks take the day's style, including the
+    /// focused style when today is focused. Today isn't marked when a
+    /// [`cell_renderer`](Self::cell_renderer) draws the days.
+    pub fn today_marker(mut self, marker: TodayMarker) -> Self {
+        self.today_marker = marker;
+        self
+    }
+
+    /// Use `date` as today's date, instead of the local date from the system clock, e.g. for tests.
+    /// To follow the clock in another time zone, use [`now_offset`](Self::now_offset) instead.
+    pub fn today(mut self, date: Date) -> Self {
+        self.today = Some(date);
+        self
+    }
+
+    /// Display the number of each week in a column left of the days, numbered by the
+    /// [`week_numbering`](Self::week_numbering). The column is as wide as the widest number in the
+    /// month.
+    pub fn show_week_numbers(mut self, style: Style) -> Self {
+        self.week_numbers = Some(style);
+        self
+    }
+
+    /// How the weeks are numbered by [`show_week_numbers`](Self::show_week_numbers). Defaults to
+    /// ISO weeks.
+    pub fn week_numbering(mut self, numbering: WeekNumbering<'a>) -> Self {
+        self.week_numbering = numbering;
+        self
+    }
+
+    /// The first day of each week (the leftmost column). Defaults to Sunday.
+    ///
+    /// Takes a [`Weekday`], or a [`WeekStart`] to start on the first day of a locale. The locale
+    /// is looked up when this is called.
+    pub fn week_start<W: Into<WeekStart>>(mut self, start: W) -> Self {
+        self.week_start = start.into().weekday();
+        self
+    }
+
+    /// The days of the week that are the weekend. Defaults to Saturday and Sunday.
+    pub fn weekend<I>(mut self, days: I) -> Self
+    where
+        I: IntoIterator<Item = Weekday>,
+    {
+        self.weekend = days
+            .into_iter()
+            .fold(0, |bits, day| bits | weekday_bit(day));
+        self
+    }
+
+    /// The style patched onto the days on the [`weekend`](Self::weekend), and their weekday
+    /// headers.
+    pub fn weekend_style(mut self, s: Style) -> Self {
+        self.weekend_style = s;
+        self
+    }
+
+    /// Styles for the column of each day of the week, from Monday to Sunday. Each is patched onto
+    /// the whole column: the weekday header, and all of the days including those of the
+    /// surrounding months. The styles stay with their weekday whatever the
+    /// [`week_start`](Self::week_start).
+    pub fn weekday_styles(mut self, styles: [Option<Style>; 7]) -> Self {
+        self.weekday_styles = styles;
+        self
+    }
+
+    fn weekday_style(&self, day: Weekday) -> Style {
+        self.weekday_styles[day.number_days_from_monday() as usize].unwrap_or_default()
+    }
+
+    /// The style of the column for `day`, with the weekend style if it is on the weekend.
+    fn column_style(&self, day: Weekday) -> Style {
+        let style = self.weekday_style(day);
+        if self.is_weekend(day) {
+            style.patch(self.weekend_style)
+        } else {
+            style
+        }
+    }
+
+    fn is_weekend(&self, day: Weekday) -> bool {
+        self.weekend & weekday_bit(day) != 0
+    }
+
+    /// Disable the dates before `min` and after `max`. Either bound can be left open with `None`.
+    ///
+    /// Disabled dates are drawn with the [`disabled_style`](Self::disabled_style). To keep the
+    /// focus off them, navigate with the `_within` methods of [`CalendarState`], passing
+    /// [`is_enabled`](Self::is_enabled). All the navigation methods of the state keep to the
+    /// bounds as set by [`bound_behavior`](Self::bound_behavior).
+    pub fn bounds(mut self, min: Option<Date>, max: Option<Date>) -> Self {
+        self.min_date = min;
+        self.max_date = max;
+        self
+    }
+
+    /// What navigating the [`CalendarState`] does at the [`bounds`](Self::bounds): stop there,
+    /// wrap around to the other bound, or carry on past them. Defaults to
+    /// [`BoundBehavior::Clamp`].
+    pub fn bound_behavior(mut self, behavior: BoundBehavior) -> Self {
+        self.bound_behavior = behavior;
+        self
+    }
+
+    /// The bounds recorded in the state when rendering.
+    fn state_bounds(&self) -> Bounds {
+        Bounds {
+            min: self.min_date,
+            max: self.max_date,
+            behavior: self.bound_behavior,
+        }
+    }
+
+    /// Disable the dates for which `f` returns true, in addition to those outside the
+    /// [`bounds`](Self::bounds).
+    pub fn disabled<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Date) -> bool + 'a,
+    {
+        self.disabled = Some(Box::new(f));
+        self
+    }
+
+    /// Only enable the dates for which `f` returns true, e.g. business days. This is the opposite of
+    /// [`disabled`](Self::disabled), for rules that are easier to write as the dates allowed, and
+    /// both can be used together.
+    ///
+    /// Dates that aren't selectable are disabled: they are drawn with the
+    /// [`disabled_style`](Self::disabled_style), and the state's `_within` methods given
+    /// [`is_enabled`](Self::is_enabled) skip them. Months without any selectable dates are still
+    /// drawn.
+    pub fn selectable<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Date) -> bool + 'a,
+    {
+        self.selectable = Some(Box::new(f));
+        self
+    }
+
+    /// The style patched onto disabled dates.
+    pub fn disabled_style(mut self, s: Style) -> Self {
+        self.disabled_style = s;
+        self
+    }
+
+    /// Is `date` within the [`bounds`](Self::bounds), [`selectable`](Self::selectable), and not
+    /// [`disabled`](Self::disabled)? With [`disable_holidays`](Self::disable_holidays) set,
+    /// holidays are disabled too.
+    pub fn is_enabled(&self, date: Date) -> bool {
+        self.min_date.is_none_or(|min| date >= min)
+            && self.max_date.is_none_or(|max| date <= max)
+            && self.selectable.as_ref().is_none_or(|f| f(date))
+            && !self.disabled.as_ref().is_some_and(|f| f(date))
+            && !(self.disable_holidays && self.holiday(date).is_some())
+    }
+
+    /// Mark the holidays from `holidays` with the [`holiday_style`](Self::holiday_style). When
+    /// rendering with a [`CalendarState`], the name of the focused holiday is available from
+    /// [`CalendarState::focused_holiday`].
+    pub fn holidays(mut self, holidays: &'a dyn HolidayProvider) -> Self {
+        self.holidays = Some(holidays);
+        self
+    }
+
+    /// The style patched onto holidays.
+    pub fn holiday_style(mut self, s: Style) -> Self {
+        self.holiday_style = s;
+        self
+    }
+
+    /// Disable holidays, so they are drawn with the [`disabled_style`](Self::disabled_style) and
+    /// rejected by [`is_enabled`](Self::is_enabled). Off by default.
+    pub fn disable_holidays(mut self, disable: bool) -> Self {
+        self.disable_holidays = disable;
+        self
+    }
+
+    /// Draw the symbol returned by `f` beside each date, e.g. a `•` on days with entries.
+    ///
+    /// The symbol is drawn in the gap after the day's number. When there is no room for it - it
+    /// isn't a single column wide, or the day is in the last column and the area is only as wide
+    /// as the month - the [`marker_style`](Self::marker_style) is patched onto the day's number
+    /// instead.
+    pub fn markers<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Date) -> Option<char> + 'a,
+    {
+        self.markers = Some(Box::new(f));
+        self
+    }
+
+    /// The style of the marker symbols.
+    pub fn marker_style(mut self, s: Style) -> Self {
+        self.marker_style = s;
+        self
+    }
+
+    /// Annotate days with the glyphs from `annotator`. Annotators added first take priority: each
+    /// day shows the annotation of the first annotator that has one for it.
+    ///
+    /// The annotation is drawn on the line below the day's number when the weeks are
+    /// [stretched](Self::stretch) over several lines, after the ordinal if it is drawn there.
+    /// Otherwise it is drawn after the number in cells wide enough for it, and left out of
+    /// narrower ones. Either way it is cut off at the edge of the cell.
+    pub fn annotator<A>(mut self, annotator: A) -> Self
+    where
+        A: Annotator + 'a,
+    {
+        self.annotators.push(Box::new(annotator));
+        self
+    }
+
+    /// Show how many entries each day has, as counted by `f`, in a badge after the day's number.
+    /// Days without entries have no badge.
+    ///
+    /// The badge is drawn inside cells with room for it after the number, such as
+    /// [stretched](Self::stretch) ones, and otherwise in the gap after the day, like the
+    /// [`markers`](Self::markers), if it is a single column wide. Badges that don't fit are
+    /// replaced by a `•` there. In the [`compact`](Self::compact) layout, the `•` goes before
+    /// single digit days, and the [`count_style`](Self::count_style) is patched onto the others.
+    pub fn counts<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Date) -> usize + 'a,
+    {
+        self.counts = Some(Box::new(f));
+        self
+    }
+
+    /// Format the badges of the [`counts`](Self::counts) with `f`, e.g. `|n| format!(" ({n})")`.
+    /// `f` isn't called for days without entries. By default counts are drawn as superscript
+    /// digits, e.g. `³`, with counts of 10 or more drawn as `⁹⁺`.
+    pub fn count_format<F>(mut self, f: F) -> Self
+    where
+        F: Fn(usize) -> String + 'a,
+    {
+        self.count_format = Some(Box::new(f));
+        self
+    }
+
+    /// The style of the [`counts`](Self::counts) badges, patched over the day's style inside its
+    /// cell.
+    pub fn count_style(mut self, s: Style) -> Self {
+        self.count_style = s;
+        self
+    }
+
+    /// Style days by how far they are from `reference`. `f` is called for each day drawn with the
+    /// number of days from the reference to the day, negative for days before it, and the style it
+    /// returns is patched onto the day. For example, to dim the past and highlight the next week:
+    ///
+    /// ```
+    /// # use extra_widgets::calendar::{Calendar, CalendarEventStore};
+    /// # use ratatui::style::{Color, Style};
+    /// # use time::macros::date;
+    /// let today = date!(2023 - 04 - 12);
+    /// let cal = Calendar::new(today, CalendarEventStore::default()).relative_highlight(today, |n| {
+    ///     match n {
+    ///         ..=-1 => Some(Style::default().fg(Color::DarkGray)),
+    ///         0..=7 => Some(Style::default().fg(Color::Yellow)),
+    ///         _ => None,
+    ///     }
+    /// });
+    /// ```
+    pub fn relative_highlight<F>(mut self, reference: Date, f: F) -> Self
+    where
+        F: Fn(i64) -> Option<Style> + 'a,
+    {
+        self.relative = Some((reference, Box::new(f)));
+        self
+    }
+
+    /// Show the day of the year (1 to 366) of each day. When the weeks are
+    /// [stretched](Self::stretch) to two lines or more, it is drawn below the day of the month,
+    /// and otherwise it replaces it.
+    ///
+    /// The day of the year takes 3 columns, so the days are widened to fit it. It isn't shown in the
+    /// [`compact`](Self::compact) layout, or when the wider month doesn't fit in the area.
+    pub fn show_ordinal(mut self, show: bool) -> Self {
+        self.show_ordinal = show;
+        self
+    }
+
+    /// Draw the days with `f` instead of the default day number. `f` is called for each day drawn
+    /// with a [`CellContext`] describing it, including the style the calendar gives it, and the
+    /// text it returns is clipped to the cell. Days not filled by the text keep the cell's style.
+    ///
+    /// Hidden days of the surrounding months aren't drawn. Use [`default_cell`] to draw a day as
+    /// the calendar does:
+    ///
+    /// ```
+    /// # use extra_widgets::calendar::{default_cell, Calendar, CalendarEventStore};
+    /// # use ratatui::text::Spans;
+    /// # use time::{macros::date, Weekday};
+    /// let cal = Calendar::new(date!(2023 - 04 - 12), CalendarEventStore::default())
+    ///     .cell_renderer(|date, cx| match date.weekday() {
+    ///         Weekday::Sunday if !cx.focused => Spans::from("--"),
+    ///         _ => default_cell(date, cx),
+    ///     });
+    /// ```
+    pub fn cell_renderer<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Date, CellContext) -> Spans<'a> + 'a,
+    {
+        self.cell_renderer = Some(Box::new(f));
+        self
+    }
+
+    /// Give each week `height` lines, so days have room below their numbers for the lines from
+    /// [`cell_content`](Self::cell_content). A day's style covers all of its lines.
+    ///
+    /// When the area isn't tall enough for every week of the month at this height, the days are
+    /// drawn on single lines instead.
+    pub fn cell_height(mut self, height: u16) -> Self {
+        self.cell_height = std::cmp::max(height, 1);
+        self
+    }
+
+    /// Fill the lines of a day below its number, with the lines returned by `f` for the date and
+    /// the width of the cell, e.g. the title of the day's first event. The lines are clipped to the
+    /// cell, and patched over the day's style. Lines beyond the [`cell_height`](Self::cell_height)
+    /// aren't drawn, and the lines below the day of the year, or an
+    /// [`annotation`](Self::annotator) drawn below the number, come first.
+    ///
+    /// ```
+    /// # use extra_widgets::calendar::{Calendar, CalendarEventStore};
+    /// # use ratatui::text::Spans;
+    /// # use time::{macros::date, Weekday};
+    /// let cal = Calendar::new(date!(2023 - 04 - 12), CalendarEventStore::default())
+    ///     .cell_height(2)
+    ///     .cell_content(|date, _width| match date.weekday() {
+    ///         Weekday::Friday => vec![Spans::from("gym")],
+    ///         _ => Vec::new(),
+    ///     });
+    /// ```
+    pub fn cell_content<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Date, u16) -> Vec<Spans<'a>> + 'a,
+    {
+        self.cell_content = Some(Box::new(f));
+        self
+    }
+
+    /// The style patched onto the day of the year, see [`show_ordinal`](Self::show_ordinal).
+    pub fn ordinal_style(mut self, s: Style) -> Self {
+        self.ordinal_style = s;
+        self
+    }
+
+    /// The marker for `date`, if it has one and is drawn.
+    fn marker(&self, date: Date) -> Option<char> {
+        if date.month() != self.display_date.month() && self.show_surrounding.is_none() {
+            return None;
+        }
+        self.markers.as_ref()?(date)
+    }
+
+    /// The badge of the number of entries on `date`, if it has any.
+    fn count_badge(&self, date: Date) -> Option<String> {
+        const SUPERSCRIPTS: [&str; 10] = ["⁰", "¹", "²", "³", "⁴", "⁵", "⁶", "⁷", "⁸", "⁹"];
+        let n = self.counts.as_ref()?(date);
+        match (n, &self.count_format) {
+            (0, _) => None,
+            (n, Some(f)) => Some(f(n)),
+            (1..=9, None) => Some(SUPERSCRIPTS[n].to_string()),
+            (_, None) => Some("⁹⁺".to_string()),
+        }
+    }
+
+    fn annotation(&self, date: Date) -> Option<Span<'_>> {
+        self.annotators.iter().find_map(|a| a.annotate(date))
+    }
+
+    fn holiday(&self, date: Date) -> Option<Cow<'a, str>> {
+        self.holidays?.holiday(date)
+    }
+
+    /// Change the order the styles of a day are patched in: the listed layers swap places, taking
+    /// the positions they have by default in the order given, and the other layers stay where
+    /// they are. Later layers win, and the range is patched over today by default, so
+    /// `[StyleLayer::Range, StyleLayer::Today]` draws today in the `today_style` even when it is
+    /// in the selected range:
+    ///
+    /// ```
+    /// # use extra_widgets::calendar::{Calendar, CalendarEventStore, StyleLayer};
+    /// # use time::macros::date;
+    /// let cal = Calendar::new(date!(2023 - 04 - 12), CalendarEventStore::default())
+    ///     .style_priority([StyleLayer::Range, StyleLayer::Today]);
+    /// ```
+    ///
+    /// Layers listed more than once only count the first time.
+    pub fn style_priority<I>(mut self, layers: I) -> Self
+    where
+        I: IntoIterator<Item = StyleLayer>,
+    {
+        let mut listed = Vec::new();
+        for layer in layers {
+            if !listed.contains(&layer) {
+                listed.push(layer);
+            }
+        }
+        let slots: Vec<_> = self
+            .style_order
+            .iter()
+            .enumerate()
+            .filter(|(_, layer)| listed.contains(layer))
+            .map(|(i, _)| i)
+            .collect();
+        for (slot, layer) in slots.into_iter().zip(listed) {
+            self.style_order[slot] = layer;
+        }
+        self
+    }
+
+    /// Use the compact layout: days without gaps between them, and one letter weekday names, to
+    /// fit the month in 14 columns. This layout is used automatically when the area is narrower
+    /// than the 21 columns of the normal layout.
+    ///
+    /// There is no room for [`markers`](Self::markers) in the compact layout, so the
+    /// [`marker_style`](Self::marker_style) is patched onto the marked days instead.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Stretch the month to fill the area: extra width goes to the days, which have their numbers
+    /// centered, and extra height to the weeks, leaving blank lines below the numbers. A day's
+    /// style covers all of its lines, so highlights are drawn as solid rectangles.
+    ///
+    /// Stretching doesn't apply to the [`compact`](Self::compact) layout.
+    pub fn stretch(mut self, stretch: bool) -> Self {
+        self.stretch = stretch;
+        self
+    }
+
     /// Render the calendar within a [Block](ratatui::widgets::Block)
     pub fn block(mut self, b: Block<'a>) -> Self {
         self.block = Some(b);
         self
     }
 
+    /// The month header, e.g. "April 2023".
+    fn month_title(&self) -> String {
+        format!("{} {}", self.display_date.month(), self.display_date.year())
+    }
+
     /// Return a style with only the backround from the defualt style
     fn default_bg(&self) -> Style {
         match self.default_style.bg {
@@ -86,85 +977,668 @@ impl<'a, S: DateStyler> Calendar<'a, S> {
         }
     }
 
+    /// Describe a day for the cell renderer.
+    fn cell_context(
+        &self,
+        date: Date,
+        state: Option<&CalendarState>,
+        width: u16,
+        style: Style,
+    ) -> CellContext {
+        let in_month = date.month() == self.display_date.month();
+        CellContext {
+            focused: state.is_some_and(|s| s.focused == date && in_month),
+            selected: state.is_some_and(|s| s.is_selected(date)),
+            today: self.show_today && self.today == Some(date),
+            disabled: !self.is_enabled(date),
+            surrounding: !in_month,
+            width,
+            style,
+        }
+    }
+
     /// All logic to style a date goes here.
-    fn format_date(&self, date: Date) -> Span {
-        let style = self.default_style.patch(self.events.get_style(date));
+    fn format_date(&self, date: Date, state: Option<&CalendarState>) -> Span<'_> {
+        if date.month() != self.display_date.month() && self.show_surrounding.is_none() {
+            let column = self.weekday_style(date.weekday());
+            return Span::styled("  ", self.default_bg().patch(column));
+        }
+        let style = self
+            .style_order
+            .iter()
+            .fold(self.default_style, |style, layer| {
+                match self.layer_style(*layer, date, state) {
+                    Some(s) => style.patch(s),
+                    None => style,
+                }
+            });
+        Span::styled(format!("{:2?}", date.day()), style)
+    }
 
-        if date.month() != self.display_date.month() {
-            match self.show_surrounding {
-                None => Span::styled("  ", self.default_bg()),
-                Some(s) => Span::styled(format!("{:2?}", date.day()), style.patch(s)),
+    /// The style of `layer` for `date`, if it applies to the date.
+    fn layer_style(
+        &self,
+        layer: StyleLayer,
+        date: Date,
+        state: Option<&CalendarState>,
+    ) -> Option<Style> {
+        let displayed = date.month() == self.display_date.month();
+        match layer {
+            StyleLayer::Weekday => Some(self.weekday_style(date.weekday())),
+            StyleLayer::Weekend => self
+                .is_weekend(date.weekday())
+                .then_some(self.weekend_style),
+            StyleLayer::Surrounding => self.show_surrounding.filter(|_| !displayed),
+            StyleLayer::Event => Some(self.events.get_style(date)),
+            StyleLayer::Relative => {
+                let (reference, f) = self.relative.as_ref()?;
+                f((date - *reference).whole_days())
             }
-        } else {
-            Span::styled(format!("{:2?}", date.day()), style)
+            StyleLayer::Holiday => self.holiday(date).map(|_| self.holiday_style),
+            StyleLayer::WeekHighlight => state
+                .filter(|s| week_of(date, self.week_start) == week_of(s.focused, self.week_start))
+                .map(|_| self.week_highlight_style),
+            StyleLayer::Today => {
+                (self.show_today && self.today == Some(date)).then_some(self.today_style)
+            }
+            StyleLayer::Disabled => (!self.is_enabled(date)).then_some(self.disabled_style),
+            StyleLayer::Range => state
+                .and_then(CalendarState::shown_range)
+                .filter(|r| r.contains(&date))
+                .map(|_| self.range_style),
+            StyleLayer::Reference => state
+                .filter(|s| s.reference() == Some(date))
+                .map(|_| self.reference_style),
+            StyleLayer::Selected => state
+                .filter(|s| s.is_selected(date))
+                .map(|_| self.selected_style),
+            StyleLayer::Focused => state
+                .filter(|s| s.focused == date && displayed)
+                .map(|_| self.focused_style),
         }
     }
 }
 
 impl<'a, S: DateStyler> Widget for Calendar<'a, S> {
-    fn render(mut self, area: Rect, buf: &mut Buffer) {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.render_month(area, buf, None);
+    }
+}
+
+impl<'a, S: DateStyler> StatefulWidget for Calendar<'a, S> {
+    type State = CalendarState;
+
+    fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        // the displayed month follows the focus
+        self.display_date = state.focused;
+        state.focused_holiday = self.holiday(state.focused).map(Cow::into_owned);
+        state.bounds = self.state_bounds();
+        state.today = Some(self.today_date());
+        let cells = self.render_month(area, buf, Some(state));
+        // the weeks drawn, when the area is too short for all of them
+        state.scroll = Some(cells.first);
+        state.set_cells(vec![cells]);
+    }
+}
+
+impl<'a, S: DateStyler> Calendar<'a, S> {
+    fn render_month(
+        mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: Option<&CalendarState>,
+    ) -> Cells {
+        if let (HeaderMode::BlockTitle, Some(style), true) =
+            (self.header_mode, self.show_month, self.block.is_some())
+        {
+            let title = Span::styled(self.month_title(), style);
+            self.block = self.block.take().map(|b| b.title(title));
+            self.show_month = None;
+        }
+        let area = self.render_frame(area, buf);
+        self.draw_month(area, buf, state)
+    }
+
+    /// Today's date: the date set with [`today`](Self::today), or else from the clock, which is
+    /// then kept for the rest of the render.
+    fn today_date(&mut self) -> Date {
+        *self.today.get_or_insert_with(|| match &self.clock {
+            Some(clock) => clock().date(),
+            None => local_today(),
+        })
+    }
+
+    /// Get ready to draw months: look up today's date, and draw the block. Returns the area inside
+    /// the block.
+    fn render_frame(&mut self, area: Rect, buf: &mut Buffer) -> Rect {
+        // Look up the date once, rather than for every day
+        if self.show_today {
+            self.today_date();
+        }
+
         // Block is used for borders and such
         // Draw that first, and use the blank area inside the block for our own purposes
-        let mut area = match self.block.take() {
+        match self.block.take() {
             None => area,
             Some(b) => {
                 let inner = b.inner(area);
                 b.render(area, buf);
                 inner
             }
+        }
+    }
+
+    /// Draw the month of the `display_date`, with its headers, at the top left of `area`. Returns
+    /// where the days were drawn.
+    fn draw_month(&self, mut area: Rect, buf: &mut Buffer, state: Option<&CalendarState>) -> Cells {
+        // Set the start of the calendar to the start of the week containing the 1st
+        let first_of_month = self.display_date.replace_day(1).unwrap();
+        let offset = Duration::days(days_since(self.week_start, first_of_month.weekday()).into());
+        let mut curr_day = first_of_month - offset;
+
+        // the week numbers, right aligned in a column as wide as the widest
+        let numbers: Vec<String> = match self.week_numbers {
+            Some(_) => iter::successors(Some(curr_day), |d| d.checked_add(Duration::WEEK))
+                .take_while(|d| *d == curr_day || d.month() == first_of_month.month())
+                .map(|d| self.week_numbering.number(d, self.week_start).to_string())
+                .collect(),
+            None => Vec::new(),
+        };
+        let digits = numbers.iter().map(String::len).max().map(|n| n.max(2));
+        let mut column = digits.unwrap_or_default() as u16;
+
+        let mut layout = if self.compact || area.width.saturating_sub(column) < NORMAL.width() {
+            COMPACT
+        } else if self.stretch {
+            // spread the width over the days
+            let cell = std::cmp::max((area.width - column) / 7 - NORMAL.gap, NORMAL.cell);
+            Layout { cell, ..NORMAL }
+        } else {
+            NORMAL
+        };
+        // the day of the year is left out rather than overflowing the days
+        let widened = Layout {
+            cell: std::cmp::max(layout.cell, 3),
+            ..layout
         };
+        let ordinal =
+            self.show_ordinal && layout != COMPACT && widened.width() + column <= area.width;
+        if ordinal {
+            layout = widened;
+        }
+        if layout.gap == 0 && column > 0 {
+            // compact days have no gap to set them apart from the numbers
+            column += 1;
+        }
+
+        // the headers make way for the weeks when the area is too short for all of them: first the
+        // month name, then the weekday separator and names
+        let days = offset.whole_days() as u16
+            + first_of_month.month().length(first_of_month.year()) as u16;
+        let weeks = days.div_ceil(7);
+        let mut title = self
+            .show_month
+            .filter(|_| area.height >= 1 + self.weekday_rows() + weeks);
+        let mut header_rows = self.weekday_rows();
+        if title.is_none() {
+            header_rows = std::cmp::min(header_rows, area.height.saturating_sub(weeks));
+        }
 
         // Draw the month name and year
-        if let Some(style) = self.show_month {
-            let line = Span::styled(
-                format!("{} {}", self.display_date.month(), self.display_date.year()),
-                style,
-            );
-            // cal is 21 cells wide, so hard code the 11
-            let x_off = 11_u16.saturating_sub(line.width() as u16 / 2);
-            buf.set_spans(area.x + x_off, area.y, &line.into(), area.width);
-            area.y += 1
+        let mut arrows = None;
+        if let (Some(style), true) = (title.take(), area.height > 0) {
+            let line = Span::styled(self.month_title(), style);
+            let centre = layout
+                .width()
+                .div_ceil(2)
+                .saturating_sub(line.width() as u16 / 2);
+            let title_width = line.width() as u16;
+            let x = area.x + std::cmp::min(column, area.width);
+            let width = std::cmp::min(layout.width(), area.width.saturating_sub(column));
+            let header = Rect::new(x, area.y, width, 1);
+            if self.header_arrows && !arrows_beside(header, centre, title_width) && width >= 2 {
+                // the arrows take the edges, so the title is centred and cut between them
+                let inner = width - 2;
+                let x_off = 1 + inner.saturating_sub(title_width) / 2;
+                buf.set_spans(header.x + x_off, area.y, &line.into(), inner + 1 - x_off);
+            } else if column + centre < area.width {
+                let x_off = column + centre;
+                buf.set_spans(area.x + x_off, area.y, &line.into(), area.width - x_off);
+            }
+            if self.header_arrows {
+                arrows = draw_arrows(buf, header, centre, title_width, style);
+            }
+            area.y += 1;
+            area.height -= 1;
         }
 
+        // the week numbers go left of the days
+        let numbers_x = area.x;
+        let column = std::cmp::min(column, area.width);
+        area.x += column;
+        area.width -= column;
+
         // Draw days of week
-        if let Some(style) = self.show_weekday {
-            let days = String::from(" Su Mo Tu We Th Fr Sa");
-            buf.set_string(area.x, area.y, days, style);
-            area.y += 1;
+        let mut area = self.draw_weekdays(area, buf, layout, header_rows);
+
+        if self.stretch && layout != COMPACT {
+            // spread the height over the weeks
+            layout.rows = std::cmp::max(area.height / weeks, 1);
+        }
+        if self.cell_height > layout.rows && area.height >= weeks * self.cell_height {
+            layout.rows = self.cell_height;
         }
+        // only the weeks around the focus are drawn when they don't all fit
+        let shown = area.height / layout.rows;
+        let skip = match state {
+            Some(state) if shown > 0 && shown < weeks => {
+                scroll_weeks(curr_day, weeks, shown, state, self.display_date)
+            }
+            _ => 0,
+        };
+        curr_day += Duration::WEEK * skip;
+        let mut cells = Cells {
+            area: Rect::new(area.x, area.y, std::cmp::min(area.width, layout.width()), 0),
+            gap: layout.gap,
+            cell: layout.cell,
+            rows: layout.rows,
+            first: curr_day,
+            month: self.display_date.month(),
+            surrounding: self.show_surrounding.is_some(),
+            arrows,
+            disabled: 0,
+        };
 
-        // Set the start of the calendar to the Sunday before the 1st (or the sunday of the first)
-        let first_of_month = self.display_date.replace_day(1).unwrap();
-        let offset = Duration::days(first_of_month.weekday().number_days_from_sunday().into());
-        let mut curr_day = first_of_month - offset;
+        // the day of the year goes below the day of the month if there is room, and annotations
+        // below that
+        let ordinal_below = ordinal && layout.rows > 1;
+        let note_row = 1 + u16::from(ordinal_below);
+        let note_below = layout.rows > note_row;
 
-        // go through all the weeks containing a day in the target month.
-        while curr_day.month() as u8 != self.display_date.month().next() as u8 {
-            let mut line = Spans(Vec::with_capacity(14));
+        // go through all the weeks containing a day in the target month, while they fit.
+        let mut week = 0;
+        while curr_day.month() as u8 != self.display_date.month().next() as u8 && area.height > 0 {
+            let mut line = Spans(Vec::with_capacity(15));
+            // the other lines of the week's row, when the rows are stretched
+            let mut blank = Spans(Vec::with_capacity(14));
+            let mut ordinals = Spans(Vec::with_capacity(14));
+            // the days drawn by the cell renderer, and their columns
+            let mut custom = Vec::new();
+            // the annotations, with their columns and widths
+            let mut notes = Vec::new();
+            // the lines of content below the days, with their rows and columns
+            let mut content = Vec::new();
+            // the marker of the previous day, drawn in the gutter after it
+            let mut marker = None;
             for i in 0..7 {
                 // Draw the gutter. Do it here so we can avoid worrying about
                 // styling the ' ' in the format_date method
-                if i == 0 {
-                    line.0.push(Span::styled(" ", Style::default()));
-                } else {
-                    line.0.push(Span::styled(" ", self.default_bg()));
+                let gutter = match i {
+                    0 => Span::styled(layout.gap_str(), Style::default()),
+                    _ => Span::styled(layout.gap_str(), self.default_bg()),
+                };
+                blank.0.push(gutter.clone());
+                ordinals.0.push(gutter.clone());
+                line.0.push(marker.take().unwrap_or(gutter));
+                let hidden = curr_day.month() != self.display_date.month()
+                    && self.show_surrounding.is_none();
+                let mut day = self.format_date(curr_day, state);
+                if ordinal && !ordinal_below && !hidden {
+                    day.content = format!("{:>3}", curr_day.ordinal()).into();
+                    day.style = day.style.patch(self.ordinal_style);
+                }
+                let x = i * (layout.gap + layout.cell) + layout.gap;
+                let note = match hidden {
+                    false => self.annotation(curr_day),
+                    true => None,
+                };
+                if layout.cell > NORMAL.cell {
+                    day.content = layout.align(&day.content).into();
+                }
+                let today = self.show_today && self.today == Some(curr_day);
+                if today && !hidden && self.cell_renderer.is_none() {
+                    // the last day only has a gutter after it if the area is wider than the month
+                    let room = i < 6 || area.width > layout.width();
+                    let (before, after) = self.today_marker.chars();
+                    match mark(&day.content, before, after) {
+                        (cell, None, None) => day.content = cell.into(),
+                        (cell, before, after) if layout.gap > 0 && (after.is_none() || room) => {
+                            let glyph = |c: char| Span::styled(c.to_string(), day.style);
+                            if let (Some(c), Some(gutter)) = (before, line.0.last_mut()) {
+                                *gutter = glyph(c);
+                            }
+                            marker = after.map(glyph);
+                            day.content = cell.into();
+                        }
+                        // squeezed into the cell, when there are no gaps to use
+                        _ => {
+                            let text: String = before
+                                .into_iter()
+                                .chain(day.content.trim().chars())
+                                .chain(after)
+                                .collect();
+                            if text.chars().count() <= layout.cell.into() {
+                                day.content = layout.align(&text).into();
+                            }
+                        }
+                    }
+                }
+                let mut note_inline = false;
+                // the rows taken below the number
+                let mut below = u16::from(ordinal_below);
+                if let Some(note) = note {
+                    let note = Span::styled(note.content, day.style.patch(note.style));
+                    // the columns up to the end of the number, and a space after it
+                    let skip = day.content.trim_end().len() as u16 + 1;
+                    if note_below {
+                        notes.push((note_row, x, layout.cell, note));
+                        below += 1;
+                    } else if layout.cell > skip && self.cell_renderer.is_none() {
+                        notes.push((0, x + skip, layout.cell - skip, note));
+                        note_inline = true;
+                    }
+                }
+                let reference = state.and_then(CalendarState::reference);
+                let glyph = match self.reference_marker {
+                    Some(c) if reference == Some(curr_day) && !hidden => {
+                        Some((c, self.reference_style, Style::default()))
+                    }
+                    _ => self
+                        .marker(curr_day)
+                        .map(|c| (c, self.marker_style, self.marker_style)),
+                };
+                // the last day only has a gutter after it if the area is wider than the month
+                let room = i < 6 || area.width > layout.width();
+                if let Some((c, style, fallback)) = glyph {
+                    let glyph = Span::styled(c.to_string(), self.default_bg().patch(style));
+                    if layout.gap > 0 && glyph.width() == 1 && room && marker.is_none() {
+                        marker = Some(glyph);
+                    } else {
+                        day.style = day.style.patch(fallback);
+                    }
+                }
+                let badge = match hidden {
+                    false => self.count_badge(curr_day),
+                    true => None,
+                };
+                if let Some(badge) = badge {
+                    let badge = Span::raw(badge);
+                    let end = day.content.trim_end().len() as u16;
+                    let inline = self.cell_renderer.is_none() && !note_inline;
+                    if inline && layout.cell >= end + badge.width() as u16 {
+                        let style = day.style.patch(self.count_style);
+                        let width = layout.cell - end;
+                        notes.push((0, x + end, width, Span::styled(badge.content, style)));
+                    } else if layout.gap > 0 && room && marker.is_none() {
+                        let glyph = match badge.width() {
+                            1 => badge.content,
+                            _ => "•".into(),
+                        };
+                        marker = Some(Span::styled(
+                            glyph,
+                            self.default_bg().patch(self.count_style),
+                        ));
+                    } else if layout.gap == 0 && day.content.starts_with(' ') && inline {
+                        day.content = format!("•{}", day.content.trim_start()).into();
+                    } else {
+                        day.style = day.style.patch(self.count_style);
+                    }
+                }
+                if let (Some(f), false) = (&self.cell_content, hidden) {
+                    let lines = f(curr_day, layout.cell).into_iter();
+                    content.extend((below + 1..layout.rows).zip(lines).map(|(r, l)| (r, x, l)));
+                }
+                let empty = Span::styled(" ".repeat(layout.cell as usize), day.style);
+                if let (Some(f), false) = (&self.cell_renderer, hidden) {
+                    let cx = self.cell_context(curr_day, state, layout.cell, day.style);
+                    custom.push((x, f(curr_day, cx)));
+                    day = empty.clone();
+                }
+                ordinals.0.push(match hidden {
+                    false if ordinal_below => Span::styled(
+                        layout.align(&curr_day.ordinal().to_string()),
+                        day.style.patch(self.ordinal_style),
+                    ),
+                    _ => empty.clone(),
+                });
+                blank.0.push(empty);
+                line.0.push(day);
+                if !self.is_enabled(curr_day) {
+                    cells.disabled |= 1 << (week * 7 + i);
                 }
-                line.0.push(self.format_date(curr_day));
                 curr_day += Duration::DAY;
             }
-            buf.set_spans(area.x, area.y, &line, area.width);
-            area.y += 1;
+            line.0.extend(marker);
+            let rows = std::cmp::min(layout.rows, area.height);
+            if let (Some(style), Some(number), Some(digits)) = (
+                self.week_numbers,
+                numbers.get(usize::from(skip + week)),
+                digits,
+            ) {
+                let number = format!("{:>digits$}", number);
+                buf.set_stringn(numbers_x, area.y, number, column.into(), style);
+            }
+            for row in 0..rows {
+                let spans = match row {
+                    0 => &line,
+                    1 if ordinal_below => &ordinals,
+                    _ => &blank,
+                };
+                buf.set_spans(area.x, area.y + row, spans, area.width);
+            }
+            if rows > 0 {
+                for (x, spans) in &custom {
+                    let width = std::cmp::min(layout.cell, area.width.saturating_sub(*x));
+                    if width > 0 {
+                        buf.set_spans(area.x + x, area.y, spans, width);
+                    }
+                }
+            }
+            for (row, x, spans) in content {
+                let width = std::cmp::min(layout.cell, area.width.saturating_sub(x));
+                if row < rows && width > 0 {
+                    buf.set_spans(area.x + x, area.y + row, &spans, width);
+                }
+            }
+            for (row, x, width, note) in notes {
+                let width = std::cmp::min(width, area.width.saturating_sub(x));
+                if row < rows && width > 0 {
+                    buf.set_spans(area.x + x, area.y + row, &note.into(), width);
+                }
+            }
+            area.y += rows;
+            area.height -= rows;
+            cells.area.height += rows;
+            week += 1;
         }
+        cells
     }
 }
 
+/// The widths of the parts of a row of days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Layout {
+    /// The blank columns before each day.
+    gap: u16,
+    /// The columns for the day's number.
+    cell: u16,
+    /// The number of lines of each week.
+    rows: u16,
+}
+
+const NORMAL: Layout = Layout {
+    gap: 1,
+    cell: 2,
+    rows: 1,
+};
+
+const COMPACT: Layout = Layout {
+    gap: 0,
+    cell: 2,
+    rows: 1,
+};
+
+impl Layout {
+    /// The width of a week.
+    fn width(self) -> u16 {
+        7 * (self.gap + self.cell)
+    }
+
+    fn gap_str(self) -> &'static str {
+        &"  "[..self.gap as usize]
+    }
+
+    /// Fit `text` to the width of a day: right aligned in normal days, and centered in stretched
+    /// ones.
+    fn align(self, text: &str) -> String {
+        // padded by columns rather than characters, for double width text
+        let pad = usize::from(self.cell).saturating_sub(text.width());
+        let before = match self.cell > NORMAL.cell {
+            true => pad / 2,
+            false => pad,
+        };
+        format!("{}{text}{}", " ".repeat(before), " ".repeat(pad - before))
+    }
+}
+
+/// Put `before` and `after` around the text of `cell`, in its spaces where there are any. Returns
+/// the cell and the characters that didn't fit in it.
+fn mark(
+    cell: &str,
+    before: Option<char>,
+    after: Option<char>,
+) -> (String, Option<char>, Option<char>) {
+    let text = cell.trim();
+    let lead = cell.len() - cell.trim_start().len();
+    let trail = cell.len() - cell.trim_end().len();
+    let mut marked = String::with_capacity(cell.len() + 2);
+    let before = match before {
+        Some(c) if lead > 0 => {
+            marked.push_str(&cell[1..lead]);
+            marked.push(c);
+            None
+        }
+        c => {
+            marked.push_str(&cell[..lead]);
+            c
+        }
+    };
+    marked.push_str(text);
+    let after = match after {
+        Some(c) if trail > 0 => {
+            marked.push(c);
+            marked.push_str(&cell[cell.len() - trail + 1..]);
+            None
+        }
+        c => {
+            marked.push_str(&cell[cell.len() - trail..]);
+            c
+        }
+    };
+    (marked, before, after)
+}
+
+/// Is there room on the `line` over the days for the arrows beside a title `width` wide drawn
+/// `centre` columns in?
+fn arrows_beside(line: Rect, centre: u16, width: u16) -> bool {
+    centre >= 2 && centre + width + 2 <= line.width
+}
+
+/// Draw the arrows for the previous and next months on the `line` over the days, beside a title
+/// `width` wide drawn `centre` columns in, returning where they were drawn. They are drawn at the
+/// edges of the line when there isn't room beside the title.
+fn draw_arrows(
+    buf: &mut Buffer,
+    line: Rect,
+    centre: u16,
+    width: u16,
+    style: Style,
+) -> Option<(Rect, Rect)> {
+    if arrows_beside(line, centre, width) {
+        let prev = Rect::new(line.x + centre - 2, line.y, 2, 1);
+        let next = Rect::new(line.x + centre + width, line.y, 2, 1);
+        buf.set_string(prev.x, prev.y, "◂ ", style);
+        buf.set_string(next.x, next.y, " ▸", style);
+        Some((prev, next))
+    } else if line.width >= 2 {
+        let prev = Rect::new(line.x, line.y, 1, 1);
+        let next = Rect::new(line.right() - 1, line.y, 1, 1);
+        buf.set_string(prev.x, prev.y, "◂", style);
+        buf.set_string(next.x, next.y, "▸", style);
+        Some((prev, next))
+    } else {
+        None
+    }
+}
+
+/// The number of weeks to skip from `first`, so `shown` of the `weeks` of the month of `display`
+/// are drawn, including the focused week, and moving as little as possible from the weeks drawn
+/// by the last render.
+fn scroll_weeks(first: Date, weeks: u16, shown: u16, state: &CalendarState, display: Date) -> u16 {
+    let index = |date: Date| {
+        u16::try_from((date - first).whole_weeks())
+            .ok()
+            .filter(|n| *n < weeks)
+    };
+    let prev = state.scroll.and_then(index).unwrap_or(0);
+    let focused = (state.focused.month() == display.month())
+        .then(|| index(state.focused))
+        .flatten();
+    let skip = match focused {
+        Some(week) => prev.clamp((week + 1).saturating_sub(shown), week),
+        None => prev,
+    };
+    std::cmp::min(skip, weeks - shown)
+}
+
+/// A bit for `day`, for sets of weekdays.
+fn weekday_bit(day: Weekday) -> u8 {
+    1 << day.number_days_from_monday()
+}
+
+/// The number of days from `start` to the next `day` (0 if they are the same day).
+fn days_since(start: Weekday, day: Weekday) -> u8 {
+    (day.number_days_from_monday() + 7 - start.number_days_from_monday()) % 7
+}
+
+/// The first day of the week containing `date`.
+fn week_of(date: Date, week_start: Weekday) -> Date {
+    let offset = Duration::days(days_since(week_start, date.weekday()).into());
+    date.checked_sub(offset).unwrap_or(date)
+}
+
+/// Today's date in the local time zone. If the local offset can't be determined, the date in UTC is
+/// used.
+fn local_today() -> Date {
+    OffsetDateTime::now_local()
+        .unwrap_or_else(|_| OffsetDateTime::now_utc())
+        .date()
+}
+
 /// Provides a method for styling a given date. [Calendar] is generic on this trait, so any type
 /// that implements this trait can be used.
+///
+/// The styler is only asked for the dates that are drawn, so days of the surrounding months aren't
+/// looked up unless `show_surrounding` is set. It is implemented for references to stylers, so a
+/// store kept by the app can be borrowed by the calendar each frame rather than cloned.
 pub trait DateStyler {
     /// Given a date, return a style for that date
     fn get_style(&self, date: Date) -> Style;
 }
 
+impl<S: DateStyler + ?Sized> DateStyler for &S {
+    fn get_style(&self, date: Date) -> Style {
+        (**self).get_style(date)
+    }
+}
+
+/// Dates in the map are styled with their style, and other dates aren't styled.
+impl DateStyler for HashMap<Date, Style> {
+    fn get_style(&self, date: Date) -> Style {
+        self.get(&date).copied().unwrap_or_default()
+    }
+}
+
 /// A simple DateStyler based on a [HashMap]
 pub struct CalendarEventStore(pub HashMap<Date, Style>);
 
@@ -181,22 +1655,11 @@ impl CalendarEventStore {
         // to simplify style nonsense, last write wins
         let _ = self.0.insert(date, style);
     }
-
-    /// Helper for trait impls
-    fn lookup_style(&self, date: Date) -> Style {
-        self.0.get(&date).copied().unwrap_or_default()
-    }
 }
 
 impl DateStyler for CalendarEventStore {
     fn get_style(&self, date: Date) -> Style {
-        self.lookup_style(date)
-    }
-}
-
-impl DateStyler for &CalendarEventStore {
-    fn get_style(&self, date: Date) -> Style {
-        self.lookup_style(date)
+        self.0.get_style(date)
     }
 }
 
@@ -205,3 +1668,1261 @@ impl Default for CalendarEventStore {
         Self(HashMap::with_capacity(4))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{
+        style::{Color, Modifier},
+        widgets::Borders,
+    };
+    use time::macros::date;
+
+    use super::{
+        testing::{render_month, render_month_with, Snapshot},
+        *,
+    };
+
+    #[test]
+    fn week_start() {
+        let cal = |start| {
+            Calendar::new(date!(2023 - 04 - 12), CalendarEventStore::default())
+                .show_weekdays(Style::default())
+                .week_start(start)
+        };
+        assert_eq!(
+            render_month(cal(Weekday::Sunday), 21, 7).lines(),
+            [
+                " Su Mo Tu We Th Fr Sa",
+                "                    1",
+                "  2  3  4  5  6  7  8",
+                "  9 10 11 12 13 14 15",
+                " 16 17 18 19 20 21 22",
+                " 23 24 25 26 27 28 29",
+                " 30                  ",
+            ]
+        );
+        assert_eq!(
+            render_month(cal(Weekday::Monday), 21, 7).lines(),
+            [
+                " Mo Tu We Th Fr Sa Su",
+                "                 1  2",
+                "  3  4  5  6  7  8  9",
+                " 10 11 12 13 14 15 16",
+                " 17 18 19 20 21 22 23",
+                " 24 25 26 27 28 29 30",
+                "                     ",
+            ]
+        );
+        assert_eq!(
+            render_month(cal(Weekday::Saturday), 21, 7).lines(),
+            [
+                " Sa Su Mo Tu We Th Fr",
+                "  1  2  3  4  5  6  7",
+                "  8  9 10 11 12 13 14",
+                " 15 16 17 18 19 20 21",
+                " 22 23 24 25 26 27 28",
+                " 29 30               ",
+                "                     ",
+            ]
+        );
+    }
+
+    #[test]
+    fn surrounding() {
+        let dim = Style::default().fg(Color::DarkGray);
+        let red = Style::default().fg(Color::Red);
+        let blue = Style::default().bg(Color::Blue);
+        let mut events = CalendarEventStore::default();
+        events.add(date!(2023 - 05 - 01), red);
+        let cal = Calendar::new(date!(2023 - 04 - 01), events)
+            .show_surrounding(dim)
+            .selected_style(blue);
+
+        let mut state = CalendarState::new(date!(2023 - 04 - 01));
+        state.focus(date!(2023 - 04 - 30));
+        state.toggle(date!(2023 - 03 - 26));
+        let snapshot = render_month_with(cal, &mut state, 21, 6);
+        assert_eq!(
+            snapshot.lines(),
+            [
+                " 26 27 28 29 30 31  1",
+                "  2  3  4  5  6  7  8",
+                "  9 10 11 12 13 14 15",
+                " 16 17 18 19 20 21 22",
+                " 23 24 25 26 27 28 29",
+                " 30  1  2  3  4  5  6",
+            ]
+        );
+        // the surrounding style is under the event and selected styles
+        assert_eq!(
+            snapshot.style_map(&[('d', dim), ('r', red), ('b', dim.patch(blue))]),
+            [
+                ".bb.dd.dd.dd.dd.dd...",
+                ".....................",
+                ".....................",
+                ".....................",
+                ".....................",
+                "....rr.dd.dd.dd.dd.dd",
+            ]
+        );
+    }
+
+    #[test]
+    fn borrowed_events() {
+        let red = Style::default().fg(Color::Red);
+        let mut map = HashMap::new();
+        map.insert(date!(2023 - 04 - 12), red);
+        map.insert(date!(2023 - 05 - 01), red);
+
+        let snapshot = render_month(Calendar::new(date!(2023 - 04 - 01), &map), 21, 6);
+        let store = CalendarEventStore(map.clone());
+        assert_eq!(
+            snapshot,
+            render_month(Calendar::new(date!(2023 - 04 - 01), store), 21, 6)
+        );
+        assert_eq!(snapshot.style_at(10, 2).fg, Some(Color::Red));
+
+        // hidden days of the surrounding months aren't looked up
+        struct AprilOnly;
+        impl DateStyler for AprilOnly {
+            fn get_style(&self, date: Date) -> Style {
+                assert_eq!(date.month(), time::Month::April);
+                Style::default()
+            }
+        }
+        render_month(Calendar::new(date!(2023 - 04 - 01), AprilOnly), 21, 6);
+    }
+
+    #[test]
+    fn range() {
+        let green = Style::default().bg(Color::Green);
+        let mut state = CalendarState::new(date!(2023 - 03 - 29));
+        state.begin_range();
+        state.move_days(5);
+
+        // the range started last month is highlighted up to the focus
+        let cal =
+            Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default()).range_style(green);
+        let snapshot = render_month_with(cal, &mut state, 21, 3);
+        assert_eq!(
+            snapshot.lines(),
+            [
+                "                    1",
+                "  2  3  4  5  6  7  8",
+                "  9 10 11 12 13 14 15",
+            ]
+        );
+        assert_eq!(
+            snapshot.style_map(&[('g', green)]),
+            [
+                "...................gg",
+                ".gg.gg...............",
+                ".....................",
+            ]
+        );
+    }
+
+    #[test]
+    fn disabled() {
+        let dim = Style::default().fg(Color::DarkGray);
+        let cal = Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+            .bounds(Some(date!(2023 - 04 - 03)), Some(date!(2023 - 04 - 28)))
+            .disabled(|d| d.weekday() == Weekday::Wednesday)
+            .disabled_style(dim);
+        assert!(cal.is_enabled(date!(2023 - 04 - 03)));
+        assert!(!cal.is_enabled(date!(2023 - 04 - 02)));
+        assert!(!cal.is_enabled(date!(2023 - 04 - 12)));
+
+        let mut state = CalendarState::new(date!(2023 - 04 - 11));
+        state.move_days_within(1, |d| cal.is_enabled(d));
+        assert_eq!(state.focused(), date!(2023 - 04 - 13));
+
+        assert_eq!(
+            render_month(cal, 21, 6).style_map(&[('d', dim)]),
+            [
+                "...................dd",
+                ".dd.......dd.........",
+                "..........dd.........",
+                "..........dd.........",
+                "..........dd.......dd",
+                ".dd..................",
+            ]
+        );
+    }
+
+    #[test]
+    fn selectable() {
+        // business days, with May 2023 closed
+        let cal = Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+            .selectable(|d| !matches!(d.weekday(), Weekday::Saturday | Weekday::Sunday))
+            .disabled(|d| d.month() == time::Month::May);
+        let enabled = |d| cal.is_enabled(d);
+        assert!(cal.is_enabled(date!(2023 - 04 - 14)));
+        assert!(!cal.is_enabled(date!(2023 - 04 - 15)));
+        assert!(!cal.is_enabled(date!(2023 - 05 - 10)));
+
+        let mut state = CalendarState::new(date!(2023 - 04 - 14));
+        state.move_days_within(1, enabled);
+        assert_eq!(state.focused(), date!(2023 - 04 - 17));
+        assert!(!state.select_within(date!(2023 - 04 - 22), enabled));
+        assert_eq!(state.selected(), None);
+        assert!(state.select_within(date!(2023 - 04 - 28), enabled));
+        assert_eq!(state.focused(), date!(2023 - 04 - 28));
+
+        // paging skips the closed month, or stops before it
+        state.move_months_within(1, enabled);
+        assert_eq!(state.focused(), date!(2023 - 06 - 28));
+        state.navigation(Navigation::Clamp);
+        state.move_months_within(-1, enabled);
+        assert_eq!(state.focused(), date!(2023 - 06 - 28));
+
+        // nothing selectable doesn't loop forever
+        let closed = Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+            .selectable(|_| false);
+        state.navigation(Navigation::Skip);
+        state.move_days_within(1, |d| closed.is_enabled(d));
+        state.move_months_within(1, |d| closed.is_enabled(d));
+        assert_eq!(state.focused(), date!(2023 - 06 - 28));
+        assert_eq!(
+            render_month(closed, 21, 1).lines(),
+            ["                    1"]
+        );
+    }
+
+    #[test]
+    fn clicks() {
+        let cal = Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+            .show_month(Style::default())
+            .show_weekdays(Style::default())
+            .bounds(None, Some(date!(2023 - 04 - 20)))
+            .block(Block::default().borders(Borders::ALL));
+        let area = Rect::new(10, 5, 23, 10);
+        let mut buf = Buffer::empty(area);
+        let mut state = CalendarState::new(date!(2023 - 04 - 05));
+        assert_eq!(state.date_at(30, 8), None);
+        StatefulWidget::render(cal, area, &mut buf, &mut state);
+
+        // the first row of days is below the border and two headers
+        assert_eq!(state.date_at(30, 8), Some(date!(2023 - 04 - 01)));
+        assert_eq!(state.date_at(12, 9), Some(date!(2023 - 04 - 02)));
+        assert_eq!(state.date_at(13, 13), Some(date!(2023 - 04 - 30)));
+        // the gap between days, headers, hidden days and the border
+        assert_eq!(state.date_at(14, 9), None);
+        assert_eq!(state.date_at(30, 7), None);
+        assert_eq!(state.date_at(12, 8), None);
+        assert_eq!(state.date_at(10, 9), None);
+        assert_eq!(state.date_at(16, 13), None);
+
+        assert_eq!(state.select_at(15, 10), Some(date!(2023 - 04 - 10)));
+        assert_eq!(state.selected(), Some(date!(2023 - 04 - 10)));
+        assert_eq!(state.focused(), date!(2023 - 04 - 10));
+        // disabled dates aren't selected
+        assert_eq!(state.select_at(18, 12), Some(date!(2023 - 04 - 25)));
+        assert_eq!(state.selected(), Some(date!(2023 - 04 - 10)));
+    }
+
+    #[test]
+    fn cell_rect() {
+        let cal = || {
+            Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+                .show_month(Style::default())
+                .show_weekdays(Style::default())
+        };
+        let mut state = CalendarState::new(date!(2023 - 04 - 05));
+        assert_eq!(state.cell_rect(date!(2023 - 04 - 01)), None);
+        let area = Rect::new(10, 5, 23, 10);
+        let mut buf = Buffer::empty(area);
+        let framed = cal().block(Block::default().borders(Borders::ALL));
+        StatefulWidget::render(framed, area, &mut buf, &mut state);
+        assert_eq!(
+            state.cell_rect(date!(2023 - 04 - 01)),
+            Some(Rect::new(30, 8, 2, 1))
+        );
+        assert_eq!(
+            state.cell_rect(date!(2023 - 04 - 30)),
+            Some(Rect::new(12, 13, 2, 1))
+        );
+        // hidden days, and days of other months
+        assert_eq!(state.cell_rect(date!(2023 - 03 - 31)), None);
+        assert_eq!(state.cell_rect(date!(2023 - 05 - 10)), None);
+
+        // the cells are where the dates are found, whatever the layout
+        let layouts = [
+            cal().compact(true).show_week_numbers(Style::default()),
+            cal().cell_height(2),
+            cal().stretch(true),
+        ];
+        for cal in layouts {
+            let cal = cal.show_surrounding(Style::default());
+            render_month_with(cal, &mut state, 30, 16);
+            let days = iter::successors(Some(date!(2023 - 03 - 26)), |d| d.next_day());
+            for date in days.take(42) {
+                let rect = state.cell_rect(date).unwrap();
+                assert_eq!(state.date_at(rect.x, rect.y), Some(date));
+                let (right, bottom) = (rect.right() - 1, rect.bottom() - 1);
+                assert_eq!(state.date_at(right, bottom), Some(date));
+            }
+        }
+
+        // not in the weeks left out of short areas
+        render_month_with(cal(), &mut state, 21, 3);
+        assert!(state.cell_rect(date!(2023 - 04 - 05)).is_some());
+        assert_eq!(state.cell_rect(date!(2023 - 04 - 30)), None);
+    }
+
+    #[test]
+    fn header_arrows() {
+        let cal = || {
+            Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+                .show_month(Style::default())
+                .header_arrows(true)
+        };
+        let mut state = CalendarState::new(date!(2023 - 04 - 05));
+        let area = Rect::new(0, 0, 21, 7);
+        let lines = render_month_with(cal(), &mut state, 21, 7).lines();
+        assert_eq!(lines[0], "    ◂ April 2023 ▸   ");
+
+        // the arrows page the month, and dates are selected
+        assert_eq!(state.click(4, 0, area), CalendarClick::PrevMonth);
+        assert_eq!(state.focused(), date!(2023 - 03 - 05));
+        render_month_with(cal(), &mut state, 21, 7);
+        assert_eq!(state.click(17, 0, area), CalendarClick::NextMonth);
+        assert_eq!(state.focused(), date!(2023 - 04 - 05));
+        render_month_with(cal(), &mut state, 21, 7);
+        assert_eq!(
+            state.click(10, 2, area),
+            CalendarClick::Date(date!(2023 - 04 - 05))
+        );
+        assert_eq!(state.selected(), Some(date!(2023 - 04 - 05)));
+        // the title, gaps, and anything outside the area
+        assert_eq!(state.click(10, 0, area), CalendarClick::None);
+        assert_eq!(state.click(9, 2, area), CalendarClick::None);
+        assert_eq!(
+            state.click(10, 2, Rect::new(0, 0, 5, 5)),
+            CalendarClick::None
+        );
+
+        // single characters at the edges of narrow months
+        let lines = render_month_with(cal(), &mut state, 14, 7).lines();
+        assert_eq!(lines[0], "◂ April 2023 ▸");
+        state.focus(date!(2023 - 09 - 05));
+        let lines = render_month_with(cal(), &mut state, 14, 7).lines();
+        assert_eq!(lines[0], "◂September 20▸");
+        assert_eq!(
+            state.click(13, 0, Rect::new(0, 0, 14, 7)),
+            CalendarClick::NextMonth
+        );
+        let lines = render_month_with(cal(), &mut state, 11, 7).lines();
+        assert_eq!(lines[0], "◂October 2▸");
+
+        // not without a header line
+        let plain = Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default());
+        let lines = render_month_with(plain.header_arrows(true), &mut state, 21, 7).lines();
+        assert!(!lines.concat().contains('◂'));
+        assert_eq!(state.click(0, 0, area), CalendarClick::None);
+    }
+
+    #[test]
+    fn cell_content() {
+        let red = Style::default().fg(Color::Red);
+        let blue = Style::default().bg(Color::Blue);
+        let cal = || {
+            Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+                .cell_height(3)
+                .focused_style(blue)
+                .cell_content(|date, width| match date.day() {
+                    3 => vec![Spans::from(format!("w{width}")), Spans::from("dentist")],
+                    4 => vec![Spans::from(Span::styled("!", red))],
+                    _ => Vec::new(),
+                })
+        };
+        let mut state = CalendarState::new(date!(2023 - 04 - 03));
+        let snapshot = render_month_with(cal(), &mut state, 21, 18);
+        assert_eq!(
+            snapshot.lines()[3..6],
+            [
+                "  2  3  4  5  6  7  8",
+                "    w2 !             ",
+                "    de               ",
+            ]
+        );
+        // the focus covers the whole cell, under the content
+        let legend = [('b', blue), ('R', red)];
+        assert_eq!(
+            snapshot.style_map(&legend)[3..6],
+            [
+                "....bb...............",
+                "....bb.R.............",
+                "....bb...............",
+            ]
+        );
+        assert_eq!(state.date_at(4, 5), Some(date!(2023 - 04 - 03)));
+
+        // single lines when the weeks don't fit
+        let lines = render_month_with(cal(), &mut state, 21, 17).lines();
+        assert_eq!(lines[1], "  2  3  4  5  6  7  8");
+        assert_eq!(lines[2], "  9 10 11 12 13 14 15");
+    }
+
+    #[test]
+    fn block_title() {
+        let cal = || {
+            Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+                .show_month(Style::default())
+                .show_weekdays(Style::default())
+                .header_mode(HeaderMode::BlockTitle)
+                .block(Block::default().borders(Borders::ALL))
+        };
+        let mut state = CalendarState::new(date!(2023 - 04 - 05));
+        assert_eq!(
+            render_month_with(cal(), &mut state, 23, 9).lines(),
+            [
+                "┌April 2023───────────┐",
+                "│ Su Mo Tu We Th Fr Sa│",
+                "│                    1│",
+                "│  2  3  4  5  6  7  8│",
+                "│  9 10 11 12 13 14 15│",
+                "│ 16 17 18 19 20 21 22│",
+                "│ 23 24 25 26 27 28 29│",
+                "│ 30                  │",
+                "└─────────────────────┘",
+            ]
+        );
+        assert_eq!(state.date_at(20, 2), Some(date!(2023 - 04 - 01)));
+        assert_eq!(state.date_at(2, 7), Some(date!(2023 - 04 - 30)));
+
+        // without a block the header keeps its line
+        let plain = Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+            .show_month(Style::default())
+            .header_mode(HeaderMode::BlockTitle);
+        assert_eq!(
+            render_month(plain, 21, 7).lines()[0],
+            "      April 2023     "
+        );
+
+        // nothing is left inside the borders
+        for (w, h) in [(0, 0), (1, 1), (2, 2), (2, 9), (23, 2)] {
+            let area = Rect::new(0, 0, w, h);
+            let mut buf = Buffer::empty(area);
+            StatefulWidget::render(cal(), area, &mut buf, &mut state);
+            assert_eq!(state.date_at(0, 0), None);
+            assert_eq!(state.date_at(1, 1), None);
+        }
+    }
+
+    #[test]
+    fn relative() {
+        let cal = Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+            .show_surrounding(Style::default())
+            .relative_highlight(date!(2023 - 04 - 12), |n| match n {
+                ..=-1 => Some(Style::default().fg(Color::DarkGray)),
+                0..=2 => Some(Style::default().fg(Color::Yellow)),
+                _ => None,
+            });
+        let snapshot = render_month(cal, 21, 6);
+        let fg = |x, y| snapshot.buffer().get(x, y).fg;
+        // the days before, including those of March
+        assert_eq!(fg(1, 0), Color::DarkGray);
+        assert_eq!(fg(19, 0), Color::DarkGray);
+        assert_eq!(fg(7, 2), Color::DarkGray);
+        // the reference and the next two days
+        assert_eq!(fg(10, 2), Color::Yellow);
+        assert_eq!(fg(16, 2), Color::Yellow);
+        // later days aren't styled
+        assert_eq!(fg(19, 2), Color::Reset);
+        assert_eq!(fg(1, 5), Color::Reset);
+    }
+
+    #[test]
+    fn ordinal() {
+        let cal = || {
+            Calendar::new(date!(2024 - 02 - 01), CalendarEventStore::default())
+                .show_ordinal(true)
+                .ordinal_style(Style::default().fg(Color::Cyan))
+        };
+        let lines = |cal, width, height| render_month(cal, width, height).lines();
+
+        // instead of the day of the month, in a leap year
+        let buf = lines(cal(), 28, 5);
+        assert_eq!(buf[0], "                  32  33  34");
+        assert_eq!(buf[4], "  56  57  58  59  60        ");
+
+        // below the day of the month
+        let buf = lines(cal().stretch(true), 28, 10);
+        assert_eq!(buf[0], "                  1   2   3 ");
+        assert_eq!(buf[1], "                 32  33  34 ");
+        assert_eq!(buf[9], " 56  57  58  59  60         ");
+
+        // refused rather than overflowing
+        let plain = lines(cal().show_ordinal(false), 27, 5);
+        assert_eq!(lines(cal(), 27, 5), plain);
+        assert_eq!(
+            lines(cal().compact(true), 30, 5),
+            lines(cal().show_ordinal(false).compact(true), 30, 5)
+        );
+    }
+
+    #[test]
+    fn weekend() {
+        let tint = Style::default().bg(Color::Magenta);
+        let red = Style::default().fg(Color::Red);
+        let mut events = CalendarEventStore::default();
+        events.add(date!(2023 - 04 - 07), red);
+        let cal = Calendar::new(date!(2023 - 04 - 01), events)
+            .show_weekdays(Style::default())
+            .weekend([Weekday::Friday, Weekday::Saturday])
+            .weekend_style(tint);
+
+        // the hidden Friday of March isn't tinted, and the event is patched over the weekend
+        assert_eq!(
+            render_month(cal, 21, 7).style_map(&[('t', tint), ('r', tint.patch(red))]),
+            [
+                "................tt.tt",
+                "...................tt",
+                "................rr.tt",
+                "................tt.tt",
+                "................tt.tt",
+                "................tt.tt",
+                ".....................",
+            ]
+        );
+    }
+
+    #[test]
+    fn weekday_header() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let tint = Style::default().bg(Color::Magenta);
+        let cal = || {
+            Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+                .show_weekdays(Style::default())
+                .weekday_header_style(bold)
+                .weekend_style(tint)
+                .weekday_separator('─')
+        };
+
+        let snapshot = render_month(cal(), 21, 8);
+        assert_eq!(
+            snapshot.lines()[..4],
+            [
+                " Su Mo Tu We Th Fr Sa",
+                "─────────────────────",
+                "                    1",
+                "  2  3  4  5  6  7  8",
+            ]
+        );
+        let legend = [('b', bold), ('B', bold.patch(tint)), ('t', tint)];
+        assert_eq!(
+            snapshot.style_map(&legend)[..4],
+            [
+                "bBBbbbbbbbbbbbbbbbbBB",
+                "bbbbbbbbbbbbbbbbbbbbb",
+                "...................tt",
+                ".tt................tt",
+            ]
+        );
+
+        // hiding the header hides its separator too
+        let snapshot = render_month(cal().hide_weekdays(), 21, 1);
+        assert_eq!(snapshot.lines(), ["                    1"]);
+        assert_eq!(snapshot.style_map(&legend), ["...................tt"]);
+        assert_eq!(cal().weekday_rows(), 2);
+        assert_eq!(cal().hide_weekdays().weekday_rows(), 0);
+    }
+
+    #[test]
+    fn weekday_labels() {
+        let cal = || {
+            Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+                .show_weekdays(Style::default())
+        };
+        let header = |cal, width| render_month(cal, width, 7).lines()[..2].to_vec();
+        assert_eq!(header(cal(), 14), [" S M T W T F S", "             1"]);
+        assert_eq!(
+            header(cal(), 21),
+            [" Su Mo Tu We Th Fr Sa", "                    1"]
+        );
+        assert_eq!(
+            header(cal().stretch(true), 28),
+            [
+                " Su  Mo  Tu  We  Th  Fr  Sa ",
+                "                          1 "
+            ]
+        );
+        assert_eq!(
+            header(cal().stretch(true), 35),
+            [
+                " Sun  Mon  Tue  Wed  Thu  Fri  Sat ",
+                "                                 1 "
+            ]
+        );
+
+        // double width labels line up with the days
+        let kanji = ["月", "火", "水", "木", "金", "土", "日"];
+        let days = [
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+            Weekday::Sunday,
+        ];
+        let labels = days
+            .into_iter()
+            .zip(kanji)
+            .fold(CalendarLabels::new(), |labels, (day, k)| {
+                labels.weekday(day, format!("{k}曜"), k, k)
+            });
+        let header = |cal: Calendar<'_, CalendarEventStore>, width| {
+            let snapshot = render_month(cal.labels(labels.clone()), width, 7);
+            let buf = snapshot.buffer();
+            // the columns where each label starts
+            (0..width)
+                .filter(|x| kanji.contains(&buf.get(*x, 0).symbol.as_str()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(header(cal().compact(true), 14), [0, 2, 4, 6, 8, 10, 12]);
+        assert_eq!(header(cal(), 21), [1, 4, 7, 10, 13, 16, 19]);
+        assert_eq!(header(cal().stretch(true), 35), [1, 6, 11, 16, 21, 26, 31]);
+    }
+
+    #[test]
+    fn week_numbers() {
+        let render = |cal: Calendar<CalendarEventStore>, width| {
+            render_month(cal.show_today(false), width, 7).lines()
+        };
+        let cal = || {
+            Calendar::new(date!(2021 - 01 - 01), CalendarEventStore::default())
+                .show_month(Style::default())
+                .show_week_numbers(Style::default())
+                .week_start(Weekday::Monday)
+        };
+        assert_eq!(
+            render(cal(), 23),
+            [
+                "       January 2021    ",
+                "53              1  2  3",
+                " 1  4  5  6  7  8  9 10",
+                " 2 11 12 13 14 15 16 17",
+                " 3 18 19 20 21 22 23 24",
+                " 4 25 26 27 28 29 30 31",
+                "                       ",
+            ]
+        );
+
+        // numbers over 99 widen the column, and a narrow area keeps a space before the days
+        let fiscal = WeekNumbering::custom(|d| if d.year() == 2021 { 100 } else { 9 });
+        assert_eq!(
+            render(cal().week_numbering(fiscal), 20)[..3],
+            [
+                "     January 2021   ",
+                "  9          1 2 3  ",
+                "100  4 5 6 7 8 910  ",
+            ]
+        );
+    }
+
+    #[test]
+    fn weekday_columns() {
+        let monday = Style::default().bg(Color::Blue);
+        let mut styles = [None; 7];
+        styles[Weekday::Monday.number_days_from_monday() as usize] = Some(monday);
+        let cal = Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+            .show_weekdays(Style::default())
+            .week_start(Weekday::Saturday)
+            .weekday_styles(styles);
+
+        // the column stays with Monday, including the hidden day of May
+        assert_eq!(
+            render_month(cal, 21, 7).style_map(&[('m', monday)]),
+            [
+                ".......mm............",
+                ".......mm............",
+                ".......mm............",
+                ".......mm............",
+                ".......mm............",
+                ".......mm............",
+                ".....................",
+            ]
+        );
+    }
+
+    #[test]
+    fn markers() {
+        let yellow = Style::default().fg(Color::Yellow);
+        let cal = |width| {
+            let cal = Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+                .markers(|d| match d.day() {
+                    1 | 15 => Some('•'),
+                    12 => Some('日'),
+                    31 => Some('x'),
+                    _ => None,
+                })
+                .marker_style(yellow);
+            render_month(cal, width, 3)
+        };
+
+        let snapshot = cal(22);
+        assert_eq!(
+            snapshot.lines(),
+            [
+                "                    1•",
+                "  2  3  4  5  6  7  8 ",
+                "  9 10 11 12 13 14 15•",
+            ]
+        );
+        // the double width marker doesn't fit
+        assert_eq!(
+            snapshot.style_map(&[('y', yellow)]),
+            [
+                ".....................y",
+                "......................",
+                "..........yy.........y",
+            ]
+        );
+
+        // without room after the last column
+        let snapshot = cal(21);
+        assert_eq!(snapshot.lines()[0], "                    1");
+        assert_eq!(
+            snapshot.style_map(&[('y', yellow)])[0],
+            "...................yy"
+        );
+    }
+
+    #[test]
+    fn short_areas() {
+        let cal = || {
+            Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+                .show_month(Style::default())
+                .show_weekdays(Style::default())
+                .weekday_separator('─')
+                .show_week_numbers(Style::default())
+        };
+        let mut state = CalendarState::new(date!(2023 - 04 - 01));
+        for h in 0..=9 {
+            let area = Rect::new(2, 2, 23, h);
+            let mut buf = Buffer::empty(Rect::new(0, 0, 30, 14));
+            StatefulWidget::render(cal(), area, &mut buf, &mut state);
+            for (x, y) in (0..30).flat_map(|x| (0..14).map(move |y| (x, y))) {
+                if !area.intersects(Rect::new(x, y, 1, 1)) {
+                    assert_eq!(buf.get(x, y).symbol, " ", "height {h} drew at {x},{y}");
+                }
+            }
+        }
+
+        // the month name goes first, then the separator, then the weekday names
+        let lines = |state: &mut CalendarState, h| render_month_with(cal(), state, 23, h).lines();
+        assert_eq!(lines(&mut state, 9)[0], "        April 2023     ");
+        assert_eq!(
+            lines(&mut state, 8)[..2],
+            ["   Su Mo Tu We Th Fr Sa", "  ─────────────────────"]
+        );
+        assert_eq!(
+            lines(&mut state, 7)[..2],
+            ["   Su Mo Tu We Th Fr Sa", "13                    1"]
+        );
+        assert_eq!(lines(&mut state, 6)[0], "13                    1");
+
+        // then only the weeks around the focus are drawn
+        state.focus(date!(2023 - 04 - 20));
+        assert_eq!(
+            lines(&mut state, 3),
+            [
+                "14  2  3  4  5  6  7  8",
+                "15  9 10 11 12 13 14 15",
+                "16 16 17 18 19 20 21 22",
+            ]
+        );
+        assert_eq!(state.date_at(6, 0), Some(date!(2023 - 04 - 03)));
+        assert_eq!(state.date_at(9, 2), Some(date!(2023 - 04 - 18)));
+        assert_eq!(
+            state.visible_range(),
+            Some(date!(2023 - 04 - 02)..=date!(2023 - 04 - 22))
+        );
+
+        // scrolling as little as possible to keep the focus in view
+        state.focus(date!(2023 - 04 - 30));
+        assert_eq!(lines(&mut state, 3)[0], "16 16 17 18 19 20 21 22");
+        state.focus(date!(2023 - 04 - 13));
+        assert_eq!(lines(&mut state, 3)[0], "15  9 10 11 12 13 14 15");
+        state.focus(date!(2023 - 04 - 25));
+        assert_eq!(lines(&mut state, 3)[0], "15  9 10 11 12 13 14 15");
+
+        // the whole month when it fits
+        render_month_with(cal(), &mut state, 23, 9);
+        assert_eq!(
+            state.visible_range(),
+            Some(date!(2023 - 04 - 01)..=date!(2023 - 04 - 30))
+        );
+        render_month_with(cal().show_surrounding(Style::default()), &mut state, 23, 9);
+        assert_eq!(
+            state.visible_range(),
+            Some(date!(2023 - 03 - 26)..=date!(2023 - 05 - 06))
+        );
+        render_month_with(cal(), &mut state, 23, 0);
+        assert_eq!(state.visible_range(), None);
+    }
+
+    #[test]
+    fn compact() {
+        let cal = || {
+            Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+                .show_month(Style::default())
+                .show_weekdays(Style::default())
+        };
+        let mut state = CalendarState::new(date!(2023 - 04 - 01));
+        assert_eq!(
+            render_month_with(cal(), &mut state, 16, 8).lines(),
+            [
+                "  April 2023    ",
+                " S M T W T F S  ",
+                "             1  ",
+                " 2 3 4 5 6 7 8  ",
+                " 9101112131415  ",
+                "16171819202122  ",
+                "23242526272829  ",
+                "30              ",
+            ]
+        );
+        assert_eq!(state.date_at(4, 4), Some(date!(2023 - 04 - 11)));
+        assert_eq!(state.date_at(14, 4), None);
+
+        // forced, in a wide area
+        let snapshot = render_month(cal().compact(true), 30, 8);
+        assert_eq!(snapshot.lines()[2], "             1                ");
+
+        // too small areas are clipped
+        for (w, h) in [(10, 3), (0, 0), (21, 1), (5, 20)] {
+            let area = Rect::new(2, 2, w, h);
+            let mut buf = Buffer::empty(Rect::new(0, 0, 30, 30));
+            StatefulWidget::render(cal(), area, &mut buf, &mut state);
+            let outside = (0..30)
+                .flat_map(|x| (0..30).map(move |y| (x, y)))
+                .filter(|&(x, y)| {
+                    x < area.left() || x >= area.right() || y < area.top() || y >= area.bottom()
+                });
+            for (x, y) in outside {
+                assert_eq!(buf.get(x, y).symbol, " ", "{w}x{h} drew at {x},{y}");
+            }
+        }
+    }
+
+    #[test]
+    fn stretch() {
+        let blue = Style::default().bg(Color::Blue);
+        // 5 weeks in 10 lines, with days 4 wide
+        let mut state = CalendarState::new(date!(2023 - 02 - 08));
+        state.select(date!(2023 - 02 - 08));
+        let cal = Calendar::new(date!(2023 - 02 - 01), CalendarEventStore::default())
+            .show_weekdays(Style::default())
+            .selected_style(blue)
+            .stretch(true);
+        let snapshot = render_month_with(cal, &mut state, 35, 11);
+        let lines = snapshot.lines();
+        assert_eq!(lines[0], " Sun  Mon  Tue  Wed  Thu  Fri  Sat ");
+        assert_eq!(lines[1], "                  1    2    3    4 ");
+        assert_eq!(lines[2].trim(), "");
+        assert_eq!(lines[3], "   5    6    7    8    9   10   11 ");
+        assert_eq!(lines[9], "  26   27   28                     ");
+
+        // the selection is a solid block, without the gaps
+        for y in 3..5 {
+            for x in 15..21 {
+                let selected = (16..20).contains(&x);
+                let bg = snapshot.buffer().get(x, y).bg;
+                assert_eq!(bg == Color::Blue, selected, "at {x},{y}");
+            }
+        }
+        assert_eq!(state.date_at(16, 4), Some(date!(2023 - 02 - 08)));
+        assert_eq!(state.date_at(19, 5), Some(date!(2023 - 02 - 15)));
+        assert_eq!(state.date_at(15, 4), None);
+        assert_eq!(state.date_at(16, 0), None);
+    }
+
+    #[test]
+    fn today() {
+        let red = Style::default().fg(Color::Red);
+        let blue = Style::default().bg(Color::Blue);
+        let cal = || {
+            Calendar::new(date!(2023 - 02 - 01), CalendarEventStore::default())
+                .today(date!(2023 - 02 - 14))
+                .today_style(red)
+                .focused_style(blue)
+        };
+        let today = |snapshot: Snapshot| snapshot.style_map(&[('r', red), ('b', red.patch(blue))]);
+
+        assert_eq!(
+            today(render_month(cal(), 21, 5))[2],
+            ".......rr............"
+        );
+
+        // the focused style is patched over the today style
+        let mut state = CalendarState::new(date!(2023 - 02 - 14));
+        assert_eq!(
+            today(render_month_with(cal(), &mut state, 21, 5))[2],
+            ".......bb............"
+        );
+
+        assert_eq!(
+            today(render_month(cal().show_today(false), 21, 5))[2],
+            "....................."
+        );
+    }
+
+    #[test]
+    fn today_marker() {
+        let blue = Style::default().bg(Color::Blue);
+        let cal = |today, marker| {
+            Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+                .today(today)
+                .today_marker(marker)
+                .focused_style(blue)
+        };
+        let line = |cal: Calendar<CalendarEventStore>, width, y: usize| {
+            render_month(cal, width, 3).lines().swap_remove(y)
+        };
+
+        let mut state = CalendarState::new(date!(2023 - 04 - 12));
+        let snapshot = render_month_with(
+            cal(date!(2023 - 04 - 12), TodayMarker::Brackets),
+            &mut state,
+            21,
+            3,
+        );
+        assert_eq!(snapshot.lines()[2], "  9 10 11[12]13 14 15");
+        // the brackets are part of the focused day
+        assert_eq!(
+            snapshot.style_map(&[('b', blue)])[2],
+            ".........bbbb........"
+        );
+
+        // single digits have room in their cell
+        let prefix = cal(date!(2023 - 04 - 05), TodayMarker::Prefix('*'));
+        assert_eq!(line(prefix, 21, 1), "  2  3  4 *5  6  7  8");
+        let brackets = cal(date!(2023 - 04 - 05), TodayMarker::Brackets);
+        assert_eq!(line(brackets, 21, 1), "  2  3  4 [5] 6  7  8");
+
+        // the last day of the week needs a column after the month
+        let suffix = || cal(date!(2023 - 04 - 15), TodayMarker::Suffix('*'));
+        assert_eq!(line(suffix(), 21, 2), "  9 10 11 12 13 14 15");
+        assert_eq!(line(suffix(), 22, 2), "  9 10 11 12 13 14 15*");
+
+        // compact days only have room for single digits
+        let compact = |today| cal(today, TodayMarker::Suffix('*')).compact(true);
+        assert_eq!(
+            line(compact(date!(2023 - 04 - 05)), 14, 1),
+            " 2 3 45* 6 7 8"
+        );
+        assert_eq!(
+            line(compact(date!(2023 - 04 - 12)), 14, 2),
+            " 9101112131415"
+        );
+
+        // stretched days have room in their cell
+        let stretched = cal(date!(2023 - 04 - 12), TodayMarker::Brackets).stretch(true);
+        assert_eq!(
+            line(stretched, 35, 2),
+            "   9   10   11  [12]  13   14   15 "
+        );
+    }
+
+    #[test]
+    fn reference() {
+        let gray = Style::default().bg(Color::DarkGray);
+        let blue = Style::default().bg(Color::Blue);
+        let cal = || {
+            Calendar::new(date!(2000 - 01 - 01), CalendarEventStore::default())
+                .reference_style(gray)
+                .reference_marker('<')
+                .selected_style(blue)
+                .show_today(false)
+        };
+        let mut state = CalendarState::new(date!(2023 - 04 - 12));
+        state.set_reference(Some(date!(2023 - 04 - 12)));
+        state.move_months(1);
+        state.move_months(-1);
+        assert_eq!(state.reference(), Some(date!(2023 - 04 - 12)));
+
+        let legend = [('g', gray), ('b', gray.patch(blue))];
+        let snapshot = render_month_with(cal(), &mut state, 21, 3);
+        assert_eq!(snapshot.lines()[2], "  9 10 11 12<13 14 15");
+        assert_eq!(snapshot.style_map(&legend)[2], "..........ggg........");
+
+        // the selected style wins, and the marker is still drawn
+        state.select(date!(2023 - 04 - 12));
+        let snapshot = render_month_with(cal(), &mut state, 21, 3);
+        assert_eq!(snapshot.lines()[2], "  9 10 11 12<13 14 15");
+        assert_eq!(snapshot.style_map(&legend)[2], "..........bbg........");
+
+        state.set_reference(None);
+        let snapshot = render_month_with(cal(), &mut state, 21, 3);
+        assert_eq!(snapshot.lines()[2], "  9 10 11 12 13 14 15");
+    }
+
+    #[test]
+    fn counts() {
+        let cyan = Style::default().fg(Color::Cyan);
+        let cal = || {
+            Calendar::new(date!(2023 - 04 - 01), CalendarEventStore::default())
+                .counts(|d| match d.day() {
+                    5 => 3,
+                    11 => 12,
+                    12 => 1,
+                    15 => 2,
+                    _ => 0,
+                })
+                .count_style(cyan)
+                .show_today(false)
+        };
+        let lines = |cal, width| render_month(cal, width, 3).lines();
+
+        // in the gap after the day, with wide counts and the last column falling back
+        let snapshot = render_month(cal(), 21, 3);
+        assert_eq!(
+            snapshot.lines(),
+            [
+                "                    1",
+                "  2  3  4  5³ 6  7  8",
+                "  9 10 11•12¹13 14 15",
+            ]
+        );
+        assert_eq!(
+            snapshot.style_map(&[('c', cyan)])[1..],
+            ["............c........", ".........c..c......cc"]
+        );
+
+        // inside cells with room, with a custom format that doesn't always fit
+        let wide = cal().stretch(true).count_format(|n| format!("({n})"));
+        assert_eq!(
+            lines(wide, 56)[2],
+            "    9      10      11   •  12(1)   13      14      15(2)"
+        );
+
+        // compact days
+        assert_eq!(lines(cal().compact(true), 14)[1], " 2 3 4•5 6 7 8");
+    }
+
+    #[test]
+    fn clock() {
+        use time::macros::{datetime, offset};
+
+        let red = Style::default().fg(Color::Red);
+        let cal =
+            || Calendar::new(date!(2023 - 02 - 01), CalendarEventStore::default()).today_style(red);
+        let today = |cal| {
+            render_month(cal, 21, 3)
+                .style_map(&[('r', red)])
+                .swap_remove(2)
+        };
+        let late = datetime!(2023 - 02 - 14 23:30 UTC);
+
+        assert_eq!(today(cal().clock(move || late)), ".......rr............");
+        // it is already tomorrow an hour east
+        let east = move || late.to_offset(offset!(+1));
+        assert_eq!(today(cal().clock(east)), "..........rr.........");
+        // a fixed date takes priority
+        let fixed = cal().clock(east).today(date!(2023 - 02 - 13));
+        assert_eq!(today(fixed), "....rr...............");
+    }
+
+    #[test]
+    fn state_today() {
+        use time::macros::datetime;
+
+        let cal = || Calendar::new(date!(2023 - 02 - 01), CalendarEventStore::default());
+        let mut state = CalendarState::new(date!(2023 - 02 - 01));
+        // the date from the clock is kept by the state, even when today isn't shown
+        let clocked = cal()
+            .show_today(false)
+            .clock(|| datetime!(2023 - 02 - 14 23:30 UTC));
+        render_month_with(clocked, &mut state, 21, 6);
+        assert_eq!(state.today(), Jump::Exact);
+        assert_eq!(state.focused(), date!(2023 - 02 - 14));
+
+        let fixed = cal().today(date!(2023 - 03 - 20));
+        render_month_with(fixed, &mut state, 21, 6);
+        assert_eq!(state.today_with(|| unreachable!()), Jump::Exact);
+        assert_eq!(state.focused(), date!(2023 - 03 - 20));
+
+        #[cfg(feature = "crossterm")]
+        {
+            use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+            state.move_days(5);
+            assert!(state.handle_key(&KeyEvent::new(KeyCode::Home, KeyModifiers::NONE)));
+            assert_eq!(state.focused(), date!(2023 - 03 - 20));
+        }
+    }
+
+    #[test]
+    fn style_priority() {
+        let tint = Style::default().bg(Color::Magenta);
+        let red = Style::default().fg(Color::Red);
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let underline = Style::default().add_modifier(Modifier::UNDERLINED);
+        let blue = Style::default().bg(Color::Blue);
+        let cyan = Style::default().fg(Color::Cyan);
+        // Saturday the 15th is on the weekend, has an event, is today, and is selected and focused
+        let cal = || {
+            let mut events = CalendarEventStore::default();
+            events.add(date!(2023 - 04 - 15), red);
+            Calendar::new(date!(2023 - 04 - 01), events)
+                .weekend_style(tint)
+                .today(date!(2023 - 04 - 15))
+                .today_style(bold)
+                .selected_style(underline)
+                .focused_style(blue)
+                .range_style(cyan)
+        };
+        let mut state = CalendarState::new(date!(2023 - 04 - 15));
+        state.select(date!(2023 - 04 - 15));
+        let style =
+            |cal, state: &mut CalendarState| render_month_with(cal, state, 21, 3).style_at(19, 2);
+
+        // partial styles compose, with the focus taking the background from the weekend
+        let all = tint.patch(red).patch(bold).patch(underline).patch(blue);
+        let drawn = |style| {
+            let mut cell = ratatui::buffer::Cell::default();
+            cell.set_style(style);
+            cell.style()
+        };
+        assert_eq!(style(cal(), &mut state), drawn(all));
+
+        // the weekend over the focus, the rest as they were
+        let weekend = cal().style_priority([StyleLayer::Focused, StyleLayer::Weekend]);
+        assert_eq!(style(weekend, &mut state), drawn(all.patch(tint)));
+
+        // the range over today by default, and today over the range when asked
+        state.focus(date!(2023 - 04 - 14));
+        state.begin_range();
+        state.focus(date!(2023 - 04 - 15));
+        state.complete_range();
+        let today = || cal().today_style(Style::default().fg(Color::Green));
+        assert_eq!(style(today(), &mut state).fg, Some(Color::Cyan));
+        let today = today().style_priority([StyleLayer::Range, StyleLayer::Today]);
+        assert_eq!(style(today, &mut state).fg, Some(Color::Green));
+    }
+
+    #[test]
+    fn stateless() {
+        let red = Style::default().fg(Color::Red);
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let tint = Style::default().bg(Color::Magenta);
+        let blue = Style::default().bg(Color::Blue);
+        let cal = || {
+            let mut events = CalendarEventStore::default();
+            events.add(date!(2023 - 02 - 08), red);
+            Calendar::new(date!(2023 - 02 - 14), events)
+                .show_month(Style::default())
+                .today(date!(2023 - 02 - 14))
+                .today_style(bold)
+                .weekend_style(tint)
+                .focused_style(blue)
+        };
+        let legend = [
+            ('r', red),
+            ('b', bold),
+            ('t', tint),
+            ('B', blue),
+            ('F', bold.patch(blue)),
+        ];
+
+        // without a state, the month is styled the same, without the focus
+        let plain = render_month(cal(), 21, 6);
+        let mut state = CalendarState::new(date!(2023 - 02 - 14));
+        let focused = render_month_with(cal(), &mut state, 21, 6);
+        assert_eq!(plain.lines(), focused.lines());
+        assert_eq!(
+            plain.style_map(&legend)[2..4],
+            [".tt.......rr.......tt", ".tt....bb..........tt"]
+        );
+        assert_eq!(focused.style_map(&legend)[3], ".tt....FF..........tt");
+    }
+
+    #[test]
+    fn focus() {
+        let blue = Style::default().bg(Color::Blue);
+        let mut state = CalendarState::new(date!(2023 - 02 - 14));
+        let cal =
+            Calendar::new(date!(2000 - 01 - 01), CalendarEventStore::default()).focused_style(blue);
+        let snapshot = render_month_with(cal, &mut state, 21, 5);
+        assert_eq!(
+            snapshot.lines(),
+            [
+                "           1  2  3  4",
+                "  5  6  7  8  9 10 11",
+                " 12 13 14 15 16 17 18",
+                " 19 20 21 22 23 24 25",
+                " 26 27 28            ",
+            ]
+        );
+        assert_eq!(
+            snapshot.style_map(&[('b', blue)])[2],
+            ".......bb............"
+        );
+        // rendering isn't a change to the state
+        assert_eq!(state.take_changed(), None);
+    }
+
+    #[test]
+    fn cell_renderer() {
+        let red = Style::default().fg(Color::Red);
+        let cal = Calendar::new(date!(2000 - 01 - 01), CalendarEventStore::default())
+            .show_surrounding(Style::default())
+            .today(date!(2023 - 04 - 03))
+            .bounds(Some(date!(2023 - 04 - 02)), None)
+            .selected_style(red)
+            .cell_renderer(|date, cx| {
+                assert_eq!(cx.width, 2);
+                let text = match cx {
+                    CellContext { focused: true, .. } => "focused",
+                    CellContext { selected: true, .. } => "SS",
+                    CellContext { today: true, .. } => "TT",
+                    CellContext { disabled: true, .. } => "--",
+                    CellContext {
+                        surrounding: true, ..
+                    } => "..",
+                    _ if date.day() % 2 == 0 => "",
+                    _ => return default_cell(date, cx),
+                };
+                Spans::from(text)
+            });
+        let mut state = CalendarState::new(date!(2023 - 04 - 05));
+        state.toggle(date!(2023 - 04 - 06));
+        let snapshot = render_month_with(cal, &mut state, 21, 2);
+        assert_eq!(
+            snapshot.lines(),
+            [" -- -- -- -- -- -- --", "    TT    fo SS  7   "]
+        );
+        // the cells keep their style
+        assert_eq!(
+            snapshot.style_map(&[('r', red)]),
+            [".....................", ".............rr......"]
+        );
+    }
+
+    #[test]
+    fn week_highlight() {
+        let gray = Style::default().bg(Color::DarkGray);
+        let blue = Style::default().bg(Color::Blue);
+        let cal = || {
+            Calendar::new(date!(2000 - 01 - 01), CalendarEventStore::default())
+                .show_surrounding(Style::default())
+                .week_highlight_style(gray)
+                .focused_style(blue)
+        };
+        let legend = [('g', gray), ('b', blue), ('b', gray.patch(blue))];
+
+        // the gaps between days aren't highlighted
+        let mut state = CalendarState::new(date!(2023 - 04 - 12));
+        let map = render_month_with(cal(), &mut state, 21, 6).style_map(&legend);
+        assert_eq!(map[1], ".....................");
+        assert_eq!(map[2], ".gg.gg.gg.bb.gg.gg.gg");
+
+        // days of the surrounding month are part of the week
+        state.focus(date!(2023 - 04 - 01));
+        let map = render_month_with(cal(), &mut state, 21, 6).style_map(&legend);
+        assert_eq!(map[0], ".gg.gg.gg.gg.gg.gg.bb");
+    }
+}
@@ -0,0 +1,184 @@
+//! Using [`chrono::NaiveDate`] with the calendar, enabled by the `chrono` feature.
+//!
+//! The calendar works with [`time::Date`]. The conversions here, and the `_naive` variants of the
+//! methods taking or returning dates, let apps built on chrono use it without converting dates at
+//! each call.
+//!
+//! chrono supports more years than the time crate (which stops at ±9999), so converting to a
+//! [`Date`] can fail. The methods taking a [`NaiveDate`] return an [`OutOfRange`] error then, and
+//! leave the state unchanged. Converting the other way always succeeds.
+use std::{collections::HashMap, fmt};
+
+use chrono::{Datelike, NaiveDate};
+use ratatui::style::Style;
+use time::Date;
+
+use super::{Calendar, CalendarState, DateStyler, Jump};
+
+/// A [`NaiveDate`] outside of the dates supported by the time crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRange(pub NaiveDate);
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is out of the range of supported dates", self.0)
+    }
+}
+
+impl std::error::Error for OutOfRange {}
+
+/// Convert a chrono date to a time date.
+pub fn to_date(date: NaiveDate) -> Result<Date, OutOfRange> {
+    // the ordinal is at most 366, so it always fits
+    Date::from_ordinal_date(date.year(), date.ordinal() as u16).map_err(|_| OutOfRange(date))
+}
+
+/// Convert a time date to a chrono date.
+pub fn from_date(date: Date) -> NaiveDate {
+    NaiveDate::from_yo_opt(date.year(), date.ordinal().into())
+        .expect("chrono supports all the years supported by time")
+}
+
+/// Dates in the map are styled with their style, and other dates aren't styled.
+impl DateStyler for HashMap<NaiveDate, Style> {
+    fn get_style(&self, date: Date) -> Style {
+        self.get(&from_date(date)).copied().unwrap_or_default()
+    }
+}
+
+impl<'a, S: DateStyler> Calendar<'a, S> {
+    /// Like [`new`](Self::new), displaying the month containing a chrono date.
+    pub fn new_naive(display_date: NaiveDate, events: S) -> Result<Self, OutOfRange> {
+        Ok(Self::new(to_date(display_date)?, events))
+    }
+
+    /// Like [`today`](Self::today), with a chrono date.
+    pub fn today_naive(self, today: NaiveDate) -> Result<Self, OutOfRange> {
+        Ok(self.today(to_date(today)?))
+    }
+
+    /// Like [`bounds`](Self::bounds), with chrono dates.
+    pub fn bounds_naive(
+        self,
+        min: Option<NaiveDate>,
+        max: Option<NaiveDate>,
+    ) -> Result<Self, OutOfRange> {
+        let min = min.map(to_date).transpose()?;
+        let max = max.map(to_date).transpose()?;
+        Ok(self.bounds(min, max))
+    }
+
+    /// Like [`is_enabled`](Self::is_enabled), for a chrono date. Dates the calendar can't display
+    /// aren't enabled.
+    pub fn is_enabled_naive(&self, date: NaiveDate) -> bool {
+        to_date(date).is_ok_and(|d| self.is_enabled(d))
+    }
+}
+
+impl CalendarState {
+    /// Like [`new`](Self::new), focusing a chrono date.
+    pub fn new_naive(focused: NaiveDate) -> Result<Self, OutOfRange> {
+        Ok(Self::new(to_date(focused)?))
+    }
+
+    /// Get the focused date as a chrono date.
+    pub fn focused_naive(&self) -> NaiveDate {
+        from_date(self.focused())
+    }
+
+    /// Like [`selected`](Self::selected), returning a chrono date.
+    pub fn selected_naive(&self) -> Option<NaiveDate> {
+        self.selected().map(from_date)
+    }
+
+    /// Like [`selected_dates`](Self::selected_dates), returning chrono dates.
+    pub fn selected_dates_naive(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.selected_dates().map(from_date)
+    }
+
+    /// Like [`range`](Self::range), returning the first and last dates of the range.
+    pub fn range_naive(&self) -> Option<(NaiveDate, NaiveDate)> {
+        self.range()
+            .map(|r| (from_date(*r.start()), from_date(*r.end())))
+    }
+
+    /// Like [`focus`](Self::focus), with a chrono date.
+    pub fn focus_naive(&mut self, date: NaiveDate) -> Result<(), OutOfRange> {
+        self.focus(to_date(date)?);
+        Ok(())
+    }
+
+    /// Like [`jump_to`](Self::jump_to), with a chrono date.
+    pub fn jump_to_naive(&mut self, date: NaiveDate) -> Result<Jump, OutOfRange> {
+        Ok(self.jump_to(to_date(date)?))
+    }
+
+    /// Like [`select`](Self::select), with a chrono date.
+    pub fn select_naive(&mut self, date: NaiveDate) -> Result<(), OutOfRange> {
+        self.select(to_date(date)?);
+        Ok(())
+    }
+
+    /// Like [`toggle`](Self::toggle), with a chrono date.
+    pub fn toggle_naive(&mut self, date: NaiveDate) -> Result<(), OutOfRange> {
+        self.toggle(to_date(date)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+    use time::macros::date;
+
+    use super::*;
+
+    fn naive(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn conversions() {
+        for (n, d) in [
+            (naive(2023, 4, 6), date!(2023 - 04 - 06)),
+            (naive(2024, 2, 29), date!(2024 - 02 - 29)),
+            (naive(-44, 3, 15), date!(-0044 - 03 - 15)),
+            (naive(9999, 12, 31), Date::MAX),
+        ] {
+            assert_eq!(to_date(n), Ok(d));
+            assert_eq!(from_date(d), n);
+        }
+        let far = naive(10_000, 1, 1);
+        assert_eq!(to_date(far), Err(OutOfRange(far)));
+    }
+
+    #[test]
+    fn state() {
+        let mut s = CalendarState::new_naive(naive(2023, 4, 6)).unwrap();
+        s.select_naive(naive(2023, 5, 1)).unwrap();
+        assert_eq!(s.focused_naive(), naive(2023, 5, 1));
+        assert_eq!(s.selected_naive(), Some(naive(2023, 5, 1)));
+
+        // the state is unchanged by a failed conversion
+        let far = naive(20_000, 1, 1);
+        assert_eq!(s.jump_to_naive(far), Err(OutOfRange(far)));
+        assert_eq!(s.focused(), date!(2023 - 05 - 01));
+        assert!(CalendarState::new_naive(far).is_err());
+    }
+
+    #[test]
+    fn calendar() {
+        let events = HashMap::from([(naive(2023, 4, 6), Style::default().fg(Color::Red))]);
+        assert_eq!(events.get_style(date!(2023 - 04 - 06)).fg, Some(Color::Red));
+        assert_eq!(events.get_style(date!(2023 - 04 - 07)), Style::default());
+
+        let cal = Calendar::new_naive(naive(2023, 4, 1), events)
+            .unwrap()
+            .bounds_naive(None, Some(naive(2023, 4, 20)))
+            .unwrap();
+        assert!(cal.is_enabled_naive(naive(2023, 4, 20)));
+        assert!(!cal.is_enabled_naive(naive(2023, 4, 21)));
+        assert!(!cal.is_enabled_naive(naive(-20_000, 1, 1)));
+        assert!(cal.bounds_naive(Some(naive(-20_000, 1, 1)), None).is_err());
+    }
+}
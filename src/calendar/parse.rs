@@ -0,0 +1,177 @@
+use std::fmt;
+
+use time::{Date, Month};
+
+use super::local_today;
+
+/// The order of the day and month in dates written with slashes, like `01/06/2025`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateOrder {
+    /// `dd/mm/yyyy`, as in most of the world.
+    #[default]
+    DayFirst,
+    /// `mm/dd/yyyy`, as in the US.
+    MonthFirst,
+}
+
+/// Why [`parse_date`] couldn't read a date, naming the part that failed so apps can tell the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseDateError {
+    /// Nothing was typed.
+    Empty,
+    /// The input isn't written as any of the supported formats.
+    Format(String),
+    /// The year isn't a number, or is outside the years supported by the time crate.
+    Year(String),
+    /// The month isn't a number from 1 to 12.
+    Month(String),
+    /// The day isn't a number, or isn't a day of the month.
+    Day {
+        /// The day as it was typed.
+        day: String,
+        /// The month of the date.
+        month: Month,
+        /// The year of the date.
+        year: i32,
+    },
+}
+
+impl fmt::Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseDateError::Empty => write!(f, "no date was given"),
+            ParseDateError::Format(input) => write!(
+                f,
+                "`{input}` isn't a date, try e.g. 2025-06-01, 01/06/2025, 06-01 or 15"
+            ),
+            ParseDateError::Year(year) => write!(f, "`{year}` isn't a year"),
+            ParseDateError::Month(month) => write!(f, "`{month}` isn't a month from 1 to 12"),
+            ParseDateError::Day { day, month, year } => {
+                write!(f, "`{day}` isn't a day of {month} {year}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseDateError {}
+
+/// Read a date typed by the user, e.g. in a "go to date" prompt, with the day before the month in
+/// dates with slashes. See [`parse_date_with`] for the formats read.
+pub fn parse_date(input: &str) -> Result<Date, ParseDateError> {
+    parse_date_with(input, DateOrder::DayFirst, local_today())
+}
+
+/// Read a date typed by the user, in one of these formats:
+/// * ISO dates, like `2025-06-01`
+/// * dates with slashes in the given `order`, like `01/06/2025`
+/// * a month and day, like `06-01`, or `01/06` with slashes, in the year of `today`
+/// * a day, like `15`, in the month of `today`
+///
+/// Spaces around the input are ignored.
+pub fn parse_date_with(input: &str, order: DateOrder, today: Date) -> Result<Date, ParseDateError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseDateError::Empty);
+    }
+    let dashes: Vec<_> = input.split('-').collect();
+    let slashes: Vec<_> = input.split('/').collect();
+    let (year, month, day) = match (&dashes[..], &slashes[..], order) {
+        ([year, month, day], [_], _) => (Some(year), Some(month), day),
+        ([month, day], [_], _) => (None, Some(month), day),
+        ([_], [day, month, year], DateOrder::DayFirst) => (Some(year), Some(month), day),
+        ([_], [month, day, year], DateOrder::MonthFirst) => (Some(year), Some(month), day),
+        ([_], [day, month], DateOrder::DayFirst) => (None, Some(month), day),
+        ([_], [month, day], DateOrder::MonthFirst) => (None, Some(month), day),
+        ([day], [_], _) if number(day).is_some() => (None, None, day),
+        _ => return Err(ParseDateError::Format(input.to_string())),
+    };
+
+    let year = match year {
+        Some(year) => number(year)
+            .filter(|y| *y <= 9999)
+            .ok_or_else(|| ParseDateError::Year(year.to_string()))?,
+        None => today.year(),
+    };
+    let month = match month {
+        Some(month) => number(month)
+            .and_then(|m| u8::try_from(m).ok())
+            .and_then(|m| Month::try_from(m).ok())
+            .ok_or_else(|| ParseDateError::Month(month.to_string()))?,
+        None => today.month(),
+    };
+    number(day)
+        .and_then(|d| u8::try_from(d).ok())
+        .and_then(|d| Date::from_calendar_date(year, month, d).ok())
+        .ok_or_else(|| ParseDateError::Day {
+            day: day.to_string(),
+            month,
+            year,
+        })
+}
+
+/// Read a number made only of digits.
+fn number(text: &str) -> Option<i32> {
+    match text.bytes().all(|b| b.is_ascii_digit()) {
+        true => text.parse().ok(),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn formats() {
+        let today = date!(2025 - 03 - 20);
+        let parse = |input| parse_date_with(input, DateOrder::DayFirst, today);
+        assert_eq!(parse("2025-06-01"), Ok(date!(2025 - 06 - 01)));
+        assert_eq!(parse(" 01/06/2025 "), Ok(date!(2025 - 06 - 01)));
+        assert_eq!(parse("06-01"), Ok(date!(2025 - 06 - 01)));
+        assert_eq!(parse("1/6"), Ok(date!(2025 - 06 - 01)));
+        assert_eq!(parse("15"), Ok(date!(2025 - 03 - 15)));
+
+        let us = |input| parse_date_with(input, DateOrder::MonthFirst, today);
+        assert_eq!(us("06/01/2025"), Ok(date!(2025 - 06 - 01)));
+        assert_eq!(us("6/1"), Ok(date!(2025 - 06 - 01)));
+        // ISO dates don't change order
+        assert_eq!(us("2025-06-01"), Ok(date!(2025 - 06 - 01)));
+    }
+
+    #[test]
+    fn errors() {
+        let today = date!(2025 - 02 - 10);
+        let parse = |input| parse_date_with(input, DateOrder::DayFirst, today);
+        assert_eq!(parse("  "), Err(ParseDateError::Empty));
+        assert_eq!(
+            parse("tomorrow"),
+            Err(ParseDateError::Format("tomorrow".into()))
+        );
+        assert_eq!(
+            parse("2025-06/01"),
+            Err(ParseDateError::Format("2025-06/01".into()))
+        );
+        assert_eq!(
+            parse("20x5-06-01"),
+            Err(ParseDateError::Year("20x5".into()))
+        );
+        assert_eq!(parse("2025-13-01"), Err(ParseDateError::Month("13".into())));
+        let err = parse("30").unwrap_err();
+        assert_eq!(
+            err,
+            ParseDateError::Day {
+                day: "30".into(),
+                month: Month::February,
+                year: 2025
+            }
+        );
+        assert_eq!(err.to_string(), "`30` isn't a day of February 2025");
+        assert_eq!(
+            parse("29/02/2024"),
+            Ok(date!(2024 - 02 - 29)),
+            "leap days are found in leap years"
+        );
+    }
+}
@@ -0,0 +1,321 @@
+#[cfg(feature = "crossterm")]
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, StatefulWidget, Widget},
+};
+use time::Date;
+
+use super::{Calendar, CalendarState, DateStyler};
+
+/// The width of the popup: a month and the borders.
+const WIDTH: u16 = 23;
+/// The height of the popup: the borders, the header, the weekdays, six weeks and the buttons.
+const HEIGHT: u16 = 11;
+
+/// How a [`DatePicker`] was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickResult {
+    /// A date was picked.
+    Picked(Date),
+    /// The picker was dismissed without picking a date.
+    Cancelled,
+}
+
+/// A popup for picking a date: a [`Calendar`] in a bordered box, with a header showing the month
+/// and year between arrows, and the keys to pick a date or cancel below it.
+///
+/// The popup is centered in the area it is rendered in, at its minimum size of 23x11 (or smaller,
+/// if the area is), so rendering it in the whole frame puts it in the middle of the screen. The
+/// content under the popup is cleared first.
+///
+/// The picker is driven by a [`DatePickerState`]. The app moves the focus through
+/// [`DatePickerState::calendar_mut`], or with `DatePickerState::handle_key` when the `crossterm`
+/// feature is enabled, and checks [`DatePickerState::outcome`] to know when to close
+/// the popup.
+pub struct DatePicker<'a, S: DateStyler> {
+    calendar: Calendar<'a, S>,
+    block: Block<'a>,
+    header_style: Style,
+    button_style: Style,
+}
+
+impl<'a, S: DateStyler> DatePicker<'a, S> {
+    /// Pick a date from `calendar`. The calendar's own month header isn't drawn, as the picker has
+    /// its own, and its weekday header is drawn if it is set.
+    pub fn new(calendar: Calendar<'a, S>) -> Self {
+        Self {
+            calendar,
+            block: Block::default().borders(Borders::ALL),
+            header_style: Style::default(),
+            button_style: Style::default(),
+        }
+    }
+
+    /// The block drawn around the popup. Defaults to all borders.
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = block;
+        self
+    }
+
+    /// The style of the header showing the month and year.
+    pub fn header_style(mut self, s: Style) -> Self {
+        self.header_style = s;
+        self
+    }
+
+    /// The style of the keys shown below the calendar.
+    pub fn button_style(mut self, s: Style) -> Self {
+        self.button_style = s;
+        self
+    }
+
+    /// The area the popup is drawn in, when rendered in `area`.
+    pub fn popup_area(area: Rect) -> Rect {
+        let width = std::cmp::min(WIDTH, area.width);
+        let height = std::cmp::min(HEIGHT, area.height);
+        Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+impl<'a, S: DateStyler> StatefulWidget for DatePicker<'a, S> {
+    type State = DatePickerState;
+
+    fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = Self::popup_area(area);
+        Clear.render(area, buf);
+        let mut inner = self.block.inner(area);
+        self.block.render(area, buf);
+
+        if inner.height > 0 {
+            self.calendar.display_date = state.calendar.focused();
+            let header = Spans::from(vec![
+                Span::raw("‹ "),
+                Span::styled(self.calendar.month_title(), self.header_style),
+                Span::raw(" ›"),
+            ]);
+            let x = inner.x + inner.width.saturating_sub(header.width() as u16) / 2;
+            buf.set_spans(x, inner.y, &header, inner.right() - x);
+            inner.y += 1;
+            inner.height -= 1;
+        }
+        if inner.height > 0 {
+            let buttons = Spans::from(vec![
+                Span::styled("Enter", self.button_style),
+                Span::raw(" ok  "),
+                Span::styled("Esc", self.button_style),
+                Span::raw(" cancel"),
+            ]);
+            let x = inner.x + inner.width.saturating_sub(buttons.width() as u16) / 2;
+            buf.set_spans(x, inner.bottom() - 1, &buttons, inner.right() - x);
+            inner.height -= 1;
+        }
+
+        self.calendar.show_month = None;
+        StatefulWidget::render(self.calendar, inner, buf, &mut state.calendar);
+    }
+}
+
+/// State for a [`DatePicker`]: the [`CalendarState`] of its calendar, and how the picker was
+/// closed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatePickerState {
+    calendar: CalendarState,
+    outcome: Option<PickResult>,
+}
+
+impl DatePickerState {
+    /// Create a state for picking a date, starting with `focused` focused.
+    pub fn new(focused: Date) -> Self {
+        Self {
+            calendar: CalendarState::new(focused),
+            outcome: None,
+        }
+    }
+
+    /// Get the state of the calendar.
+    pub fn calendar(&self) -> &CalendarState {
+        &self.calendar
+    }
+
+    /// Get the state of the calendar, e.g. to move the focus.
+    pub fn calendar_mut(&mut self) -> &mut CalendarState {
+        &mut self.calendar
+    }
+
+    /// Pick the focused date, unless it is outside the calendar's bounds or disabled (as far as
+    /// the last render showed), as those dates can't be picked. Returns whether it was picked.
+    pub fn pick(&mut self) -> bool {
+        if !self.calendar.focused_enabled() {
+            return false;
+        }
+        self.outcome = Some(PickResult::Picked(self.calendar.focused()));
+        true
+    }
+
+    /// Close the picker without picking a date.
+    pub fn cancel(&mut self) {
+        self.outcome = Some(PickResult::Cancelled);
+    }
+
+    /// Get how the picker was closed, or `None` while it is open.
+    pub fn outcome(&self) -> Option<PickResult> {
+        self.outcome
+    }
+
+    /// Open the picker again, forgetting the outcome.
+    pub fn reset(&mut self) {
+        self.outcome = None;
+    }
+
+    /// Handle a key press: enter picks the focused date (see [`pick`](Self::pick)) and escape
+    /// cancels, and other keys move the focus as with [`CalendarState::handle_key`]. Returns
+    /// whether the key was used.
+    #[cfg(feature = "crossterm")]
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Enter => {
+                self.pick();
+            }
+            KeyCode::Esc => self.cancel(),
+            _ => return self.calendar.handle_key(key),
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+    use crate::calendar::CalendarEventStore;
+
+    #[test]
+    fn popup() {
+        let cal = Calendar::new(date!(2000 - 01 - 01), CalendarEventStore::default())
+            .show_month(Style::default())
+            .show_weekdays(Style::default());
+        let area = Rect::new(0, 0, 27, 13);
+        let mut buf = Buffer::with_lines(vec!["x".repeat(27); 13]);
+        let mut state = DatePickerState::new(date!(2023 - 04 - 06));
+        DatePicker::new(cal).render(area, &mut buf, &mut state);
+        let expected = Buffer::with_lines(vec![
+            "xxxxxxxxxxxxxxxxxxxxxxxxxxx",
+            "xx┌─────────────────────┐xx",
+            "xx│   ‹ April 2023 ›    │xx",
+            "xx│ Su Mo Tu We Th Fr Sa│xx",
+            "xx│                    1│xx",
+            "xx│  2  3  4  5  6  7  8│xx",
+            "xx│  9 10 11 12 13 14 15│xx",
+            "xx│ 16 17 18 19 20 21 22│xx",
+            "xx│ 23 24 25 26 27 28 29│xx",
+            "xx│ 30                  │xx",
+            "xx│Enter ok  Esc cancel │xx",
+            "xx└─────────────────────┘xx",
+            "xxxxxxxxxxxxxxxxxxxxxxxxxxx",
+        ]);
+        assert_eq!(buf, expected);
+        // clicks are mapped inside the popup
+        assert_eq!(state.calendar().date_at(4, 5), Some(date!(2023 - 04 - 02)));
+
+        // small areas are filled, without panicking
+        for (w, h) in [(0, 0), (1, 1), (10, 2), (23, 3), (30, 5)] {
+            let area = Rect::new(0, 0, w, h);
+            let mut buf = Buffer::empty(area);
+            let cal = Calendar::new(date!(2000 - 01 - 01), CalendarEventStore::default());
+            DatePicker::new(cal).render(area, &mut buf, &mut state);
+        }
+    }
+
+    #[test]
+    fn outcome() {
+        let mut state = DatePickerState::new(date!(2023 - 04 - 06));
+        assert_eq!(state.outcome(), None);
+        state.calendar_mut().move_days(1);
+        assert!(state.pick());
+        assert_eq!(
+            state.outcome(),
+            Some(PickResult::Picked(date!(2023 - 04 - 07)))
+        );
+        state.reset();
+        assert_eq!(state.outcome(), None);
+        state.cancel();
+        assert_eq!(state.outcome(), Some(PickResult::Cancelled));
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn keys() {
+        use crossterm::event::KeyModifiers;
+
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let mut state = DatePickerState::new(date!(2023 - 04 - 06));
+        assert!(state.handle_key(&key(KeyCode::Down)));
+        assert!(state.handle_key(&key(KeyCode::Left)));
+        assert!(state.handle_key(&key(KeyCode::PageDown)));
+        assert!(!state.handle_key(&key(KeyCode::Char('x'))));
+        assert_eq!(state.outcome(), None);
+        assert!(state.handle_key(&key(KeyCode::Enter)));
+        assert_eq!(
+            state.outcome(),
+            Some(PickResult::Picked(date!(2023 - 05 - 12)))
+        );
+        assert!(state.handle_key(&key(KeyCode::Esc)));
+        assert_eq!(state.outcome(), Some(PickResult::Cancelled));
+    }
+
+    #[test]
+    fn unpickable() {
+        let picker = || {
+            let cal = Calendar::new(date!(2000 - 01 - 01), CalendarEventStore::default())
+                .disabled(|date| date == date!(2023 - 04 - 07))
+                .bounds(None, Some(date!(2023 - 04 - 20)));
+            DatePicker::new(cal)
+        };
+        let area = Rect::new(0, 0, 23, 11);
+        let mut state = DatePickerState::new(date!(2023 - 04 - 07));
+        picker().render(area, &mut Buffer::empty(area), &mut state);
+
+        // disabled dates can't be picked
+        assert!(!state.pick());
+        assert_eq!(state.outcome(), None);
+
+        // nor can dates outside the bounds
+        state.calendar_mut().focus(date!(2023 - 04 - 25));
+        picker().render(area, &mut Buffer::empty(area), &mut state);
+        assert!(!state.pick());
+        assert_eq!(state.outcome(), None);
+
+        state.calendar_mut().focus(date!(2023 - 04 - 20));
+        assert!(state.pick());
+        assert_eq!(
+            state.outcome(),
+            Some(PickResult::Picked(date!(2023 - 04 - 20)))
+        );
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn enter_on_disabled() {
+        use crossterm::event::KeyModifiers;
+
+        let cal = Calendar::new(date!(2000 - 01 - 01), CalendarEventStore::default())
+            .disabled(|date| date == date!(2023 - 04 - 07));
+        let area = Rect::new(0, 0, 23, 11);
+        let mut state = DatePickerState::new(date!(2023 - 04 - 07));
+        DatePicker::new(cal).render(area, &mut Buffer::empty(area), &mut state);
+
+        // the picker stays open on a disabled day
+        assert!(state.handle_key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+        assert_eq!(state.outcome(), None);
+    }
+}
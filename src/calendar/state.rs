@@ -0,0 +1,1274 @@
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::{collections::BTreeSet, ops::RangeInclusive};
+
+#[cfg(feature = "crossterm")]
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Rect;
+use time::{Date, Duration, Month, Weekday};
+
+use super::{parse_date, ParseDateError};
+
+/// State for a [`Calendar`](super::Calendar).
+///
+/// This tracks the focused date - the cursor moved around the calendar with the keyboard - and the
+/// selected dates, which stay marked while the focus moves. The calendar displays the month
+/// containing the focused date, so moving the focus into another month changes the month
+/// displayed.
+///
+/// For picking a single date use [`select`](Self::select) and [`selected`](Self::selected). Any
+/// number of dates can be selected with [`toggle`](Self::toggle), and read back with
+/// [`selected_dates`](Self::selected_dates).
+///
+/// A range of dates is selected in two steps: [`begin_range`](Self::begin_range) starts it at the
+/// focused date, and [`complete_range`](Self::complete_range) ends it at the date focused then.
+/// In between, the calendar previews the range from its start to the focused date.
+///
+/// The `_within` variants of the navigation methods only focus dates accepted by a predicate,
+/// usually [`Calendar::is_enabled`](super::Calendar::is_enabled). How they treat disabled dates is
+/// set with [`navigation`](Self::navigation).
+///
+/// Rendering the calendar records where its days were drawn, so mouse clicks can be mapped to
+/// dates with [`date_at`](Self::date_at) and [`select_at`](Self::select_at).
+///
+/// Changes to the focus and selection are flagged, so an app can reload what it shows for the
+/// focused or selected dates when [`take_changed`](Self::take_changed) returns a change, rather
+/// than comparing dates every frame. Rendering never flags a change, and the flag isn't
+/// serialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct CalendarState {
+    pub(super) focused: Date,
+    pub(super) selected: BTreeSet<Date>,
+    range_start: Option<Date>,
+    range: Option<RangeInclusive<Date>>,
+    reference: Option<Date>,
+    /// The day of the month paging by months keeps to, when the focused month is too short for it.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    intended_day: Option<u8>,
+    navigation: Navigation,
+    /// Where the days of each month were drawn by the last render.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) cells: Vec<Cells>,
+    /// The dates drawn by the last render.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) visible: Option<RangeInclusive<Date>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) focused_holiday: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    changed: Option<CalendarChange>,
+    /// The week at the top of a [`ContinuousCalendar`](super::ContinuousCalendar), or of a
+    /// [`Calendar`](super::Calendar) too short for all the weeks of its month.
+    pub(super) scroll: Option<Date>,
+    /// The first month of [`LinkedCalendars`](super::LinkedCalendars).
+    pub(super) first_panel: Option<Date>,
+    /// The bounds of the calendar, as of the last render.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) bounds: Bounds,
+    /// Today's date, as of the last render.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) today: Option<Date>,
+}
+
+/// The bounds of a calendar, and what navigation does at them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) struct Bounds {
+    pub(super) min: Option<Date>,
+    pub(super) max: Option<Date>,
+    pub(super) behavior: BoundBehavior,
+}
+
+/// Where the days of a month were drawn by the last render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Cells {
+    /// The rows of weeks.
+    pub(super) area: Rect,
+    /// The width of the gap before each day.
+    pub(super) gap: u16,
+    /// The width of each day.
+    pub(super) cell: u16,
+    /// The number of lines of each week.
+    pub(super) rows: u16,
+    /// The date drawn at the top left.
+    pub(super) first: Date,
+    /// The month displayed.
+    pub(super) month: Month,
+    /// Were the days of the surrounding months drawn?
+    pub(super) surrounding: bool,
+    /// The disabled dates, a bit for each day from `first`.
+    pub(super) disabled: u64,
+    /// The arrows for the previous and next months, if they were drawn.
+    pub(super) arrows: Option<(Rect, Rect)>,
+}
+
+impl Cells {
+    /// Get the date at a screen position, and whether it is enabled.
+    fn cell_at(&self, x: u16, y: u16) -> Option<(Date, bool)> {
+        let (col, row) = (x.checked_sub(self.area.x)?, y.checked_sub(self.area.y)?);
+        // each day is a gap and then the day's number
+        let step = self.gap + self.cell;
+        if col >= self.area.width || row >= self.area.height || col % step < self.gap {
+            return None;
+        }
+        let n = row / self.rows * 7 + col / step;
+        let date = self.first.checked_add(Duration::days(n.into()))?;
+        if date.month() != self.month && !self.surrounding {
+            return None;
+        }
+        Some((date, self.disabled & (1 << n) == 0))
+    }
+
+    /// Get the first and last dates drawn, if any were.
+    fn drawn(&self) -> Option<(Date, Date)> {
+        if self.area.height == 0 || self.area.width == 0 {
+            return None;
+        }
+        let weeks = self.area.height.div_ceil(self.rows);
+        let mut first = self.first;
+        let mut last = first.checked_add(Duration::days(i64::from(weeks) * 7 - 1))?;
+        if !self.surrounding {
+            // the days of the surrounding months are blank
+            while first.month() != self.month && first < last {
+                first = first.next_day()?;
+            }
+            while last.month() != self.month && last > first {
+                last = last.previous_day()?;
+            }
+        }
+        Some((first, last))
+    }
+
+    /// Get the screen area of `date`, if it was drawn.
+    fn rect_of(&self, date: Date) -> Option<Rect> {
+        if date.month() != self.month && !self.surrounding {
+            return None;
+        }
+        let n = u16::try_from((date - self.first).whole_days()).ok()?;
+        let (row, col) = ((n / 7).checked_mul(self.rows)?, n % 7);
+        let x = self.area.x + self.gap + col * (self.gap + self.cell);
+        if row >= self.area.height || x >= self.area.right() {
+            return None;
+        }
+        let width = std::cmp::min(self.cell, self.area.right() - x);
+        let height = std::cmp::min(self.rows, self.area.height - row);
+        Some(Rect::new(x, self.area.y + row, width, height))
+    }
+
+    /// Is `date` drawn disabled? Dates that weren't drawn aren't.
+    fn is_disabled(&self, date: Date) -> bool {
+        match u32::try_from((date - self.first).whole_days()) {
+            Ok(n) if n < u64::BITS => self.disabled & (1 << n) != 0,
+            _ => false,
+        }
+    }
+}
+
+/// How the `_within` navigation methods of [`CalendarState`] handle moving onto a disabled date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Navigation {
+    /// Keep going past disabled dates to the next enabled one, looking up to a year ahead.
+    #[default]
+    Skip,
+    /// Stop at the enabled date closest to the destination, without going past it.
+    Clamp,
+}
+
+/// What the navigation methods of [`CalendarState`] do at the [`bounds`](super::Calendar::bounds)
+/// of the calendar, set with [`Calendar::bound_behavior`](super::Calendar::bound_behavior). The
+/// state learns the bounds when the calendar is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum BoundBehavior {
+    /// Stop at the first or last date within the bounds.
+    #[default]
+    Clamp,
+    /// Carry on from the other bound, e.g. moving a month from December in a calendar bounded to
+    /// one year focuses January of that year. Calendars with an open bound clamp instead.
+    Wrap,
+    /// Move past the bounds, as if there weren't any.
+    Ignore,
+}
+
+/// A change to a [`CalendarState`], returned by [`CalendarState::take_changed`].
+///
+/// Changes are ordered by how much they affect: when several happen between two calls of
+/// `take_changed`, the greatest of them is returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CalendarChange {
+    /// The focus moved to another date in the displayed month.
+    Focus,
+    /// The focus moved to another month, so the month displayed changed.
+    Month,
+    /// The selected dates or range changed. The focus may have moved as well.
+    Selection,
+}
+
+/// Where [`CalendarState::jump_to`] and [`CalendarState::jump_to_within`] moved the focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jump {
+    /// The focus moved to the requested date.
+    Exact,
+    /// The requested date is disabled or outside the calendar's bounds, so the focus moved to the
+    /// nearest enabled date instead.
+    Clamped(Date),
+    /// No enabled date was found near the requested date, so the focus didn't move.
+    Unavailable,
+}
+
+/// What [`CalendarState::click`] found at a screen position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarClick {
+    /// The arrow for the previous month, from [`Calendar::header_arrows`](super::Calendar::header_arrows).
+    PrevMonth,
+    /// The arrow for the next month.
+    NextMonth,
+    /// A date, which is selected unless it is disabled, as by
+    /// [`select_at`](CalendarState::select_at).
+    Date(Date),
+    /// Nothing that can be clicked.
+    None,
+}
+
+/// How many days [`Navigation::Skip`] looks through for an enabled date.
+const SKIP_LIMIT: u32 = 366;
+
+impl CalendarState {
+    /// Create a state with `focused` as the focused date, and nothing selected.
+    pub fn new(focused: Date) -> Self {
+        Self {
+            focused,
+            selected: BTreeSet::new(),
+            range_start: None,
+            range: None,
+            reference: None,
+            intended_day: None,
+            navigation: Navigation::default(),
+            cells: Vec::new(),
+            visible: None,
+            focused_holiday: None,
+            changed: None,
+            scroll: None,
+            first_panel: None,
+            bounds: Bounds::default(),
+            today: None,
+        }
+    }
+
+    /// Take the change flagged since the last call, clearing it. Returns `None` if neither the
+    /// focus nor the selection changed.
+    pub fn take_changed(&mut self) -> Option<CalendarChange> {
+        self.changed.take()
+    }
+
+    /// Flag `change`, keeping a greater change already flagged.
+    fn flag(&mut self, change: CalendarChange) {
+        self.changed = std::cmp::max(self.changed, Some(change));
+    }
+
+    /// Move the focus to `date`, flagging the move.
+    fn set_focus(&mut self, date: Date) {
+        if date == self.focused {
+            return;
+        }
+        self.intended_day = None;
+        let month = (date.year(), date.month()) != (self.focused.year(), self.focused.month());
+        self.flag(if month {
+            CalendarChange::Month
+        } else {
+            CalendarChange::Focus
+        });
+        self.focused = date;
+    }
+
+    /// Change the selected dates or range with `f`, flagging the change if there is one.
+    fn update_selection<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let before = (self.selected.clone(), self.range_start, self.range.clone());
+        f(self);
+        if before != (self.selected.clone(), self.range_start, self.range.clone()) {
+            self.flag(CalendarChange::Selection);
+        }
+    }
+
+    /// Set how the `_within` navigation methods handle disabled dates.
+    pub fn navigation(&mut self, navigation: Navigation) {
+        self.navigation = navigation;
+    }
+
+    /// Get the selected date. If several dates are selected, this is the earliest of them.
+    pub fn selected(&self) -> Option<Date> {
+        self.selected.first().copied()
+    }
+
+    /// Get all the selected dates, in order.
+    pub fn selected_dates(&self) -> impl Iterator<Item = Date> + '_ {
+        self.selected.iter().copied()
+    }
+
+    /// Is `date` selected?
+    pub fn is_selected(&self, date: Date) -> bool {
+        self.selected.contains(&date)
+    }
+
+    /// Select only `date`, replacing any other selected dates. The focus moves to the date too, so
+    /// the calendar displays the month it is in.
+    pub fn select(&mut self, date: Date) {
+        self.update_selection(|s| s.selected = BTreeSet::from([date]));
+        self.set_focus(date);
+    }
+
+    /// Like [`select`](Self::select), but only if `enabled` returns true for `date`, e.g.
+    /// [`Calendar::is_enabled`](super::Calendar::is_enabled). Returns whether the date was
+    /// selected: a disabled date is rejected, leaving the selection and focus unchanged.
+    pub fn select_within<F>(&mut self, date: Date, enabled: F) -> bool
+    where
+        F: Fn(Date) -> bool,
+    {
+        let allowed = enabled(date);
+        if allowed {
+            self.select(date);
+        }
+        allowed
+    }
+
+    /// Select only the focused date, replacing any other selected dates.
+    pub fn select_focused(&mut self) {
+        self.update_selection(|s| s.selected = BTreeSet::from([s.focused]));
+    }
+
+    /// Select `date` if it isn't selected, or deselect it if it is. Other selected dates are kept.
+    pub fn toggle(&mut self, date: Date) {
+        self.update_selection(|s| {
+            if !s.selected.remove(&date) {
+                s.selected.insert(date);
+            }
+        });
+    }
+
+    /// Toggle the selection of the focused date.
+    pub fn toggle_focused(&mut self) {
+        self.toggle(self.focused);
+    }
+
+    /// Add the seven days of the week containing `date` to the selection. Weeks begin on
+    /// `week_start`, which should match the [`week_start`](super::Calendar::week_start) of the
+    /// calendar so a whole row is selected.
+    pub fn select_week_of(&mut self, date: Date, week_start: Weekday) {
+        let offset = (date.weekday().number_days_from_monday() + 7
+            - week_start.number_days_from_monday())
+            % 7;
+        let mut day = date.checked_sub(Duration::days(offset.into()));
+        self.update_selection(|s| {
+            for _ in 0..7 {
+                let Some(d) = day else { break };
+                s.selected.insert(d);
+                day = d.next_day();
+            }
+        });
+    }
+
+    /// Start selecting a range at the focused date. This replaces any previously selected range.
+    pub fn begin_range(&mut self) {
+        self.update_selection(|s| {
+            s.range_start = Some(s.focused);
+            s.range = None;
+        });
+    }
+
+    /// Finish selecting a range, at the focused date. The range covers the dates between where it
+    /// started and the focused date, whichever comes first. Does nothing if no range was started.
+    pub fn complete_range(&mut self) {
+        self.update_selection(|s| {
+            if let Some(start) = s.range_start.take() {
+                s.range = Some(ordered(start, s.focused));
+            }
+        });
+    }
+
+    /// Stop selecting a range without completing it. A range completed before is kept.
+    pub fn cancel_range(&mut self) {
+        self.update_selection(|s| s.range_start = None);
+    }
+
+    /// Is a range being selected, i.e. [`begin_range`](Self::begin_range) was called but not
+    /// [`complete_range`](Self::complete_range)?
+    pub fn selecting_range(&self) -> bool {
+        self.range_start.is_some()
+    }
+
+    /// Get the selected range. This is `None` while a new range is being selected.
+    pub fn range(&self) -> Option<RangeInclusive<Date>> {
+        self.range.clone()
+    }
+
+    /// The range to display: the one being selected, or the completed one.
+    pub(super) fn shown_range(&self) -> Option<RangeInclusive<Date>> {
+        match self.range_start {
+            Some(start) => Some(ordered(start, self.focused)),
+            None => self.range(),
+        }
+    }
+
+    /// Get the name of the holiday on the focused date, as of the last render. This is set when the
+    /// calendar has [`holidays`](super::Calendar::holidays), e.g. to show in a status line.
+    pub fn focused_holiday(&self) -> Option<&str> {
+        self.focused_holiday.as_deref()
+    }
+
+    /// Get the date drawn at column `x` and row `y` of the screen by the last render. Returns `None`
+    /// if there is no date there, e.g. the position is a header, the block, the gap between days,
+    /// or a hidden day of a surrounding month.
+    pub fn date_at(&self, x: u16, y: u16) -> Option<Date> {
+        self.cell_at(x, y).map(|(date, _)| date)
+    }
+
+    /// Select the date drawn at column `x` and row `y` of the screen by the last render, e.g. when
+    /// it is clicked, and return it. Like [`select`](Self::select), the focus moves to the date.
+    ///
+    /// A disabled date is returned, but isn't selected or focused. The app can still select it
+    /// with [`select`](Self::select) if it wants to.
+    pub fn select_at(&mut self, x: u16, y: u16) -> Option<Date> {
+        let (date, enabled) = self.cell_at(x, y)?;
+        if enabled {
+            self.select(date);
+        }
+        Some(date)
+    }
+
+    /// Get the screen area of the cell `date` was drawn in by the last render, e.g. to draw a
+    /// popup beside it. Returns `None` if the date wasn't drawn. A day of a surrounding month drawn
+    /// by two months of a [`MonthsGrid`](super::MonthsGrid) is found in its own month.
+    pub fn cell_rect(&self, date: Date) -> Option<Rect> {
+        let own = self
+            .cells
+            .iter()
+            .filter(|cells| cells.month == date.month());
+        own.chain(&self.cells).find_map(|cells| cells.rect_of(date))
+    }
+
+    /// Handle a click at column `x` and row `y` of the screen, on a calendar drawn in `area` by the
+    /// last render. The arrows of [`header_arrows`](super::Calendar::header_arrows) page to the
+    /// previous or next month, and dates are selected as by [`select_at`](Self::select_at).
+    /// Returns what was clicked, which is [`CalendarClick::None`] for clicks outside `area`.
+    pub fn click(&mut self, x: u16, y: u16, area: Rect) -> CalendarClick {
+        let hit = |rect: Rect| rect.intersects(Rect::new(x, y, 1, 1));
+        if !hit(area) {
+            return CalendarClick::None;
+        }
+        let arrow = self.cells.iter().find_map(|cells| {
+            let (prev, next) = cells.arrows?;
+            match (hit(prev), hit(next)) {
+                (true, _) => Some(CalendarClick::PrevMonth),
+                (_, true) => Some(CalendarClick::NextMonth),
+                _ => None,
+            }
+        });
+        match arrow {
+            Some(CalendarClick::PrevMonth) => self.prev_month(),
+            Some(CalendarClick::NextMonth) => self.next_month(),
+            _ => {}
+        }
+        arrow.unwrap_or_else(|| {
+            self.select_at(x, y)
+                .map_or(CalendarClick::None, CalendarClick::Date)
+        })
+    }
+
+    /// Get the dates drawn by the last render, from the first to the last, e.g. to load the events
+    /// of just those dates. The days of the surrounding months are included when they are drawn,
+    /// and the range spans all the months of a [`MonthsGrid`](super::MonthsGrid) or
+    /// [`LinkedCalendars`](super::LinkedCalendars). When the area is too short for all the weeks
+    /// of a month, only those around the focused date are drawn.
+    ///
+    /// Returns `None` if no dates were drawn, e.g. in an empty area, or before the first render.
+    pub fn visible_range(&self) -> Option<RangeInclusive<Date>> {
+        self.visible.clone()
+    }
+
+    /// Keep where the days were drawn by a render, and the range of dates drawn.
+    pub(super) fn set_cells(&mut self, cells: Vec<Cells>) {
+        self.visible = cells
+            .iter()
+            .filter_map(Cells::drawn)
+            .reduce(|(a, b), (c, d)| (a.min(c), b.max(d)))
+            .map(|(first, last)| first..=last);
+        self.cells = cells;
+    }
+
+    /// Get the date at a screen position, and whether it is enabled.
+    fn cell_at(&self, x: u16, y: u16) -> Option<(Date, bool)> {
+        self.cells.iter().find_map(|cells| cells.cell_at(x, y))
+    }
+
+    /// Set the reference date, drawn with the calendar's
+    /// [`reference_style`](super::Calendar::reference_style) whichever month is displayed, e.g.
+    /// to compare the original date of an appointment with the dates it could be moved to.
+    /// `None` clears it. The reference date isn't selected or focused, and setting it isn't a
+    /// change returned by [`take_changed`](Self::take_changed).
+    pub fn set_reference(&mut self, date: Option<Date>) {
+        self.reference = date;
+    }
+
+    /// Get the reference date. See [`set_reference`](Self::set_reference).
+    pub fn reference(&self) -> Option<Date> {
+        self.reference
+    }
+
+    /// Clear the selection, including any selected range.
+    pub fn clear_selection(&mut self) {
+        self.update_selection(|s| {
+            s.selected.clear();
+            s.range_start = None;
+            s.range = None;
+        });
+    }
+
+    /// Get the focused date.
+    pub fn focused(&self) -> Date {
+        self.focused
+    }
+
+    /// Is the focused date within the bounds and not disabled, as far as the last render showed?
+    pub(super) fn focused_enabled(&self) -> bool {
+        known_enabled(self.bounds, &self.cells, self.focused)
+    }
+
+    /// Set the focused date.
+    pub fn focus(&mut self, date: Date) {
+        self.set_focus(date);
+    }
+
+    /// Focus `date`, displaying its month. Dates outside the calendar's bounds focus the nearest
+    /// bound instead, unless its [`bound_behavior`](super::Calendar::bound_behavior) ignores them.
+    pub fn jump_to(&mut self, date: Date) -> Jump {
+        let target = self.bounds.keep(Some(date), 0).unwrap_or(date);
+        self.set_focus(target);
+        if target == date {
+            Jump::Exact
+        } else {
+            Jump::Clamped(target)
+        }
+    }
+
+    /// Read a date typed by the user with [`parse_date`], and [jump](Self::jump_to) to it. The
+    /// focus doesn't move if the date can't be read. Use
+    /// [`parse_date_with`](super::parse_date_with) and [`jump_to`](Self::jump_to) for dates with
+    /// the month first.
+    pub fn jump_to_str(&mut self, input: &str) -> Result<Jump, ParseDateError> {
+        let date = parse_date(input)?;
+        Ok(self.jump_to(date))
+    }
+
+    /// Focus `date` if `enabled` returns true for it. Otherwise the nearest enabled date up to a
+    /// year before or after it is focused, preferring the later date when two are as near.
+    pub fn jump_to_within<F>(&mut self, date: Date, enabled: F) -> Jump
+    where
+        F: Fn(Date) -> bool,
+    {
+        if enabled(date) {
+            self.set_focus(date);
+            return Jump::Exact;
+        }
+        for n in 1..=i64::from(SKIP_LIMIT) {
+            let found = [n, -n]
+                .into_iter()
+                .filter_map(|n| date.checked_add(Duration::days(n)))
+                .find(|d| enabled(*d));
+            if let Some(found) = found {
+                self.set_focus(found);
+                return Jump::Clamped(found);
+            }
+        }
+        Jump::Unavailable
+    }
+
+    /// Focus today's date, as the calendar was last rendered with (see
+    /// [`Calendar::today`](super::Calendar::today) and [`Calendar::clock`](super::Calendar::clock)),
+    /// or the date in the local time zone before the first render. See [`jump_to`](Self::jump_to).
+    pub fn today(&mut self) -> Jump {
+        self.today_with(super::local_today)
+    }
+
+    /// Like [`today`](Self::today), but calls `clock` for the date before the first render, e.g.
+    /// for a fixed date in tests.
+    pub fn today_with<F>(&mut self, clock: F) -> Jump
+    where
+        F: FnOnce() -> Date,
+    {
+        self.jump_to(self.today.unwrap_or_else(clock))
+    }
+
+    /// Move the focus `n` days forward (or backward for negative `n`), crossing into other months
+    /// as needed. The focus doesn't move if the result would be outside the dates supported by
+    /// the time crate.
+    ///
+    /// This and the other navigation methods keep to the calendar's bounds as set by its
+    /// [`bound_behavior`](super::Calendar::bound_behavior).
+    pub fn move_days(&mut self, n: i64) {
+        if let Some(date) = self.bounds.add_days(self.focused, n) {
+            self.set_focus(date);
+        }
+    }
+
+    /// Move the focus `n` weeks forward (or backward for negative `n`).
+    pub fn move_weeks(&mut self, n: i64) {
+        self.move_days(n.saturating_mul(7));
+    }
+
+    /// Move the focus `n` months forward (or backward for negative `n`), keeping the day of the
+    /// month. If the day doesn't exist in the new month, the last day of the month is focused
+    /// instead (e.g. moving a month from January 31st focuses February 28th or 29th).
+    ///
+    /// The day is remembered while paging by months and years, so paging on from a shortened
+    /// month goes back to it: January 31st pages to February 28th and then March 31st. Moving the
+    /// focus any other way forgets it.
+    pub fn move_months(&mut self, n: i32) {
+        let day = self.paging_day();
+        if let Some(date) = self.bounds.add_months(self.focused, n, day) {
+            self.page_to(date, day);
+        }
+    }
+
+    /// The day of the month to keep to when paging by months.
+    fn paging_day(&self) -> u8 {
+        self.intended_day.unwrap_or(self.focused.day())
+    }
+
+    /// Focus `date`, paged to while keeping to `day`. The day is remembered if the month is too
+    /// short for it, but not if the date was moved for another reason, such as the bounds.
+    fn page_to(&mut self, date: Date, day: u8) {
+        self.set_focus(date);
+        if day > date.month().length(date.year()) {
+            self.intended_day = Some(day);
+        }
+    }
+
+    /// Move the focus `n` years forward (or backward for negative `n`), keeping the month and day.
+    /// February 29th moves to February 28th in years that aren't leap years.
+    pub fn move_years(&mut self, n: i32) {
+        self.move_months(n.saturating_mul(12));
+    }
+
+    /// Display the next month, keeping the focused day of the month. See
+    /// [`move_months`](Self::move_months).
+    ///
+    /// To page a [`MonthsGrid`](super::MonthsGrid) a whole grid at a time, move by the number of
+    /// months it displays instead, and to keep to enabled dates use
+    /// [`move_months_within`](Self::move_months_within).
+    pub fn next_month(&mut self) {
+        self.move_months(1);
+    }
+
+    /// Display the previous month, keeping the focused day of the month. See
+    /// [`next_month`](Self::next_month).
+    pub fn prev_month(&mut self) {
+        self.move_months(-1);
+    }
+
+    /// Display the same month next year, keeping the focused day of the month. See
+    /// [`next_month`](Self::next_month).
+    pub fn next_year(&mut self) {
+        self.move_years(1);
+    }
+
+    /// Display the same month last year, keeping the focused day of the month. See
+    /// [`next_month`](Self::next_month).
+    pub fn prev_year(&mut self) {
+        self.move_years(-1);
+    }
+
+    /// Like [`move_days`](Self::move_days), but only focuses dates for which `enabled` returns
+    /// true. If the destination is disabled, [`Navigation::Skip`] moves on to the next enabled date
+    /// in the same direction, and [`Navigation::Clamp`] moves back to the enabled date closest to
+    /// it. The focus doesn't move if no enabled date is found.
+    pub fn move_days_within<F>(&mut self, n: i64, enabled: F)
+    where
+        F: Fn(Date) -> bool,
+    {
+        let Some(target) = self.bounds.add_days(self.focused, n) else {
+            return;
+        };
+        let found = match self.navigation {
+            Navigation::Skip => search(target, n.signum(), SKIP_LIMIT.into(), &enabled),
+            // the dates between the destination and the focus, not including the focus
+            Navigation::Clamp => search(target, -n.signum(), n.unsigned_abs(), &enabled),
+        };
+        if let Some(date) = found {
+            self.set_focus(date);
+        }
+    }
+
+    /// Like [`move_weeks`](Self::move_weeks), but only focuses dates for which `enabled` returns
+    /// true. See [`move_days_within`](Self::move_days_within).
+    pub fn move_weeks_within<F>(&mut self, n: i64, enabled: F)
+    where
+        F: Fn(Date) -> bool,
+    {
+        self.move_days_within(n.saturating_mul(7), enabled);
+    }
+
+    /// Like [`move_months`](Self::move_months), but only focuses dates for which `enabled` returns
+    /// true. If the destination is disabled, the enabled date closest to it in the same month is
+    /// focused instead.
+    ///
+    /// When the whole month is disabled, [`Navigation::Skip`] pages on in the same direction to the
+    /// next month with an enabled date, looking up to a year ahead, while [`Navigation::Clamp`]
+    /// doesn't move the focus. The focus doesn't move if no enabled date is found.
+    pub fn move_months_within<F>(&mut self, n: i32, enabled: F)
+    where
+        F: Fn(Date) -> bool,
+    {
+        let tries = if self.navigation == Navigation::Skip && n != 0 {
+            12
+        } else {
+            1
+        };
+        let day = self.paging_day();
+        let found = (0..tries)
+            .map_while(|i| {
+                self.bounds
+                    .add_months(self.focused, n.saturating_add(i * n.signum()), day)
+            })
+            .find_map(|target| {
+                (1..=target.month().length(target.year()))
+                    .filter_map(|day| target.replace_day(day).ok())
+                    .filter(|date| enabled(*date))
+                    .min_by_key(|date| date.day().abs_diff(target.day()))
+            });
+        if let Some(date) = found {
+            self.page_to(date, day);
+        }
+    }
+
+    /// Like [`move_years`](Self::move_years), but only focuses dates for which `enabled` returns
+    /// true. See [`move_months_within`](Self::move_months_within).
+    pub fn move_years_within<F>(&mut self, n: i32, enabled: F)
+    where
+        F: Fn(Date) -> bool,
+    {
+        self.move_months_within(n.saturating_mul(12), enabled);
+    }
+}
+
+impl CalendarState {
+    /// Handle a key press with the default bindings, returning whether the key was used, so an app
+    /// can handle the keys that weren't:
+    ///
+    /// * the arrow keys, or `h`, `j`, `k` and `l`, move the focus by days and weeks
+    /// * page up and page down move the focus by months, or by years with shift
+    /// * home focuses today, as of the last render
+    /// * enter selects the focused date
+    ///
+    /// Keys pressed with control or alt aren't used. The focus is kept to the dates enabled as of
+    /// the last render: those within the calendar's [`bounds`](super::Calendar::bounds), and not
+    /// [`disabled`](super::Calendar::disabled) in the month drawn. Days disabled in other months
+    /// are only known once their month has been drawn, so the focus may land on them. At the
+    /// bounds, the focus stops, wraps or carries on as set by the calendar's
+    /// [`bound_behavior`](super::Calendar::bound_behavior), though dates outside them are never
+    /// selected. Available with the `crossterm` feature.
+    #[cfg(feature = "crossterm")]
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return false;
+        }
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let (bounds, cells) = (self.bounds, self.cells.clone());
+        let reach = match bounds.behavior {
+            BoundBehavior::Ignore => Bounds::default(),
+            _ => bounds,
+        };
+        let enabled = |date| known_enabled(reach, &cells, date);
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => self.move_days_within(-1, enabled),
+            KeyCode::Right | KeyCode::Char('l') => self.move_days_within(1, enabled),
+            KeyCode::Up | KeyCode::Char('k') => self.move_weeks_within(-1, enabled),
+            KeyCode::Down | KeyCode::Char('j') => self.move_weeks_within(1, enabled),
+            KeyCode::PageUp if shift => self.move_years_within(-1, enabled),
+            KeyCode::PageDown if shift => self.move_years_within(1, enabled),
+            KeyCode::PageUp => self.move_months_within(-1, enabled),
+            KeyCode::PageDown => self.move_months_within(1, enabled),
+            KeyCode::Home => {
+                let today = self.today.unwrap_or_else(super::local_today);
+                self.jump_to_within(today, enabled);
+            }
+            KeyCode::Enter => {
+                if known_enabled(bounds, &cells, self.focused) {
+                    self.select_focused();
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Is `date` within `bounds`, and not drawn disabled in `cells`?
+fn known_enabled(bounds: Bounds, cells: &[Cells], date: Date) -> bool {
+    bounds.contains(date) && !cells.iter().any(|cells| cells.is_disabled(date))
+}
+
+impl Bounds {
+    fn contains(&self, date: Date) -> bool {
+        self.min.is_none_or(|min| date >= min) && self.max.is_none_or(|max| date <= max)
+    }
+
+    fn clamp(&self, date: Date) -> Date {
+        let date = self.min.map_or(date, |min| date.max(min));
+        self.max.map_or(date, |max| date.min(max))
+    }
+
+    /// The bounds to wrap around in, when wrapping from `from`.
+    fn wrapping(&self, from: Date) -> Option<(Date, Date)> {
+        match (self.behavior, self.min, self.max) {
+            (BoundBehavior::Wrap, Some(min), Some(max)) if self.contains(from) => Some((min, max)),
+            _ => None,
+        }
+    }
+
+    /// Keep `target`, the destination of a move in the direction of `step`, within the bounds.
+    fn keep(&self, target: Option<Date>, step: i64) -> Option<Date> {
+        match (self.behavior, target) {
+            (BoundBehavior::Ignore, _) => target,
+            (_, Some(target)) => Some(self.clamp(target)),
+            // moving past the dates supported by the time crate stops at the bound, if any
+            (_, None) if step < 0 => self.min,
+            (_, None) => self.max,
+        }
+    }
+
+    /// The date `n` days from `from`.
+    fn add_days(&self, from: Date, n: i64) -> Option<Date> {
+        if let Some((min, max)) = self.wrapping(from) {
+            let span = (max - min).whole_days() + 1;
+            let offset = ((from - min).whole_days() + n.rem_euclid(span)) % span;
+            return min.checked_add(Duration::days(offset));
+        }
+        self.keep(from.checked_add(Duration::days(n)), n)
+    }
+
+    /// The date on `day` `n` months from `from`. See [`add_months_on`].
+    fn add_months(&self, from: Date, n: i32, day: u8) -> Option<Date> {
+        if let Some((min, max)) = self.wrapping(from) {
+            let index = |date: Date| date.year() * 12 + date.month() as i32;
+            let span = index(max) - index(min) + 1;
+            let offset = index(from) - index(min);
+            let wrapped = (offset + n.rem_euclid(span)) % span;
+            // the day may be outside the bounds in the first and last months
+            return add_months_on(from, wrapped - offset, day).map(|date| self.clamp(date));
+        }
+        self.keep(add_months_on(from, n, day), n.into())
+    }
+}
+
+/// Find the first enabled date of the `count` dates starting at `from`, stepping `step` days at a
+/// time.
+fn search<F>(from: Date, step: i64, count: u64, enabled: &F) -> Option<Date>
+where
+    F: Fn(Date) -> bool,
+{
+    let mut date = from;
+    for _ in 0..count {
+        if enabled(date) {
+            return Some(date);
+        }
+        date = date.checked_add(Duration::days(step))?;
+    }
+    None
+}
+
+fn ordered(a: Date, b: Date) -> RangeInclusive<Date> {
+    std::cmp::min(a, b)..=std::cmp::max(a, b)
+}
+
+/// Add `n` months to `date`, clamping the day to the length of the new month. Returns `None` if
+/// the result is outside the supported range of dates.
+pub(super) fn add_months(date: Date, n: i32) -> Option<Date> {
+    add_months_on(date, n, date.day())
+}
+
+/// Add `n` months to `date`, on `day` of the new month, or its last day if it is shorter.
+fn add_months_on(date: Date, n: i32, day: u8) -> Option<Date> {
+    let months = date.year() * 12 + date.month() as i32 - 1 + n;
+    let year = months.div_euclid(12);
+    let month = Month::try_from((months.rem_euclid(12) + 1) as u8).ok()?;
+    let day = std::cmp::min(day, month.length(year));
+    Date::from_calendar_date(year, month, day).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn days_and_weeks() {
+        let mut s = CalendarState::new(date!(2023 - 01 - 31));
+        s.move_days(1);
+        assert_eq!(s.focused(), date!(2023 - 02 - 01));
+        s.move_days(-1);
+        assert_eq!(s.focused(), date!(2023 - 01 - 31));
+        s.move_weeks(-5);
+        assert_eq!(s.focused(), date!(2022 - 12 - 27));
+    }
+
+    #[test]
+    fn months_and_years() {
+        let mut s = CalendarState::new(date!(2024 - 01 - 31));
+        s.move_months(1);
+        assert_eq!(s.focused(), date!(2024 - 02 - 29));
+        // the 31st is kept while paging
+        s.move_months(-2);
+        assert_eq!(s.focused(), date!(2023 - 12 - 31));
+        s.move_months(14);
+        assert_eq!(s.focused(), date!(2025 - 02 - 28));
+        s.move_months(1);
+        assert_eq!(s.focused(), date!(2025 - 03 - 31));
+        s.move_months(1);
+        assert_eq!(s.focused(), date!(2025 - 04 - 30));
+        // and forgotten when moving otherwise
+        s.move_days(-1);
+        s.move_months(1);
+        assert_eq!(s.focused(), date!(2025 - 05 - 29));
+
+        let mut s = CalendarState::new(date!(2024 - 02 - 29));
+        s.move_years(1);
+        assert_eq!(s.focused(), date!(2025 - 02 - 28));
+        s.move_years(-2);
+        assert_eq!(s.focused(), date!(2023 - 02 - 28));
+        s.move_years(5);
+        assert_eq!(s.focused(), date!(2028 - 02 - 29));
+        s.move_years(1);
+        s.move_months(1);
+        assert_eq!(s.focused(), date!(2029 - 03 - 29));
+    }
+
+    #[test]
+    fn paging() {
+        let mut s = CalendarState::new(date!(2024 - 01 - 31));
+        s.next_month();
+        assert_eq!(s.focused(), date!(2024 - 02 - 29));
+        s.prev_month();
+        assert_eq!(s.focused(), date!(2024 - 01 - 31));
+        s.prev_year();
+        assert_eq!(s.focused(), date!(2023 - 01 - 31));
+        s.next_year();
+        s.next_year();
+        assert_eq!(s.focused(), date!(2025 - 01 - 31));
+
+        // skipping a disabled month keeps the day as well
+        let mut s = CalendarState::new(date!(2023 - 01 - 31));
+        s.move_months_within(1, |d| d.month() != Month::March);
+        assert_eq!(s.focused(), date!(2023 - 02 - 28));
+        s.move_months_within(1, |d| d.month() != Month::March);
+        assert_eq!(s.focused(), date!(2023 - 04 - 30));
+        s.move_months_within(1, |_| true);
+        assert_eq!(s.focused(), date!(2023 - 05 - 31));
+    }
+
+    #[test]
+    fn selection() {
+        let mut s = CalendarState::new(date!(2023 - 01 - 10));
+        assert_eq!(s.selected(), None);
+        s.select_focused();
+        s.move_days(3);
+        assert_eq!(s.selected(), Some(date!(2023 - 01 - 10)));
+        assert_eq!(s.focused(), date!(2023 - 01 - 13));
+
+        s.select(date!(2023 - 03 - 01));
+        assert_eq!(s.selected(), Some(date!(2023 - 03 - 01)));
+        assert_eq!(s.focused(), date!(2023 - 03 - 01));
+        s.clear_selection();
+        assert_eq!(s.selected(), None);
+    }
+
+    #[test]
+    fn multiple_selection() {
+        let mut s = CalendarState::new(date!(2023 - 04 - 10));
+        s.toggle_focused();
+        s.toggle(date!(2023 - 04 - 03));
+        s.toggle(date!(2023 - 04 - 20));
+        assert!(s.is_selected(date!(2023 - 04 - 10)));
+        assert_eq!(s.selected(), Some(date!(2023 - 04 - 03)));
+        s.toggle(date!(2023 - 04 - 03));
+        assert!(!s.is_selected(date!(2023 - 04 - 03)));
+        assert_eq!(
+            s.selected_dates().collect::<Vec<_>>(),
+            [date!(2023 - 04 - 10), date!(2023 - 04 - 20)]
+        );
+
+        // select replaces the whole selection
+        s.select(date!(2023 - 04 - 01));
+        assert_eq!(s.selected_dates().count(), 1);
+
+        s.clear_selection();
+        s.select_week_of(date!(2023 - 04 - 12), Weekday::Sunday);
+        assert_eq!(s.selected(), Some(date!(2023 - 04 - 09)));
+        assert_eq!(s.selected_dates().last(), Some(date!(2023 - 04 - 15)));
+        s.clear_selection();
+        s.select_week_of(date!(2023 - 04 - 09), Weekday::Monday);
+        assert_eq!(s.selected(), Some(date!(2023 - 04 - 03)));
+        assert_eq!(s.selected_dates().count(), 7);
+    }
+
+    #[test]
+    fn range_selection() {
+        let mut s = CalendarState::new(date!(2023 - 04 - 20));
+        s.complete_range();
+        assert_eq!(s.range(), None);
+
+        s.begin_range();
+        s.move_weeks(2);
+        assert!(s.selecting_range());
+        assert_eq!(s.range(), None);
+        assert_eq!(
+            s.shown_range(),
+            Some(date!(2023 - 04 - 20)..=date!(2023 - 05 - 04))
+        );
+        s.complete_range();
+        assert_eq!(
+            s.range(),
+            Some(date!(2023 - 04 - 20)..=date!(2023 - 05 - 04))
+        );
+
+        // a range selected backwards is put in order
+        s.begin_range();
+        s.move_months(-1);
+        s.complete_range();
+        assert_eq!(
+            s.range(),
+            Some(date!(2023 - 04 - 04)..=date!(2023 - 05 - 04))
+        );
+
+        s.begin_range();
+        s.cancel_range();
+        assert_eq!(s.shown_range(), None);
+        s.clear_selection();
+        assert_eq!(s.range(), None);
+    }
+
+    #[test]
+    fn navigation_within() {
+        // weekdays in April 2023, from the 3rd
+        let enabled = |d: Date| {
+            d >= date!(2023 - 04 - 03)
+                && d <= date!(2023 - 04 - 30)
+                && !matches!(d.weekday(), Weekday::Saturday | Weekday::Sunday)
+        };
+
+        let mut s = CalendarState::new(date!(2023 - 04 - 06));
+        s.move_days_within(2, enabled);
+        assert_eq!(s.focused(), date!(2023 - 04 - 10));
+        s.move_days_within(-1, enabled);
+        assert_eq!(s.focused(), date!(2023 - 04 - 07));
+        s.move_weeks_within(-1, enabled);
+        assert_eq!(s.focused(), date!(2023 - 04 - 07));
+        // nothing is enabled after the 30th
+        s.move_weeks_within(4, enabled);
+        assert_eq!(s.focused(), date!(2023 - 04 - 07));
+
+        s.navigation(Navigation::Clamp);
+        s.move_days_within(2, enabled);
+        assert_eq!(s.focused(), date!(2023 - 04 - 07));
+        s.move_days_within(4, enabled);
+        assert_eq!(s.focused(), date!(2023 - 04 - 11));
+        s.move_weeks_within(4, enabled);
+        assert_eq!(s.focused(), date!(2023 - 04 - 28));
+
+        // months stay in enabled months
+        s.move_months_within(1, enabled);
+        assert_eq!(s.focused(), date!(2023 - 04 - 28));
+        s.focus(date!(2023 - 03 - 01));
+        s.move_months_within(1, enabled);
+        assert_eq!(s.focused(), date!(2023 - 04 - 03));
+    }
+
+    #[test]
+    fn bound_behavior() {
+        let bounded = |behavior| {
+            let mut s = CalendarState::new(date!(2023 - 12 - 30));
+            s.bounds = Bounds {
+                min: Some(date!(2023 - 01 - 01)),
+                max: Some(date!(2023 - 12 - 31)),
+                behavior,
+            };
+            s
+        };
+
+        let mut s = bounded(BoundBehavior::Clamp);
+        s.move_days(3);
+        assert_eq!(s.focused(), date!(2023 - 12 - 31));
+        s.move_months(1);
+        assert_eq!(s.focused(), date!(2023 - 12 - 31));
+        s.move_years(-2);
+        assert_eq!(s.focused(), date!(2023 - 01 - 01));
+        s.move_days(-100_000_000);
+        assert_eq!(s.focused(), date!(2023 - 01 - 01));
+
+        // December to January of the same year
+        let mut s = bounded(BoundBehavior::Wrap);
+        s.move_days(3);
+        assert_eq!(s.focused(), date!(2023 - 01 - 02));
+        s.move_weeks(-1);
+        assert_eq!(s.focused(), date!(2023 - 12 - 26));
+        s.move_months(1);
+        assert_eq!(s.focused(), date!(2023 - 01 - 26));
+        s.move_months(-13);
+        assert_eq!(s.focused(), date!(2023 - 12 - 26));
+        s.move_years(1);
+        assert_eq!(s.focused(), date!(2023 - 12 - 26));
+        s.move_days_within(7, |d| d.day() != 2);
+        assert_eq!(s.focused(), date!(2023 - 01 - 03));
+        s.move_months_within(-1, |d| d.month() != Month::December);
+        assert_eq!(s.focused(), date!(2023 - 11 - 03));
+        // an open bound clamps
+        s.bounds.min = None;
+        s.move_months(2);
+        assert_eq!(s.focused(), date!(2023 - 12 - 31));
+
+        let mut s = bounded(BoundBehavior::Ignore);
+        s.move_days(3);
+        assert_eq!(s.focused(), date!(2024 - 01 - 02));
+        s.move_years(-2);
+        assert_eq!(s.focused(), date!(2022 - 01 - 02));
+
+        // jumps clamp to the bounds, unless they are ignored
+        let mut s = bounded(BoundBehavior::Clamp);
+        assert_eq!(s.jump_to(date!(2023 - 06 - 01)), Jump::Exact);
+        let last = date!(2023 - 12 - 31);
+        assert_eq!(s.jump_to(date!(2024 - 03 - 01)), Jump::Clamped(last));
+        assert_eq!(s.focused(), last);
+        assert_eq!(
+            s.jump_to_str("2022-12-31"),
+            Ok(Jump::Clamped(date!(2023 - 01 - 01)))
+        );
+        assert_eq!(s.today_with(|| date!(2024 - 01 - 01)), Jump::Clamped(last));
+        let mut s = bounded(BoundBehavior::Ignore);
+        assert_eq!(s.jump_to(date!(2024 - 03 - 01)), Jump::Exact);
+        assert_eq!(s.focused(), date!(2024 - 03 - 01));
+    }
+
+    #[test]
+    fn jumps() {
+        let mut s = CalendarState::new(date!(2023 - 04 - 06));
+        assert_eq!(s.jump_to(date!(2025 - 03 - 01)), Jump::Exact);
+        assert_eq!(s.focused(), date!(2025 - 03 - 01));
+        assert_eq!(s.today_with(|| date!(2024 - 02 - 29)), Jump::Exact);
+        assert_eq!(s.focused(), date!(2024 - 02 - 29));
+
+        let bounds = |d: Date| d >= date!(2024 - 01 - 01) && d <= date!(2024 - 12 - 31);
+        assert_eq!(s.jump_to_within(date!(2024 - 06 - 01), bounds), Jump::Exact);
+        let last = date!(2024 - 12 - 31);
+        assert_eq!(
+            s.jump_to_within(date!(2025 - 03 - 01), bounds),
+            Jump::Clamped(last)
+        );
+        assert_eq!(s.focused(), last);
+        assert_eq!(
+            s.jump_to_within(date!(2027 - 01 - 01), bounds),
+            Jump::Unavailable
+        );
+        assert_eq!(s.focused(), last);
+
+        // typed dates
+        assert_eq!(s.jump_to_str("2025-06-01"), Ok(Jump::Exact));
+        assert_eq!(s.focused(), date!(2025 - 06 - 01));
+        assert_eq!(
+            s.jump_to_str("2025-02-30").map_err(|e| e.to_string()),
+            Err("`30` isn't a day of February 2025".to_string())
+        );
+        assert_eq!(s.focused(), date!(2025 - 06 - 01));
+    }
+
+    #[test]
+    fn changes() {
+        let mut s = CalendarState::new(date!(2023 - 04 - 06));
+        assert_eq!(s.take_changed(), None);
+        s.move_days(1);
+        assert_eq!(s.take_changed(), Some(CalendarChange::Focus));
+        assert_eq!(s.take_changed(), None);
+        s.move_weeks(4);
+        assert_eq!(s.take_changed(), Some(CalendarChange::Month));
+
+        // moving nowhere isn't a change
+        s.focus(s.focused());
+        s.move_days_within(1, |_| false);
+        assert_eq!(s.take_changed(), None);
+
+        s.toggle_focused();
+        assert_eq!(s.take_changed(), Some(CalendarChange::Selection));
+        s.select(s.focused());
+        assert_eq!(s.take_changed(), None);
+        // the greatest change is kept
+        s.next_month();
+        s.select(date!(2023 - 06 - 01));
+        s.move_days(1);
+        assert_eq!(s.take_changed(), Some(CalendarChange::Selection));
+
+        s.begin_range();
+        s.move_days(2);
+        s.complete_range();
+        assert_eq!(s.take_changed(), Some(CalendarChange::Selection));
+        s.cancel_range();
+        assert_eq!(s.take_changed(), None);
+        s.clear_selection();
+        assert_eq!(s.take_changed(), Some(CalendarChange::Selection));
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn keys() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let mut s = CalendarState::new(date!(2023 - 04 - 06));
+        s.bounds.max = Some(date!(2023 - 05 - 10));
+        assert!(s.handle_key(&key(KeyCode::Right)));
+        assert!(s.handle_key(&key(KeyCode::Char('j'))));
+        assert_eq!(s.focused(), date!(2023 - 04 - 14));
+        assert!(s.handle_key(&key(KeyCode::PageDown)));
+        assert_eq!(s.focused(), date!(2023 - 05 - 10));
+        // the focus stays within the bounds
+        assert!(s.handle_key(&key(KeyCode::Down)));
+        assert_eq!(s.focused(), date!(2023 - 05 - 10));
+        let shift = &KeyEvent::new(KeyCode::PageUp, KeyModifiers::SHIFT);
+        assert!(s.handle_key(shift));
+        assert_eq!(s.focused(), date!(2022 - 05 - 10));
+        assert!(s.handle_key(&key(KeyCode::Enter)));
+        assert_eq!(s.selected(), Some(date!(2022 - 05 - 10)));
+
+        // other keys are left to the app
+        assert!(!s.handle_key(&key(KeyCode::Char('x'))));
+        let ctrl = &KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL);
+        assert!(!s.handle_key(ctrl));
+        assert_eq!(s.focused(), date!(2022 - 05 - 10));
+
+        // wrapping, and ignoring the bounds without selecting past them
+        s.bounds = Bounds {
+            min: Some(date!(2022 - 05 - 01)),
+            max: Some(date!(2022 - 05 - 31)),
+            behavior: BoundBehavior::Wrap,
+        };
+        assert!(s.handle_key(&key(KeyCode::Up)));
+        assert_eq!(s.focused(), date!(2022 - 05 - 03));
+        assert!(s.handle_key(&key(KeyCode::Up)));
+        assert_eq!(s.focused(), date!(2022 - 05 - 27));
+        s.bounds.behavior = BoundBehavior::Ignore;
+        assert!(s.handle_key(&key(KeyCode::Down)));
+        assert_eq!(s.focused(), date!(2022 - 06 - 03));
+        assert!(s.handle_key(&key(KeyCode::Enter)));
+        assert_eq!(s.selected(), Some(date!(2022 - 05 - 10)));
+    }
+
+    #[test]
+    fn out_of_range() {
+        let mut s = CalendarState::new(Date::MAX);
+        s.move_days(1);
+        s.move_months(1);
+        assert_eq!(s.focused(), Date::MAX);
+    }
+}
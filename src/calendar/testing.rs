@@ -0,0 +1,139 @@
+//! Helpers for testing calendar layouts, used by the crate's own tests and available to apps with
+//! the `testing` feature.
+//!
+//! [`render_month`] draws a [`Calendar`] into a [`Buffer`], and the returned [`Snapshot`] gives the
+//! text of each line, and a map of the styles used, to compare with expected strings. The other
+//! calendar widgets are drawn the same way with [`render_widget`]:
+//!
+//! ```
+//! use extra_widgets::calendar::{testing::render_month, Calendar, CalendarEventStore};
+//! use ratatui::style::{Color, Style};
+//! use time::macros::date;
+//!
+//! let red = Style::default().fg(Color::Red);
+//! let mut events = CalendarEventStore::default();
+//! events.add(date!(2023 - 04 - 12), red);
+//! let snapshot = render_month(Calendar::new(date!(2023 - 04 - 01), events), 21, 3);
+//! assert_eq!(
+//!     snapshot.lines(),
+//!     [
+//!         "                    1",
+//!         "  2  3  4  5  6  7  8",
+//!         "  9 10 11 12 13 14 15",
+//!     ]
+//! );
+//! assert_eq!(
+//!     snapshot.style_map(&[('r', red)]),
+//!     [
+//!         ".....................",
+//!         ".....................",
+//!         "..........rr.........",
+//!     ]
+//! );
+//! ```
+use ratatui::{
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::Style,
+    widgets::{StatefulWidget, Widget},
+};
+
+use super::{Calendar, CalendarState, DateStyler};
+
+/// A calendar drawn by [`render_month`] or [`render_month_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    buffer: Buffer,
+}
+
+/// Draw `calendar` in an area of `width` by `height` at the top left of an empty buffer.
+pub fn render_month<S: DateStyler>(calendar: Calendar<'_, S>, width: u16, height: u16) -> Snapshot {
+    render_widget(calendar, width, height)
+}
+
+/// Like [`render_month`], drawing the calendar with `state`.
+pub fn render_month_with<S: DateStyler>(
+    calendar: Calendar<'_, S>,
+    state: &mut CalendarState,
+    width: u16,
+    height: u16,
+) -> Snapshot {
+    render_widget_with(calendar, state, width, height)
+}
+
+/// Like [`render_month`], for any widget, e.g. a [`MonthsGrid`](super::MonthsGrid).
+pub fn render_widget<W: Widget>(widget: W, width: u16, height: u16) -> Snapshot {
+    let area = Rect::new(0, 0, width, height);
+    let mut buffer = Buffer::empty(area);
+    widget.render(area, &mut buffer);
+    Snapshot { buffer }
+}
+
+/// Like [`render_widget`], drawing the widget with `state`, e.g. an
+/// [`Agenda`](super::Agenda) with its [`AgendaState`](super::AgendaState).
+pub fn render_widget_with<W: StatefulWidget>(
+    widget: W,
+    state: &mut W::State,
+    width: u16,
+    height: u16,
+) -> Snapshot {
+    let area = Rect::new(0, 0, width, height);
+    let mut buffer = Buffer::empty(area);
+    widget.render(area, &mut buffer, state);
+    Snapshot { buffer }
+}
+
+impl Snapshot {
+    /// Get the buffer the calendar was drawn in.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Get the text of each line.
+    pub fn lines(&self) -> Vec<String> {
+        let area = self.buffer.area;
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| self.buffer.get(x, y).symbol.as_str())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Get the style of the cell at column `x` and row `y`.
+    pub fn style_at(&self, x: u16, y: u16) -> Style {
+        self.buffer.get(x, y).style()
+    }
+
+    /// Map the style of each cell to a character, giving a line of characters for each line of
+    /// the buffer. Cells are mapped to the character paired with their style in `legend`, to `.`
+    /// if they aren't styled, and to `?` if their style isn't in the legend.
+    ///
+    /// Styles are compared as they are drawn, so `Style::default().fg(Color::Red)` matches a
+    /// red cell without a background.
+    pub fn style_map(&self, legend: &[(char, Style)]) -> Vec<String> {
+        let drawn = |style| {
+            let mut cell = Cell::default();
+            cell.set_style(style);
+            cell.style()
+        };
+        let legend: Vec<_> = legend.iter().map(|(c, s)| (*c, drawn(*s))).collect();
+        let unstyled = Cell::default().style();
+        let area = self.buffer.area;
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| {
+                        let style = self.style_at(x, y);
+                        match legend.iter().find(|(_, s)| *s == style) {
+                            Some((c, _)) => *c,
+                            None if style == unstyled => '.',
+                            None => '?',
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
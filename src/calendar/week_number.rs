@@ -0,0 +1,100 @@
+use time::{Date, Duration, Weekday};
+
+use super::days_since;
+
+/// Closure numbering a week from its first day.
+type NumberFn<'a> = Box<dyn Fn(Date) -> u8 + 'a>;
+
+/// How the weeks are numbered in the column drawn by
+/// [`Calendar::show_week_numbers`](super::Calendar::show_week_numbers).
+///
+/// Each row of the calendar is a week starting on the calendar's
+/// [`week_start`](super::Calendar::week_start), which needn't be the first day of the scheme's
+/// weeks, so each scheme picks the day of the row it numbers.
+#[derive(Default)]
+pub enum WeekNumbering<'a> {
+    /// ISO 8601 weeks, which start on Monday, with week 1 containing the year's first Thursday.
+    /// A row is numbered from its Monday, so the number covers most of its days when the calendar
+    /// starts weeks on another day.
+    #[default]
+    Iso,
+    /// Week 1 is the row containing January 1st, and each row after it is the next week, as is
+    /// usual in the US. A row is numbered from its last day, so the row containing both
+    /// December 31st and January 1st is week 1.
+    StartingJan1,
+    /// Number a row from its first day, e.g. for a 4-4-5 fiscal calendar.
+    Custom(NumberFn<'a>),
+}
+
+impl<'a> WeekNumbering<'a> {
+    /// Number the weeks with `f`, which is given the first day of each row.
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(Date) -> u8 + 'a,
+    {
+        WeekNumbering::Custom(Box::new(f))
+    }
+
+    /// Get the number of the row starting on `first`, in a calendar whose weeks start on
+    /// `week_start`.
+    pub fn number(&self, first: Date, week_start: Weekday) -> u8 {
+        let day = |n: u8| first.checked_add(Duration::days(n.into())).unwrap_or(first);
+        match self {
+            WeekNumbering::Iso => day(days_since(week_start, Weekday::Monday)).iso_week(),
+            WeekNumbering::StartingJan1 => {
+                let last = day(6);
+                let jan1 = last.replace_ordinal(1).unwrap_or(last);
+                let offset = u16::from(days_since(week_start, jan1.weekday()));
+                ((last.ordinal() - 1 + offset) / 7 + 1) as u8
+            }
+            WeekNumbering::Custom(f) => f(first),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn iso() {
+        let iso = WeekNumbering::Iso;
+        // 2020 has 53 weeks, and 2021 starts on a Friday
+        assert_eq!(iso.number(date!(2020 - 12 - 28), Weekday::Monday), 53);
+        assert_eq!(iso.number(date!(2021 - 01 - 04), Weekday::Monday), 1);
+        // Sunday rows are numbered from their Monday
+        assert_eq!(iso.number(date!(2020 - 12 - 27), Weekday::Sunday), 53);
+        assert_eq!(iso.number(date!(2021 - 01 - 03), Weekday::Sunday), 1);
+        // the first Thursday of 2026 is January 1st
+        assert_eq!(iso.number(date!(2025 - 12 - 29), Weekday::Monday), 1);
+        assert_eq!(iso.number(date!(2025 - 12 - 22), Weekday::Monday), 52);
+    }
+
+    #[test]
+    fn starting_jan1() {
+        let us = WeekNumbering::StartingJan1;
+        // 2022 starts on a Saturday, so its first week is a day long
+        assert_eq!(us.number(date!(2021 - 12 - 26), Weekday::Sunday), 1);
+        assert_eq!(us.number(date!(2022 - 01 - 02), Weekday::Sunday), 2);
+        assert_eq!(us.number(date!(2022 - 12 - 25), Weekday::Sunday), 53);
+        // the row with December 31st and January 1st is week 1
+        assert_eq!(us.number(date!(2023 - 12 - 31), Weekday::Sunday), 1);
+        assert_eq!(us.number(date!(2023 - 12 - 25), Weekday::Monday), 53);
+        assert_eq!(us.number(date!(2024 - 01 - 01), Weekday::Monday), 1);
+        assert_eq!(us.number(Date::MAX, Weekday::Sunday), 53);
+    }
+
+    #[test]
+    fn custom() {
+        // a fiscal year starting on the first Monday of April
+        let fiscal = WeekNumbering::custom(|d| {
+            let start = date!(2023 - 04 - 03);
+            ((d - start).whole_weeks().rem_euclid(52) + 1) as u8
+        });
+        assert_eq!(fiscal.number(date!(2023 - 04 - 03), Weekday::Monday), 1);
+        assert_eq!(fiscal.number(date!(2024 - 03 - 25), Weekday::Monday), 52);
+        assert_eq!(fiscal.number(date!(2024 - 04 - 01), Weekday::Monday), 1);
+    }
+}
@@ -0,0 +1,172 @@
+use std::env;
+
+use time::Weekday;
+
+/// The first day of the week of a [`Calendar`](super::Calendar) or [`YearView`](super::YearView).
+///
+/// A [`Weekday`] converts into `WeekStart::Day`, so the `week_start` builders take either.
+///
+/// With [`Locale`](Self::Locale) or [`Tag`](Self::Tag), the first day is looked up from the
+/// region of a locale, in a small built-in table following the Unicode CLDR:
+///
+/// | First day | Regions |
+/// |-----------|---------|
+/// | Sunday    | AG AS BD BR BS BT BW BZ CA CO DM DO ET GT GU HK HN ID IL IN JM JP KE KH KR LA MH MM MO MT MX MZ NI NP PA PE PH PK PR PT PY SA SG SV TH TT TW UM US VE VI WS YE ZA ZW |
+/// | Saturday  | AE AF BH DJ DZ EG IQ IR JO KW LY OM QA SD SY |
+/// | Monday    | every other region |
+///
+/// A BCP-47 tag with a `fw` extension, such as `en-US-u-fw-mon`, starts on the day it names
+/// instead. Locales without a region, and ones that can't be parsed, start on Monday.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WeekStart {
+    /// Start on this day.
+    Day(Weekday),
+    /// Start on the first day of the locale of the environment, from the first of `LC_ALL`,
+    /// `LC_TIME` and `LANG` that is set, e.g. `en_US.UTF-8`.
+    Locale,
+    /// Start on the first day of the locale named by a BCP-47 tag, e.g. `en-GB`, or a POSIX
+    /// locale name, e.g. `de_DE.UTF-8`.
+    Tag(String),
+}
+
+impl From<Weekday> for WeekStart {
+    fn from(day: Weekday) -> Self {
+        WeekStart::Day(day)
+    }
+}
+
+/// Regions whose weeks start on Sunday.
+const SUNDAY: &[&str] = &[
+    "AG", "AS", "BD", "BR", "BS", "BT", "BW", "BZ", "CA", "CO", "DM", "DO", "ET", "GT", "GU", "HK",
+    "HN", "ID", "IL", "IN", "JM", "JP", "KE", "KH", "KR", "LA", "MH", "MM", "MO", "MT", "MX", "MZ",
+    "NI", "NP", "PA", "PE", "PH", "PK", "PR", "PT", "PY", "SA", "SG", "SV", "TH", "TT", "TW", "UM",
+    "US", "VE", "VI", "WS", "YE", "ZA", "ZW",
+];
+
+/// Regions whose weeks start on Saturday.
+const SATURDAY: &[&str] = &[
+    "AE", "AF", "BH", "DJ", "DZ", "EG", "IQ", "IR", "JO", "KW", "LY", "OM", "QA", "SD", "SY",
+];
+
+impl WeekStart {
+    /// Get the first day of the week. [`Locale`](Self::Locale) reads the environment each time
+    /// this is called.
+    pub fn weekday(&self) -> Weekday {
+        match self {
+            WeekStart::Day(day) => *day,
+            WeekStart::Locale => locale_start(|name| env::var(name).ok()),
+            WeekStart::Tag(tag) => tag_start(tag),
+        }
+    }
+}
+
+/// The first day of the locale from the environment, with `var` looking up variables.
+fn locale_start<F>(var: F) -> Weekday
+where
+    F: Fn(&str) -> Option<String>,
+{
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .into_iter()
+        .filter_map(var)
+        .find(|value| !value.is_empty())
+        .map_or(Weekday::Monday, |tag| tag_start(&tag))
+}
+
+/// The first day of the locale named by `tag`.
+fn tag_start(tag: &str) -> Weekday {
+    // drop the encoding and modifier of POSIX names
+    let tag = tag.split(['.', '@']).next().unwrap_or_default();
+    let mut subtags = tag.split(['-', '_']).skip(1);
+    let mut region = None;
+    while let Some(subtag) = subtags.next() {
+        if subtag.eq_ignore_ascii_case("u") {
+            // the unicode extension may name the first day
+            let mut keys = subtags
+                .by_ref()
+                .skip_while(|s| !s.eq_ignore_ascii_case("fw"));
+            if let Some(day) = keys.nth(1).and_then(weekday_key) {
+                return day;
+            }
+            break;
+        }
+        let is_region = (subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+            || (subtag.len() == 3 && subtag.chars().all(|c| c.is_ascii_digit()));
+        if region.is_none() && is_region {
+            region = Some(subtag.to_ascii_uppercase());
+        }
+    }
+    match region.as_deref() {
+        Some(r) if SUNDAY.contains(&r) => Weekday::Sunday,
+        Some(r) if SATURDAY.contains(&r) => Weekday::Saturday,
+        _ => Weekday::Monday,
+    }
+}
+
+/// The day named by the value of a `fw` key.
+fn weekday_key(key: &str) -> Option<Weekday> {
+    let day = match key.to_ascii_lowercase().as_str() {
+        "mon" => Weekday::Monday,
+        "tue" => Weekday::Tuesday,
+        "wed" => Weekday::Wednesday,
+        "thu" => Weekday::Thursday,
+        "fri" => Weekday::Friday,
+        "sat" => Weekday::Saturday,
+        "sun" => Weekday::Sunday,
+        _ => return None,
+    };
+    Some(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags() {
+        for (tag, day) in [
+            ("en-US", Weekday::Sunday),
+            ("en-GB", Weekday::Monday),
+            ("ar-EG", Weekday::Saturday),
+            ("zh-Hant-TW", Weekday::Sunday),
+            ("es-419", Weekday::Monday),
+            ("en_US.UTF-8", Weekday::Sunday),
+            ("de_DE@euro", Weekday::Monday),
+            ("pt_br", Weekday::Sunday),
+            ("en-US-u-fw-mon", Weekday::Monday),
+            ("en-GB-u-ca-gregory-fw-sun", Weekday::Sunday),
+            ("en-GB-u-fw-xyz", Weekday::Monday),
+            ("fr", Weekday::Monday),
+            ("C", Weekday::Monday),
+            ("", Weekday::Monday),
+        ] {
+            assert_eq!(tag_start(tag), day, "{tag}");
+            assert_eq!(WeekStart::Tag(tag.into()).weekday(), day, "{tag}");
+        }
+        assert_eq!(WeekStart::from(Weekday::Friday).weekday(), Weekday::Friday);
+    }
+
+    #[test]
+    fn environment() {
+        let env = |vars: &'static [(&str, &str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(locale_start(env(&[])), Weekday::Monday);
+        assert_eq!(
+            locale_start(env(&[("LANG", "en_US.UTF-8")])),
+            Weekday::Sunday
+        );
+        assert_eq!(
+            locale_start(env(&[("LANG", "en_US.UTF-8"), ("LC_TIME", "en_GB.UTF-8")])),
+            Weekday::Monday
+        );
+        // empty variables are skipped
+        assert_eq!(
+            locale_start(env(&[("LC_ALL", ""), ("LANG", "ar_SY")])),
+            Weekday::Saturday
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, StatefulWidget, Widget},
+};
+use time::{Date, Month, Weekday};
+
+use super::{days_since, CalendarState, DateStyler, WeekStart};
+
+/// The size of a month, including the space for its outline.
+const MONTH_WIDTH: u16 = 9;
+const MONTH_HEIGHT: u16 = 9;
+
+/// A year at a glance: twelve small months where each day is a single cell.
+///
+/// The days are too small for numbers, so each is drawn as the `symbol`, styled with the
+/// `default_style` patched with the style from the [`DateStyler`]. This makes the view a good fit
+/// for showing where events fall over the year, or a heatmap.
+///
+/// Months are laid out in up to [`columns`](Self::columns) columns, with fewer used when the area
+/// is too narrow. Months that don't fit in the height are skipped. Rendered with a
+/// [`CalendarState`], the year containing the focused date is shown, the focused month is outlined
+/// and the focused day is highlighted.
+///
+/// Use [`date_at`](Self::date_at) to find the date drawn in a cell, e.g. for mouse clicks.
+pub struct YearView<'a, S: DateStyler> {
+    year: i32,
+    events: S,
+    default_style: Style,
+    focused_style: Style,
+    outline_style: Style,
+    header_style: Style,
+    symbol: &'a str,
+    columns: u8,
+    week_start: Weekday,
+    block: Option<Block<'a>>,
+}
+
+impl<'a, S: DateStyler> YearView<'a, S> {
+    /// Construct a view of `year`, styling the days with `events`.
+    pub fn new(year: i32, events: S) -> Self {
+        Self {
+            year,
+            events,
+            default_style: Style::default(),
+            focused_style: Style::default(),
+            outline_style: Style::default(),
+            header_style: Style::default(),
+            symbol: "·",
+            columns: 4,
+            week_start: Weekday::Sunday,
+            block: None,
+        }
+    }
+
+    /// How to render otherwise unstyled days.
+    pub fn default_style(mut self, s: Style) -> Self {
+        self.default_style = s;
+        self
+    }
+
+    /// The style patched onto the focused day when rendering with a [`CalendarState`].
+    pub fn focused_style(mut self, s: Style) -> Self {
+        self.focused_style = s;
+        self
+    }
+
+    /// The style of the outline drawn around the focused month.
+    pub fn outline_style(mut self, s: Style) -> Self {
+        self.outline_style = s;
+        self
+    }
+
+    /// The style of the month names.
+    pub fn header_style(mut self, s: Style) -> Self {
+        self.header_style = s;
+        self
+    }
+
+    /// The symbol drawn for each day. Defaults to `·`.
+    pub fn symbol(mut self, symbol: &'a str) -> Self {
+        self.symbol = symbol;
+        self
+    }
+
+    /// The most months in each row. Defaults to 4.
+    pub fn columns(mut self, n: u8) -> Self {
+        self.columns = n;
+        self
+    }
+
+    /// The first day of each week (the leftmost column). Defaults to Sunday. Takes a [`Weekday`]
+    /// or a [`WeekStart`], as [`Calendar::week_start`](super::Calendar::week_start) does.
+    pub fn week_start<W: Into<WeekStart>>(mut self, start: W) -> Self {
+        self.week_start = start.into().weekday();
+        self
+    }
+
+    /// Render the view within a [Block](ratatui::widgets::Block)
+    pub fn block(mut self, b: Block<'a>) -> Self {
+        self.block = Some(b);
+        self
+    }
+
+    /// Get the date drawn at column `x` and row `y` of the screen, when the view is rendered in
+    /// `area`. Returns `None` if there is no day at that position.
+    ///
+    /// When rendering with a [`CalendarState`], the year displayed is that of the focused date, so
+    /// set the view's year to match before calling this.
+    pub fn date_at(&self, area: Rect, x: u16, y: u16) -> Option<Date> {
+        let area = self.inner(area);
+        (0..12).find_map(|i| {
+            let month = self.month_area(area, i)?;
+            let (col, row) = (x.checked_sub(month.x + 1)?, y.checked_sub(month.y + 2)?);
+            if col >= 7 || row >= 6 {
+                return None;
+            }
+            let first =
+                Date::from_calendar_date(self.year, Month::try_from(i + 1).ok()?, 1).ok()?;
+            let day = (row * 7 + col) as i32 - days_since(self.week_start, first.weekday()) as i32;
+            let len = first.month().length(self.year);
+            (0..len as i32)
+                .contains(&day)
+                .then(|| first.replace_day(day as u8 + 1).ok())?
+        })
+    }
+
+    /// The area inside the block.
+    fn inner(&self, area: Rect) -> Rect {
+        match &self.block {
+            None => area,
+            Some(b) => b.inner(area),
+        }
+    }
+
+    /// The area of month `i` (0 for January), including its outline, if it fits in `area`.
+    fn month_area(&self, area: Rect, i: u8) -> Option<Rect> {
+        let columns = std::cmp::min(self.columns.into(), area.width / MONTH_WIDTH);
+        if columns == 0 {
+            return None;
+        }
+        let i = u16::from(i);
+        let x = (i % columns) * MONTH_WIDTH;
+        let y = (i / columns) * MONTH_HEIGHT;
+        (y + MONTH_HEIGHT <= area.height)
+            .then(|| Rect::new(area.x + x, area.y + y, MONTH_WIDTH, MONTH_HEIGHT))
+    }
+
+    fn render_year(mut self, area: Rect, buf: &mut Buffer, state: Option<&CalendarState>) {
+        let area = match self.block.take() {
+            None => area,
+            Some(b) => {
+                let inner = b.inner(area);
+                b.render(area, buf);
+                inner
+            }
+        };
+
+        for i in 0..12 {
+            let (Some(rect), Ok(month)) = (self.month_area(area, i), Month::try_from(i + 1)) else {
+                continue;
+            };
+            let Ok(first) = Date::from_calendar_date(self.year, month, 1) else {
+                continue;
+            };
+            if state.is_some_and(|s| s.focused.year() == self.year && s.focused.month() == month) {
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(self.outline_style)
+                    .render(rect, buf);
+            }
+
+            let name = &month.to_string()[..3];
+            buf.set_string(rect.x + 3, rect.y + 1, name, self.header_style);
+
+            let offset = days_since(self.week_start, first.weekday());
+            for day in 1..=month.length(self.year) {
+                let Ok(date) = first.replace_day(day) else {
+                    continue;
+                };
+                let n = u16::from(offset + day - 1);
+                let mut style = self.default_style.patch(self.events.get_style(date));
+                if state.is_some_and(|s| s.focused == date) {
+                    style = style.patch(self.focused_style);
+                }
+                buf.set_string(rect.x + 1 + n % 7, rect.y + 2 + n / 7, self.symbol, style);
+            }
+        }
+    }
+}
+
+impl<'a, S: DateStyler> Widget for YearView<'a, S> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.render_year(area, buf, None);
+    }
+}
+
+impl<'a, S: DateStyler> StatefulWidget for YearView<'a, S> {
+    type State = CalendarState;
+
+    fn render(mut self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        self.year = state.focused.year();
+        self.render_year(area, buf, Some(state));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+    use time::macros::date;
+
+    use super::*;
+    use crate::calendar::CalendarEventStore;
+
+    #[test]
+    fn layout() {
+        let red = Style::default().fg(Color::Red);
+        let mut events = CalendarEventStore::default();
+        events.add(date!(2023 - 02 - 01), red);
+        let view = || YearView::new(2023, &events);
+
+        // 4 columns, and room for 2 rows
+        let area = Rect::new(0, 0, 40, 20);
+        let mut buf = Buffer::empty(area);
+        let mut state = CalendarState::new(date!(2023 - 02 - 14));
+        StatefulWidget::render(view(), area, &mut buf, &mut state);
+
+        assert_eq!(buf.get(12, 1).symbol, "F");
+        assert_eq!(buf.get(1, 2).symbol, "·");
+        // February is outlined, and starts on a Wednesday
+        assert_eq!(buf.get(9, 0).symbol, "┌");
+        assert_eq!(buf.get(0, 0).symbol, " ");
+        assert_eq!(buf.get(13, 2).fg, Color::Red);
+        // September doesn't fit
+        assert_eq!(buf.get(1, 20 - 1).symbol, " ");
+
+        assert_eq!(view().date_at(area, 1, 2), Some(date!(2023 - 01 - 01)));
+        assert_eq!(view().date_at(area, 13, 4), Some(date!(2023 - 02 - 15)));
+        assert_eq!(view().date_at(area, 12, 2), None);
+        assert_eq!(view().date_at(area, 1, 18), None);
+
+        // narrow areas use fewer columns
+        let narrow = Rect::new(0, 0, 20, 20);
+        assert_eq!(view().date_at(narrow, 4, 11), Some(date!(2023 - 03 - 01)));
+        let outline = Block::default().borders(Borders::ALL);
+        let view = view().block(outline);
+        assert_eq!(view.date_at(narrow, 2, 3), Some(date!(2023 - 01 - 01)));
+    }
+}
@@ -23,6 +23,11 @@
 //!
 //! Macros (e.g. `bold!(...)`) are gated by the `text_macros` feature.
 //!
+//! The calendar uses dates from the `time` crate. Enabling the `chrono` feature adds conversions
+//! and methods for `chrono::NaiveDate`, and the `crossterm` feature adds key handling to the
+//! calendar's date picker. The `testing` feature adds [`calendar::testing`], with helpers for
+//! checking how a calendar is drawn in an app's tests.
+//!
 //! ### Serde support
 //!
 //! State structs can be serialized with Serde by enabling the `serde` feature. This can be useful
@@ -30,6 +35,9 @@
 //!
 //! Serializeable states:
 //!   * [styled_list::ListState]
+//!   * [styled_list::TreeState]
+//!   * [calendar::CalendarState]
+//!   * [calendar::CalendarEvents]
 //!
 #[cfg(feature = "calendar")]
 pub mod calendar;
@@ -39,3 +47,6 @@ pub mod styled_list;
 
 #[cfg(feature = "text_macros")]
 pub mod text_macros;
+
+#[cfg(any(feature = "styled_list", feature = "text_macros"))]
+mod truncate;
@@ -0,0 +1,113 @@
+use super::{ListItem, ListState, StyledList};
+
+/// A [`ListItem`] carrying a payload, e.g. the record the item displays.
+///
+/// Lists of `DataItem`s are built with [`StyledList::with_data`], which keeps each payload with
+/// its item so the payload for the selection can be looked up without a parallel `Vec`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataItem<'a, T> {
+    pub item: ListItem<'a>,
+    pub data: T,
+}
+
+impl<'a> ListItem<'a> {
+    /// Attach a payload to this item.
+    pub fn with_data<T>(self, data: T) -> DataItem<'a, T> {
+        DataItem { item: self, data }
+    }
+}
+
+/// The items of a [`StyledList`] built with [`StyledList::with_data`]. Iterating this yields the
+/// [`ListItem`]s for display, dropping the payloads, and iterating a reference to it yields
+/// references to them (e.g. for [`ListState::select_by_mnemonic`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataItems<'a, T>(Vec<DataItem<'a, T>>);
+
+impl<'a, T> DataItems<'a, T> {
+    /// Get the payload of the item at index `idx`.
+    pub fn data(&self, idx: usize) -> Option<&T> {
+        self.0.get(idx).map(|d| &d.data)
+    }
+}
+
+impl<'a, T> FromIterator<DataItem<'a, T>> for DataItems<'a, T> {
+    fn from_iter<D: IntoIterator<Item = DataItem<'a, T>>>(iter: D) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<'a, T> IntoIterator for DataItems<'a, T> {
+    type Item = ListItem<'a>;
+    type IntoIter =
+        std::iter::Map<std::vec::IntoIter<DataItem<'a, T>>, fn(DataItem<'a, T>) -> ListItem<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter().map(|d| d.item)
+    }
+}
+
+impl<'b, 'a, T> IntoIterator for &'b DataItems<'a, T> {
+    type Item = &'b ListItem<'a>;
+    type IntoIter = std::iter::Map<
+        std::slice::Iter<'b, DataItem<'a, T>>,
+        fn(&'b DataItem<'a, T>) -> &'b ListItem<'a>,
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().map(|d| &d.item)
+    }
+}
+
+impl<'a, T> StyledList<'a, DataItems<'a, T>> {
+    /// Create a list from items carrying payloads. The list renders exactly as one created with
+    /// [`StyledList::new`] from the same [`ListItem`]s.
+    pub fn with_data<D>(items: D) -> Self
+    where
+        D: IntoIterator<Item = DataItem<'a, T>>,
+    {
+        Self::new(items.into_iter().collect())
+    }
+
+    /// Get the payload of the item at index `idx`.
+    pub fn data(&self, idx: usize) -> Option<&T> {
+        self.items.data(idx)
+    }
+
+    /// Get the payload of the item selected in `state`.
+    pub fn selected_data(&self, state: &ListState) -> Option<&T> {
+        self.data(state.selected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+
+    use super::*;
+
+    #[test]
+    fn payloads() {
+        let records = [("a", 10), ("b", 20), ("c", 30)];
+        let list = || {
+            StyledList::with_data(
+                records
+                    .iter()
+                    .map(|(name, size)| ListItem::new(*name).with_data(*size)),
+            )
+        };
+        let mut state = ListState::new(3);
+        state.select(1);
+        assert_eq!(list().selected_data(&state), Some(&20));
+        assert_eq!(list().data(2), Some(&30));
+        assert_eq!(list().data(3), None);
+
+        // renders the same as the plain items
+        let area = Rect::new(0, 0, 2, 3);
+        let mut plain = Buffer::empty(area);
+        let items = records.iter().map(|(name, _)| ListItem::new(*name));
+        StyledList::new(items).render(area, &mut plain, &mut state);
+        let mut with_data = Buffer::empty(area);
+        list().render(area, &mut with_data, &mut state);
+        assert_eq!(plain, with_data);
+    }
+}
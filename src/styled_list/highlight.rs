@@ -0,0 +1,186 @@
+use std::{borrow::Cow, ops::Range};
+
+use ratatui::{
+    style::Style,
+    text::{Span, Spans},
+};
+
+/// Highlight every occurence of `needle` in the line by patching `style` into the matching text.
+///
+/// Matches may cross span boundaries, in which case each span gets highlighted for the part of the
+/// match it contains. Spans are split at match boundaries, and keep their own style outside of
+/// the match. Unless `case_sensitive` is set, the needle is matched case-insensitively.
+pub(super) fn highlight<'a>(
+    line: Spans<'a>,
+    needle: &str,
+    style: Style,
+    case_sensitive: bool,
+) -> Spans<'a> {
+    if needle.is_empty() {
+        return line;
+    }
+
+    let haystack: String = line.0.iter().map(|s| s.content.as_ref()).collect();
+    let matches = find_matches(&haystack, needle, case_sensitive);
+    if matches.is_empty() {
+        return line;
+    }
+
+    let mut res = Vec::with_capacity(line.0.len() + matches.len() * 2);
+    let mut offset = 0;
+    let mut matches = matches.into_iter().peekable();
+    for span in line.0 {
+        let len = span.content.len();
+        let span_range = offset..offset + len;
+        let mut pos = 0;
+
+        while let Some(m) = matches.peek() {
+            if m.start >= span_range.end {
+                break;
+            }
+            let start = m.start.max(span_range.start) - offset;
+            let end = m.end.min(span_range.end) - offset;
+            if start > pos {
+                res.push(Span::styled(slice(&span.content, pos..start), span.style));
+            }
+            res.push(Span::styled(
+                slice(&span.content, start..end),
+                span.style.patch(style),
+            ));
+            pos = end;
+            // a match running past this span continues in the next one
+            if m.end > span_range.end {
+                break;
+            }
+            matches.next();
+        }
+
+        if pos == 0 {
+            res.push(span);
+        } else if pos < len {
+            res.push(Span::styled(slice(&span.content, pos..len), span.style));
+        }
+        offset += len;
+    }
+    Spans(res)
+}
+
+/// Get part of a span's content, borrowing when the content is borrowed.
+fn slice<'a>(content: &Cow<'a, str>, range: Range<usize>) -> Cow<'a, str> {
+    match content {
+        Cow::Borrowed(s) => Cow::Borrowed(&s[range]),
+        Cow::Owned(s) => Cow::Owned(s[range].to_string()),
+    }
+}
+
+/// Find the byte ranges of non-overlapping occurences of `needle` in `haystack`.
+fn find_matches(haystack: &str, needle: &str, case_sensitive: bool) -> Vec<Range<usize>> {
+    if case_sensitive {
+        return haystack
+            .match_indices(needle)
+            .map(|(i, m)| i..i + m.len())
+            .collect();
+    }
+
+    // Lowercasing a char can produce several chars, so match over the folded chars, each
+    // remembering the byte range of the char it came from.
+    let folded: Vec<(char, Range<usize>)> = haystack
+        .char_indices()
+        .flat_map(|(i, c)| {
+            let range = i..i + c.len_utf8();
+            c.to_lowercase().map(move |l| (l, range.clone()))
+        })
+        .collect();
+    let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+
+    let mut res = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= folded.len() {
+        let candidate = &folded[i..i + needle.len()];
+        if candidate.iter().map(|(c, _)| *c).eq(needle.iter().copied()) {
+            let start = candidate[0].1.start;
+            let end = candidate[candidate.len() - 1].1.end;
+            // a match must start and end on whole chars of the haystack
+            let end_idx = i + needle.len();
+            if (i == 0 || folded[i - 1].1 != folded[i].1)
+                && (end_idx == folded.len() || folded[end_idx].1 != folded[end_idx - 1].1)
+            {
+                res.push(start..end);
+                i = end_idx;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Modifier};
+
+    use super::*;
+
+    fn hl() -> Style {
+        Style::default().bg(Color::Yellow)
+    }
+
+    #[test]
+    fn single_span() {
+        let res = highlight(Spans::from("foo bar foo"), "foo", hl(), true);
+        assert_eq!(
+            res,
+            Spans(vec![
+                Span::styled("foo", hl()),
+                Span::raw(" bar "),
+                Span::styled("foo", hl()),
+            ])
+        );
+    }
+
+    #[test]
+    fn no_match() {
+        let line = Spans::from("foo bar");
+        assert_eq!(highlight(line.clone(), "baz", hl(), false), line);
+        assert_eq!(highlight(line.clone(), "", hl(), false), line);
+        assert_eq!(highlight(line.clone(), "FOO", hl(), true), line);
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let res = highlight(Spans::from("Foo FOO"), "fOo", hl(), false);
+        assert_eq!(
+            res,
+            Spans(vec![
+                Span::styled("Foo", hl()),
+                Span::raw(" "),
+                Span::styled("FOO", hl()),
+            ])
+        );
+
+        // folding changes byte lengths here, the match must still map back correctly
+        let res = highlight(Spans::from("xİy"), "i̇y", hl(), false);
+        assert_eq!(res, Spans(vec![Span::raw("x"), Span::styled("İy", hl())]));
+    }
+
+    #[test]
+    fn across_spans() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let line = Spans(vec![
+            Span::raw("ab"),
+            Span::styled("cd", bold),
+            Span::raw("ef"),
+        ]);
+        let res = highlight(line, "bcde", hl(), true);
+        assert_eq!(
+            res,
+            Spans(vec![
+                Span::raw("a"),
+                Span::styled("b", hl()),
+                Span::styled("cd", bold.patch(hl())),
+                Span::styled("e", hl()),
+                Span::raw("f"),
+            ])
+        );
+    }
+}
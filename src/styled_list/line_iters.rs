@@ -1,8 +1,11 @@
 use std::iter::Enumerate;
 
-use ratatui::{style::Style, text::Spans};
+use ratatui::{
+    style::Style,
+    text::{Span, Spans, Text},
+};
 
-use super::{DisplayLine, LineIndicators, ListItem, Separator};
+use super::{list_item::underline_first, DisplayLine, LineIndicators, ListItem, Separator};
 
 /// A struct for iterating through display lines given an item and a selection state
 pub(super) struct ToLines<'a> {
@@ -12,12 +15,20 @@ pub(super) struct ToLines<'a> {
     indicators: LineIndicators,
     selected: bool,
     line_count: usize,
+    index: Option<usize>,
+    badges: Vec<Span<'a>>,
+    expansion: std::vec::IntoIter<Spans<'a>>,
+    expansion_style: Style,
 }
 
 impl<'a> ToLines<'a> {
     pub(super) fn new(item: ListItem<'a>, selected: bool) -> Self {
-        let line_count = item.height();
-        let text_items = item.content.lines.into_iter().enumerate();
+        let line_count = item.height() as usize;
+        let mut content = item.content;
+        if let Some(key) = item.mnemonic {
+            underline_first(&mut content, key);
+        }
+        let text_items = content.lines.into_iter().enumerate();
 
         //let text_items = VecDeque::from_iter(text_items);
         Self {
@@ -26,9 +37,27 @@ impl<'a> ToLines<'a> {
             indicators: item.indicators,
             selected,
             line_count,
+            index: None,
+            badges: item.badges,
+            expansion: Vec::new().into_iter(),
+            expansion_style: Style::default(),
         }
     }
 
+    /// Set the index of the item in the list, which is carried by the DisplayLines produced.
+    pub(super) fn index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Add lines after the item's own lines, drawn with `style` patched over the item style.
+    /// These lines are part of the item, so they carry its selection state and index.
+    pub(super) fn expand(mut self, text: Text<'a>, style: Style) -> Self {
+        self.expansion = text.lines.into_iter();
+        self.expansion_style = self.style.patch(style);
+        self
+    }
+
     pub(super) fn empty_with_selection(selected: bool) -> Self {
         Self {
             style: Style::default(),
@@ -36,6 +65,10 @@ impl<'a> ToLines<'a> {
             selected,
             indicators: LineIndicators::default(),
             line_count: 0,
+            index: None,
+            badges: Vec::new(),
+            expansion: Vec::new().into_iter(),
+            expansion_style: Style::default(),
         }
     }
 }
@@ -43,13 +76,33 @@ impl<'a> ToLines<'a> {
 impl<'a> Iterator for ToLines<'a> {
     type Item = DisplayLine<'a>;
     fn next(&mut self) -> Option<Self::Item> {
-        let (i, line) = self.text_items.next()?;
+        let (i, line) = match self.text_items.next() {
+            Some(l) => l,
+            None => {
+                let line = self.expansion.next()?;
+                return Some(DisplayLine {
+                    style: self.expansion_style,
+                    line,
+                    must_display: self.selected,
+                    left_indicator: " ",
+                    right_indicator: " ",
+                    item: self.index,
+                    badges: Vec::new(),
+                });
+            }
+        };
         let res = DisplayLine {
             style: self.style,
             line,
             must_display: self.selected,
-            left_indicator: self.indicators.left.fill_char(i, self.line_count).into(),
-            right_indicator: self.indicators.right.fill_char(i, self.line_count).into(),
+            left_indicator: self.indicators.left.fill_char(i, self.line_count),
+            right_indicator: self.indicators.right.fill_char(i, self.line_count),
+            item: self.index,
+            badges: if i == 0 {
+                std::mem::take(&mut self.badges)
+            } else {
+                Vec::new()
+            },
         };
         Some(res)
     }
@@ -168,6 +221,35 @@ mod test {
         }
     }
 
+    #[test]
+    fn to_lines_badges() {
+        let it = ListItem::new("a\nb")
+            .badge(Span::raw("1"))
+            .badge(Span::raw("M"));
+        let lines: Vec<_> = ToLines::new(it, false).collect();
+        assert_eq!(lines[0].badges, vec![Span::raw("1"), Span::raw("M")]);
+        assert!(lines[1].badges.is_empty());
+    }
+
+    #[test]
+    fn to_lines_expanded() {
+        let style = Style::default().fg(Color::Red);
+        let estyle = Style::default().bg(Color::Blue);
+        let it = ListItem::new("a").style(style);
+        let lines: Vec<_> = ToLines::new(it, true)
+            .index(3)
+            .expand(Text::from("x\ny"), estyle)
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].style, style);
+        for (dl, t) in lines[1..].iter().zip(["x", "y"]) {
+            assert_eq!(dl.line, Spans::from(t));
+            assert_eq!(dl.style, style.patch(estyle));
+            assert!(dl.must_display);
+            assert_eq!(dl.item, Some(3));
+        }
+    }
+
     #[test]
     fn to_lines_selected() {
         let item = ListItem::new("a\nb");
@@ -1,4 +1,10 @@
-use ratatui::{style::Style, text::Text};
+use std::borrow::Cow;
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Span, Text},
+};
+use unicode_segmentation::UnicodeSegmentation;
 
 /// An Item in the list
 #[derive(Debug, Clone, PartialEq)]
@@ -6,6 +12,8 @@ pub struct ListItem<'a> {
     pub(super) content: Text<'a>,
     pub(super) style: Style,
     pub(super) indicators: LineIndicators,
+    pub(super) badges: Vec<Span<'a>>,
+    pub(super) mnemonic: Option<char>,
 }
 
 impl<'a> ListItem<'a> {
@@ -17,6 +25,8 @@ impl<'a> ListItem<'a> {
             content: content.into(),
             style: Style::default(),
             indicators: LineIndicators::default(),
+            badges: Vec::new(),
+            mnemonic: None,
         }
     }
 
@@ -28,8 +38,40 @@ impl<'a> ListItem<'a> {
     }
 
     /// How many rows this item will take on display
-    pub fn height(&self) -> usize {
-        self.content.height()
+    pub fn height(&self) -> u16 {
+        self.content.height() as u16
+    }
+
+    /// How many columns the widest line of this item takes on display. This is the width the
+    /// item text needs to be drawn without truncation, and does not include indicator columns or
+    /// [badges](Self::badge).
+    pub fn width(&self) -> u16 {
+        self.content.width() as u16
+    }
+
+    /// Add a badge to this item. Badges are drawn right aligned on the first line of the item,
+    /// in the order they are added. When the row is too narrow, the item text is truncated first,
+    /// and badges are dropped (starting with the first added) once the text would be narrower
+    /// than the list's [`min_content_width`](super::StyledList::min_content_width).
+    pub fn badge(mut self, badge: Span<'a>) -> Self {
+        self.badges.push(badge);
+        self
+    }
+
+    /// Set the accelerator key for this item (see [`ListState::select_by_mnemonic`]). The first
+    /// occurrence of the key in the item text is underlined when drawn. Keys are matched ignoring
+    /// case. Setting the mnemonic again replaces it.
+    ///
+    /// [`ListState::select_by_mnemonic`]: super::ListState::select_by_mnemonic
+    pub fn mnemonic(mut self, key: char) -> Self {
+        self.mnemonic = Some(key);
+        self
+    }
+
+    /// Does this item have `key` as its [mnemonic](Self::mnemonic)?
+    pub fn has_mnemonic(&self, key: char) -> bool {
+        self.mnemonic
+            .is_some_and(|m| m.to_lowercase().eq(key.to_lowercase()))
     }
 
     /// set the indicators for this item. These will be replaced with the lists's
@@ -40,6 +82,40 @@ impl<'a> ListItem<'a> {
     }
 }
 
+/// Underline the first grapheme in `text` starting with `key` (ignoring case), splitting the span
+/// it is in so that the rest of the span keeps its style.
+pub(super) fn underline_first(text: &mut Text, key: char) {
+    let key: Vec<char> = key.to_lowercase().collect();
+    for line in text.lines.iter_mut() {
+        for (i, span) in line.0.iter().enumerate() {
+            let found = span.content.grapheme_indices(true).find(|(_, g)| {
+                g.chars()
+                    .next()
+                    .is_some_and(|c| c.to_lowercase().eq(key.iter().copied()))
+            });
+            if let Some((start, g)) = found {
+                let end = start + g.len();
+                let span = line.0.remove(i);
+                let part = |r: std::ops::Range<usize>| match &span.content {
+                    Cow::Borrowed(s) => Cow::Borrowed(&s[r]),
+                    Cow::Owned(s) => Cow::Owned(s[r].to_string()),
+                };
+                let parts = [
+                    Span::styled(part(0..start), span.style),
+                    Span::styled(
+                        part(start..end),
+                        span.style.add_modifier(Modifier::UNDERLINED),
+                    ),
+                    Span::styled(part(end..span.content.len()), span.style),
+                ];
+                let parts = parts.into_iter().filter(|s| !s.content.is_empty());
+                line.0.splice(i..i, parts);
+                return;
+            }
+        }
+    }
+}
+
 /// Container for holding the [Indicator]s for the left and right indicator columns
 #[derive(Copy, Clone, Default, PartialEq, Debug, Eq)]
 pub struct LineIndicators {
@@ -114,3 +190,58 @@ impl Default for Indicator {
         Indicator::Char(" ")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{style::Color, text::Spans};
+
+    use super::*;
+    use crate::styled_list::line_iters::ToLines;
+
+    /// The item's lines as drawn.
+    fn drawn(item: ListItem) -> Vec<Spans> {
+        ToLines::new(item, false).map(|l| l.line).collect()
+    }
+
+    #[test]
+    fn mnemonic_underline() {
+        let red = Style::default().fg(Color::Red);
+        let ul = |s: Style| s.add_modifier(Modifier::UNDERLINED);
+        let item = ListItem::new(Spans::from(vec![
+            Span::raw("Open "),
+            Span::styled("fi\u{301}le", red),
+        ]))
+        .mnemonic('I');
+        assert!(item.has_mnemonic('i'));
+        assert!(!item.has_mnemonic('o'));
+        assert_eq!(
+            drawn(item),
+            vec![Spans::from(vec![
+                Span::raw("Open "),
+                Span::styled("f", red),
+                Span::styled("i\u{301}", ul(red)),
+                Span::styled("le", red),
+            ])]
+        );
+
+        // a key missing from the text leaves it unchanged
+        let item = ListItem::new("Quit").mnemonic('x');
+        assert_eq!(drawn(item), vec![Spans::from("Quit")]);
+    }
+
+    #[test]
+    fn mnemonic_replaced() {
+        let ul = Style::default().add_modifier(Modifier::UNDERLINED);
+        let item = ListItem::new("Save as").mnemonic('s').mnemonic('a');
+        assert!(item.has_mnemonic('a'));
+        assert!(!item.has_mnemonic('s'));
+        assert_eq!(
+            drawn(item),
+            vec![Spans::from(vec![
+                Span::raw("S"),
+                Span::styled("a", ul),
+                Span::raw("ve as"),
+            ])]
+        );
+    }
+}
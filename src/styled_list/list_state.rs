@@ -1,13 +1,38 @@
-use std::cmp::min;
+use std::{cmp::min, collections::BTreeSet};
+
+use super::{window_type::WindowBuffer, ListItem, StyledList};
 
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
 
+/// Something that happened to a [`ListState`]. Events are recorded as the state is mutated (or
+/// rendered), and accumulate until they are collected with [`ListState::drain_events`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListEvent {
+    /// The selection moved from one item index to another.
+    SelectionMoved { from: usize, to: usize },
+    /// The item at this index was activated with [`ListState::activate`].
+    Activated(usize),
+    /// Rendering moved the top of the display window to this line.
+    ScrolledTo(usize),
+    /// The set of marked items changed.
+    MarksChanged,
+}
+
 /// State for a [`StyledList`](super::StyledList)
 ///
 /// This state tracks the selected item in a list, and provides methods for cycling the list.
 /// The size of the list is the number of [`ListItem`](super::ListItem)s to cycle through.
 ///
+/// In addition to the selection (the cursor), the state tracks a set of marked items. Marked
+/// items are rendered with the list's
+/// [`marked_style`](super::StyledList::marked_style). Marks can be toggled individually, or
+/// an anchor can be set so that moving the cursor marks the contiguous range between the anchor
+/// and the cursor (like shift-selection in a file manager).
+///
+/// Changes to the state are recorded as [`ListEvent`]s, so an app can react to what happened
+/// rather than comparing the state every frame. The event queue is not serialized.
+///
 /// panics if created or resized to have a size of 0
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
@@ -15,6 +40,17 @@ pub struct ListState {
     pub(super) size: usize,
     pub(super) selected: usize,
     pub(super) window_first: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(super) marked: BTreeSet<usize>,
+    pub(super) anchor: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) events: Vec<ListEvent>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(super) loading: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) needs_more: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) window: WindowBuffer,
 }
 
 impl ListState {
@@ -24,45 +60,44 @@ impl ListState {
             size: 1,
             selected: 0,
             window_first: 0,
+            marked: BTreeSet::new(),
+            anchor: None,
+            events: Vec::new(),
+            loading: false,
+            needs_more: false,
+            window: WindowBuffer::default(),
         };
         res.resize(size);
+        res.events.clear();
         res
     }
 
-    /// Set the position of the first DisplayLine of the selection.
-    pub(super) fn set_pos(&mut self, pos: usize) {
-        self.window_first = pos;
-    }
-
     /// Select the next [ListItem](super::ListItem) without wrapping
     pub fn next(&mut self) {
-        self.selected = min(self.selected + 1, self.size - 1)
+        self.move_to(min(self.selected + 1, self.size - 1));
     }
 
     /// Select the previous [ListItem](super::ListItem) without wrapping
     pub fn prev(&mut self) {
-        self.selected = self.selected.saturating_sub(1);
+        self.move_to(self.selected.saturating_sub(1));
     }
 
     /// Select the next item in the list. If the current item is the last [ListItem`(super::ListItem), it will
     /// move the selection to the first [ListItem](super::ListItem)
     pub fn cycle_next(&mut self) {
-        self.selected = (self.selected + 1) % self.size;
+        self.move_to((self.selected + 1) % self.size);
     }
 
     /// Select the previous item in the list. If the current item is the first [ListItem](super::ListItem), it will
     /// move the selection to the last [ListItem](super::ListItem)
     pub fn cycle_prev(&mut self) {
-        self.selected = (self.selected + self.size - 1) % self.size;
+        self.move_to((self.selected + self.size - 1) % self.size);
     }
 
     /// Specify which [ListItem](super::ListItem) is selected. If the selection is beyond the end of the list, the
     /// last item will be selected.
     pub fn select(&mut self, n: usize) {
-        self.selected = n;
-        if self.selected >= self.size {
-            self.selected = self.size.saturating_sub(1);
-        }
+        self.move_to(min(n, self.size - 1));
     }
 
     /// Get the index of the selected [ListItem](super::ListItem)
@@ -76,8 +111,162 @@ impl ListState {
             panic!("ListState has invalid size: 0");
         }
         self.size = size;
-        if self.selected >= self.size {
-            self.selected = self.size.saturating_sub(1);
+        // drop any marks beyond the end of the list
+        if !self.marked.split_off(&size).is_empty() {
+            self.events.push(ListEvent::MarksChanged);
+        }
+        if let Some(anchor) = self.anchor {
+            self.anchor = Some(min(anchor, size - 1));
+        }
+        self.move_to(min(self.selected, size - 1));
+    }
+
+    /// Select the next item with `key` as its [mnemonic](super::ListItem::mnemonic), after the
+    /// current selection and wrapping around to the start of the list. Pressing the same key
+    /// again cycles between items sharing the mnemonic. Returns the index of the selected item,
+    /// or `None` (leaving the selection as is) if no item has the mnemonic.
+    pub fn select_by_mnemonic<'a, I>(
+        &mut self,
+        key: char,
+        list: &StyledList<'a, I>,
+    ) -> Option<usize>
+    where
+        I: IntoIterator<Item = ListItem<'a>>,
+        for<'b> &'b I: IntoIterator<Item = &'b ListItem<'a>>,
+    {
+        let mut first = None;
+        let mut after = None;
+        for (i, item) in (&list.items).into_iter().enumerate() {
+            if i >= self.size || !item.has_mnemonic(key) {
+                continue;
+            }
+            first = first.or(Some(i));
+            if i > self.selected {
+                after = Some(i);
+                break;
+            }
+        }
+        let n = after.or(first)?;
+        self.move_to(n);
+        Some(n)
+    }
+
+    /// Activate the selected [ListItem](super::ListItem) (e.g. when the user presses enter).
+    /// This records a [`ListEvent::Activated`] for the selection.
+    pub fn activate(&mut self) {
+        self.events.push(ListEvent::Activated(self.selected));
+    }
+
+    /// Mark the list as loading more items. While loading, a list configured with a
+    /// [`loading_row`](super::StyledList::loading_row) displays that row after the last item.
+    pub fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
+    }
+
+    /// Is the list loading more items?
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Did the last render find the end of the list close to the display window? This is
+    /// updated on each render of a list configured with
+    /// [`on_near_end`](super::StyledList::on_near_end), and is never set while loading.
+    pub fn needs_more(&self) -> bool {
+        self.needs_more
+    }
+
+    /// Take all the [`ListEvent`]s recorded since the last call, oldest first.
+    pub fn drain_events(&mut self) -> Vec<ListEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Toggle the mark on the [ListItem](super::ListItem) at index `n`. Indexes beyond the end of
+    /// the list are ignored.
+    pub fn toggle_mark(&mut self, n: usize) {
+        if n >= self.size {
+            return;
+        }
+        if !self.marked.remove(&n) {
+            self.marked.insert(n);
+        }
+        self.events.push(ListEvent::MarksChanged);
+    }
+
+    /// Is the [ListItem](super::ListItem) at index `n` marked?
+    pub fn is_marked(&self, n: usize) -> bool {
+        self.marked.contains(&n)
+    }
+
+    /// Iterate the indexes of the marked [ListItems](super::ListItem) in ascending order.
+    pub fn marked(&self) -> impl Iterator<Item = usize> + '_ {
+        self.marked.iter().copied()
+    }
+
+    /// Remove all marks. This does not clear the anchor, so the next cursor movement will mark
+    /// the range again if an anchor is set.
+    pub fn clear_marks(&mut self) {
+        if !self.marked.is_empty() {
+            self.marked.clear();
+            self.events.push(ListEvent::MarksChanged);
+        }
+    }
+
+    /// Set the anchor to the current selection. While an anchor is set, the marked items are the
+    /// contiguous range between the anchor and the selection, and moving the selection updates
+    /// the range.
+    pub fn set_anchor(&mut self) {
+        self.anchor = Some(self.selected);
+        self.extend_to_cursor();
+    }
+
+    /// Get the index of the anchor, if one is set.
+    pub fn anchor(&self) -> Option<usize> {
+        self.anchor
+    }
+
+    /// Mark the contiguous range between the anchor and the selection, replacing any other
+    /// marks. Does nothing if no anchor is set.
+    pub fn extend_to_cursor(&mut self) {
+        if let Some(anchor) = self.anchor {
+            let (first, last) = if anchor <= self.selected {
+                (anchor, self.selected)
+            } else {
+                (self.selected, anchor)
+            };
+            let range: BTreeSet<usize> = (first..=last).collect();
+            if range != self.marked {
+                self.marked = range;
+                self.events.push(ListEvent::MarksChanged);
+            }
+        }
+    }
+
+    /// Remove the anchor. The currently marked range stays marked, but further cursor movement
+    /// will no longer change it.
+    pub fn clear_anchor(&mut self) {
+        self.anchor = None;
+    }
+
+    /// Set the position of the first DisplayLine of the window, recording the scroll if it moved.
+    pub(super) fn set_pos(&mut self, pos: usize) {
+        if pos != self.window_first {
+            self.window_first = pos;
+            self.events.push(ListEvent::ScrolledTo(pos));
+        }
+    }
+
+    /// Move the selection to `n`, recording the movement and keeping the marked range in sync
+    /// with the cursor when an anchor is set.
+    fn move_to(&mut self, n: usize) {
+        if n != self.selected {
+            self.events.push(ListEvent::SelectionMoved {
+                from: self.selected,
+                to: n,
+            });
+            self.selected = n;
+        }
+        if self.anchor.is_some() {
+            self.extend_to_cursor();
         }
     }
 }
@@ -88,6 +277,46 @@ impl Default for ListState {
     }
 }
 
+/// Convert the state of a ratatui [`List`](ratatui::widgets::List).
+///
+/// The ratatui state doesn't know the length of the list, so the converted state is sized to
+/// just hold the selection (or 1 if nothing is selected), and should be
+/// [resized](ListState::resize) to the real length before use. A ratatui state with no selection
+/// selects the first item, since this state always has a selection.
+///
+/// ratatui's scroll offset isn't readable, so the window position starts at the top of the list.
+/// The first render scrolls the window to show the selection.
+impl From<ratatui::widgets::ListState> for ListState {
+    fn from(state: ratatui::widgets::ListState) -> Self {
+        let selected = state.selected().unwrap_or(0);
+        let mut res = ListState::new(selected + 1);
+        res.select(selected);
+        res.events.clear();
+        res
+    }
+}
+
+/// Convert to the state of a ratatui [`List`](ratatui::widgets::List).
+///
+/// Only the selection is kept. ratatui's scroll offset can't be set, so it starts at the top of
+/// the list and ratatui scrolls to the selection when rendering. (The window position of this
+/// state counts display lines, not items, so it wouldn't match ratatui's offset for multi-line
+/// items or [`ItemDisplay::Separated`](super::ItemDisplay::Separated) lists anyway.) Marks, the
+/// anchor, loading state and pending events are dropped.
+impl From<&ListState> for ratatui::widgets::ListState {
+    fn from(state: &ListState) -> Self {
+        let mut res = ratatui::widgets::ListState::default();
+        res.select(Some(state.selected));
+        res
+    }
+}
+
+impl From<ListState> for ratatui::widgets::ListState {
+    fn from(state: ListState) -> Self {
+        Self::from(&state)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,6 +368,147 @@ mod tests {
         assert_eq!(s.selected(), 3);
     }
 
+    #[test]
+    fn marks() {
+        let mut s = ListState::new(4);
+        s.toggle_mark(1);
+        s.toggle_mark(3);
+        s.toggle_mark(7);
+        assert_eq!(s.marked().collect::<Vec<_>>(), vec![1, 3]);
+        s.toggle_mark(1);
+        assert!(!s.is_marked(1));
+        assert!(s.is_marked(3));
+        s.clear_marks();
+        assert_eq!(s.marked().count(), 0);
+    }
+
+    #[test]
+    fn anchor_range() {
+        let mut s = ListState::new(6);
+        s.select(2);
+        s.set_anchor();
+        assert_eq!(s.marked().collect::<Vec<_>>(), vec![2]);
+        s.next();
+        s.next();
+        assert_eq!(s.marked().collect::<Vec<_>>(), vec![2, 3, 4]);
+        // moving back toward the anchor shrinks the range
+        s.prev();
+        assert_eq!(s.marked().collect::<Vec<_>>(), vec![2, 3]);
+        // and past it grows the range the other way
+        s.select(0);
+        assert_eq!(s.marked().collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        s.clear_anchor();
+        s.select(5);
+        assert_eq!(s.marked().collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn anchor_resize() {
+        let mut s = ListState::new(6);
+        s.select(5);
+        s.set_anchor();
+        s.select(3);
+        assert_eq!(s.marked().collect::<Vec<_>>(), vec![3, 4, 5]);
+        s.resize(4);
+        assert_eq!(s.anchor(), Some(3));
+        assert_eq!(s.marked().collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_without_marks() {
+        // states saved before marks and loading were added
+        let s: ListState =
+            serde_json::from_str(r#"{"size": 5, "selected": 3, "window_first": 1}"#).unwrap();
+        assert_eq!(s.size, 5);
+        assert_eq!(s.selected(), 3);
+        assert_eq!(s.window_first, 1);
+        assert_eq!(s.marked().count(), 0);
+        assert_eq!(s.anchor(), None);
+        assert!(!s.is_loading());
+    }
+
+    #[test]
+    fn events() {
+        let mut s = ListState::new(3);
+        assert!(s.drain_events().is_empty());
+        s.next();
+        s.next();
+        s.next();
+        s.toggle_mark(2);
+        s.activate();
+        assert_eq!(
+            s.drain_events(),
+            vec![
+                ListEvent::SelectionMoved { from: 0, to: 1 },
+                ListEvent::SelectionMoved { from: 1, to: 2 },
+                ListEvent::MarksChanged,
+                ListEvent::Activated(2),
+            ]
+        );
+        assert!(s.drain_events().is_empty());
+
+        s.set_pos(4);
+        s.set_pos(4);
+        s.resize(1);
+        assert_eq!(
+            s.drain_events(),
+            vec![
+                ListEvent::ScrolledTo(4),
+                ListEvent::MarksChanged,
+                ListEvent::SelectionMoved { from: 2, to: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn mnemonics() {
+        let list = StyledList::new(vec![
+            ListItem::new("Open").mnemonic('o'),
+            ListItem::new("Save").mnemonic('s'),
+            ListItem::new("Save as").mnemonic('S'),
+            ListItem::new("Quit"),
+        ]);
+        let mut s = ListState::new(4);
+        assert_eq!(s.select_by_mnemonic('s', &list), Some(1));
+        assert_eq!(s.select_by_mnemonic('s', &list), Some(2));
+        assert_eq!(s.select_by_mnemonic('s', &list), Some(1));
+        assert_eq!(s.select_by_mnemonic('O', &list), Some(0));
+        assert_eq!(s.select_by_mnemonic('O', &list), Some(0));
+        assert_eq!(s.select_by_mnemonic('q', &list), None);
+        assert_eq!(s.selected(), 0);
+
+        // lists carrying payloads work the same
+        let list = StyledList::with_data(vec![
+            ListItem::new("Open").mnemonic('o').with_data("open"),
+            ListItem::new("Save").mnemonic('s').with_data("save"),
+        ]);
+        assert_eq!(s.select_by_mnemonic('s', &list), Some(1));
+        assert_eq!(list.selected_data(&s), Some(&"save"));
+    }
+
+    #[test]
+    fn ratatui_round_trip() {
+        let mut s = ListState::new(10);
+        s.select(7);
+        let r = ratatui::widgets::ListState::from(&s);
+        assert_eq!(r.selected(), Some(7));
+        let mut back = ListState::from(r.clone());
+        assert_eq!(back.selected(), 7);
+        assert!(back.drain_events().is_empty());
+        back.resize(10);
+        assert_eq!(
+            ratatui::widgets::ListState::from(back).selected(),
+            r.selected()
+        );
+
+        // no selection maps to the first item
+        let s = ListState::from(ratatui::widgets::ListState::default());
+        assert_eq!(s.selected(), 0);
+        assert_eq!(s.size, 1);
+    }
+
     #[test]
     #[should_panic]
     fn zero_size_create() {
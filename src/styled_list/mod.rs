@@ -17,41 +17,46 @@
 // The rendering pipeline goes like this:
 //
 // items
+// -> Drop the items too far before the selection to be in the window
 // -> Apply selection styling based on state
 // -> flatten iter of ToLines to iter of DisplayLine, insert any DisplayLines for the ItemDisplay
 //    style
 // -> Filter the DiplayLines iterator to only the lines to draw via the window iterator.
 //
 // The window iterators process in a single pass so this pipeline is at worst O(n) (althougth if
-// window fills up before finishing the display, it will stop iteration before that.
+// window fills up before finishing the display, it will stop iteration before that. The items
+// dropped before the window are only counted, so most of the list is never styled or turned into
+// lines. The window storage is sized from the last render, as remembered by the ListState.
+use std::{cell::Cell, collections::VecDeque};
+
+mod data;
+mod highlight;
 mod line_iters;
 mod list_item;
 mod list_state;
+mod overflow;
+pub mod raw;
 mod separator;
+mod tree;
 mod window_type;
 
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::Style,
-    text::Spans,
+    text::{Span, Spans, Text},
     widgets::{Block, StatefulWidget, Widget},
 };
 
+use crate::truncate;
+
+pub use crate::truncate::TruncateAt;
+pub use data::{DataItem, DataItems};
 pub use list_item::{Indicator, LineIndicators, ListItem};
-pub use list_state::ListState;
+pub use list_state::{ListEvent, ListState};
+use raw::DisplayLine;
 use separator::Separator;
-
-/// A rendered line of text in the list widget. Multiple DisplayLines can be created from a single
-/// [`ListItem`]. The window operates on an iterable of [`DiplayLine`]s
-#[derive(Clone, Debug)]
-struct DisplayLine<'a> {
-    pub(super) style: Style,
-    pub(super) line: Spans<'a>,
-    pub(super) must_display: bool,
-    pub(super) left_indicator: Spans<'a>,
-    pub(super) right_indicator: Spans<'a>,
-}
+pub use tree::{TreeItem, TreeState};
 
 /// Control how lines are rendered
 #[derive(Debug, Copy, Clone)]
@@ -66,6 +71,7 @@ pub enum ItemDisplay {
 
 /// Control how the window places itself with respect to the rendered lines, i.e. control the list
 /// display of rendered lines.
+#[derive(Debug)]
 pub enum WindowType {
     /// Diplay rendered lines so that the first selected [`ListItem`] is always visible. The location of
     /// the selected items within the display window is dependent on movement. This operates the
@@ -77,6 +83,16 @@ pub enum WindowType {
     Fixed(usize),
 }
 
+/// Which edge of a [`StyledList`] an [overflow indicator](StyledList::overflow_indicators) is
+/// drawn on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OverflowSide {
+    /// Items are hidden above the first row.
+    Above,
+    /// Items are hidden below the last row.
+    Below,
+}
+
 /// A general purpose List widget that has several modes of display
 pub struct StyledList<'a, I>
 where
@@ -85,14 +101,40 @@ where
     block: Option<Block<'a>>,
     default_style: Style,
     selected_style: Style,
+    marked_style: Style,
     selected_indicator: LineIndicators,
     show_left_indicator: bool,
     show_right_indicator: bool,
     window_type: WindowType,
     item_display: ItemDisplay,
+    near_end: Option<usize>,
+    loading_row: Option<(Spans<'a>, Style)>,
+    badge_gap: u16,
+    min_content_width: u16,
+    highlight_text: Option<&'a str>,
+    match_style: Style,
+    case_sensitive: bool,
+    detail: Option<DetailFn<'a>>,
+    detail_style: Style,
+    expand: Option<ExpandFn<'a>>,
+    expanded_style: Style,
+    overflow_indicators: bool,
+    overflow_format: OverflowFn<'a>,
+    overflow_style: Style,
+    truncate: Option<TruncateAt>,
+    ellipsis: &'a str,
     items: I,
 }
 
+/// Closure producing the detail footer line for the selected item.
+type DetailFn<'a> = Box<dyn Fn(usize, &ListItem<'a>) -> Spans<'a> + 'a>;
+
+/// Closure producing the lines inserted below the selected item.
+type ExpandFn<'a> = Box<dyn Fn(usize, &ListItem<'a>) -> Text<'a> + 'a>;
+
+/// Closure producing an overflow indicator line from the number of hidden items.
+type OverflowFn<'a> = Box<dyn Fn(OverflowSide, usize) -> Spans<'a> + 'a>;
+
 impl<'a, I> StyledList<'a, I>
 where
     I: IntoIterator<Item = ListItem<'a>>,
@@ -103,11 +145,34 @@ where
             block: None,
             default_style: Style::default(),
             selected_style: Style::default(),
+            marked_style: Style::default(),
             selected_indicator: LineIndicators::default(),
             show_left_indicator: false,
             show_right_indicator: false,
             window_type: WindowType::SelectionScroll,
             item_display: ItemDisplay::Basic,
+            near_end: None,
+            loading_row: None,
+            badge_gap: 1,
+            min_content_width: 1,
+            highlight_text: None,
+            match_style: Style::default(),
+            case_sensitive: false,
+            detail: None,
+            detail_style: Style::default(),
+            expand: None,
+            expanded_style: Style::default(),
+            overflow_indicators: false,
+            overflow_format: Box::new(|side, n| {
+                let arrow = match side {
+                    OverflowSide::Above => "↑",
+                    OverflowSide::Below => "↓",
+                };
+                Spans::from(format!("{} {} more", arrow, n))
+            }),
+            overflow_style: Style::default(),
+            truncate: None,
+            ellipsis: "…",
         }
     }
     /// Wrap the list in a block (e.g. to set borders or a title).
@@ -130,6 +195,14 @@ where
         self
     }
 
+    /// The style applied to lines of marked items (see [`ListState::toggle_mark`] and
+    /// [`ListState::set_anchor`]). This is patched over the item's style, and the selected style
+    /// is patched over it when a marked item is also selected.
+    pub fn marked_style(mut self, s: Style) -> Self {
+        self.marked_style = s;
+        self
+    }
+
     /// The indicators to use for the selected item
     pub fn selected_indicator(mut self, indicator: LineIndicators) -> Self {
         self.selected_indicator = indicator;
@@ -159,6 +232,251 @@ where
         self.item_display = it;
         self
     }
+
+    /// The number of columns between the item text and each [badge](ListItem::badge). Defaults
+    /// to 1.
+    pub fn badge_gap(mut self, gap: u16) -> Self {
+        self.badge_gap = gap;
+        self
+    }
+
+    /// The narrowest the item text may be truncated to in order to fit its badges. Badges that
+    /// don't fit without going below this width are not drawn. Defaults to 1.
+    pub fn min_content_width(mut self, width: u16) -> Self {
+        self.min_content_width = width;
+        self
+    }
+
+    /// Highlight occurences of `needle` in the visible items using the
+    /// [`match_style`](Self::match_style). Only lines in the display window are searched.
+    pub fn highlight_text(mut self, needle: &'a str) -> Self {
+        self.highlight_text = Some(needle);
+        self
+    }
+
+    /// The style patched into text matching the [`highlight_text`](Self::highlight_text).
+    pub fn match_style(mut self, s: Style) -> Self {
+        self.match_style = s;
+        self
+    }
+
+    /// Match the [`highlight_text`](Self::highlight_text) case-sensitively. Matching ignores case
+    /// by default.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Truncate item lines that are too wide for the list, by replacing text at the given
+    /// position with the [`ellipsis`](Self::ellipsis). Without this, lines are clipped at the
+    /// right edge.
+    pub fn truncate(mut self, at: TruncateAt) -> Self {
+        self.truncate = Some(at);
+        self
+    }
+
+    /// The string that replaces text removed by truncation. Defaults to `…`.
+    pub fn ellipsis(mut self, ellipsis: &'a str) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// Show a footer on the last row of the list with detail for the selected item. The closure
+    /// is called with the index of the selected item and the item itself, and the line it
+    /// returns is truncated at the end with the [`ellipsis`](Self::ellipsis) if it is too wide.
+    pub fn detail<F>(mut self, f: F) -> Self
+    where
+        F: Fn(usize, &ListItem<'a>) -> Spans<'a> + 'a,
+    {
+        self.detail = Some(Box::new(f));
+        self
+    }
+
+    /// The style of the [`detail`](Self::detail) footer row.
+    pub fn detail_style(mut self, s: Style) -> Self {
+        self.detail_style = s;
+        self
+    }
+
+    /// Expand the selected item with extra lines of detail, inserted directly below it and
+    /// pushing the following items down. The closure is called with the index of the selected
+    /// item and the item itself.
+    ///
+    /// The expansion is part of the selected item, so the window keeps it on screen along with the
+    /// item when they fit. When they don't, the window starts at the item's first line.
+    pub fn expand_selected<F>(mut self, f: F) -> Self
+    where
+        F: Fn(usize, &ListItem<'a>) -> Text<'a> + 'a,
+    {
+        self.expand = Some(Box::new(f));
+        self
+    }
+
+    /// The style of the [expanded](Self::expand_selected) lines. This is patched over the style
+    /// of the selected item.
+    pub fn expanded_style(mut self, s: Style) -> Self {
+        self.expanded_style = s;
+        self
+    }
+
+    /// Show a count of the items hidden above or below the list, e.g. `↑ 12 more`. An indicator
+    /// takes the first or last row of the list, but only when there are items hidden in that
+    /// direction. The selection is still kept in view. Indicators are not shown in lists shorter
+    /// than 3 rows.
+    pub fn overflow_indicators(mut self, show: bool) -> Self {
+        self.overflow_indicators = show;
+        self
+    }
+
+    /// Format the [overflow indicators](Self::overflow_indicators). The closure is called with
+    /// the side of the list the indicator is on and the number of hidden items.
+    pub fn overflow_format<F>(mut self, f: F) -> Self
+    where
+        F: Fn(OverflowSide, usize) -> Spans<'a> + 'a,
+    {
+        self.overflow_format = Box::new(f);
+        self
+    }
+
+    /// The style of the [overflow indicator](Self::overflow_indicators) rows.
+    pub fn overflow_style(mut self, s: Style) -> Self {
+        self.overflow_style = s;
+        self
+    }
+
+    /// Set [`ListState::needs_more`] when rendering shows fewer than `threshold` items remaining
+    /// below the display window. This is a hook for lists that load items incrementally.
+    pub fn on_near_end(mut self, threshold: usize) -> Self {
+        self.near_end = Some(threshold);
+        self
+    }
+
+    /// A row displayed after the last item while [`ListState::is_loading`]. The row is not an
+    /// item, so it can't be selected.
+    pub fn loading_row<T>(mut self, content: T, style: Style) -> Self
+    where
+        T: Into<Spans<'a>>,
+    {
+        self.loading_row = Some((content.into(), style));
+        self
+    }
+}
+
+impl<'a, I> StyledList<'a, I>
+where
+    I: IntoIterator<Item = ListItem<'a>>,
+    for<'b> &'b I: IntoIterator<Item = &'b ListItem<'a>>,
+{
+    /// The [width](ListItem::width) of the widest item in the list, or 0 if there are no items.
+    /// This is useful for sizing the list so no items are truncated. Indicator columns and
+    /// badges are not included.
+    pub fn max_item_width(&self) -> u16 {
+        (&self.items)
+            .into_iter()
+            .map(ListItem::width)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl<'a, I> StyledList<'a, I>
+where
+    I: IntoIterator<Item = ListItem<'a>> + AsRef<[ListItem<'a>]>,
+{
+    /// The items in the list.
+    pub fn items(&self) -> &[ListItem<'a>] {
+        self.items.as_ref()
+    }
+
+    /// The number of items in the list.
+    pub fn len(&self) -> usize {
+        self.items().len()
+    }
+
+    /// Does the list have no items?
+    pub fn is_empty(&self) -> bool {
+        self.items().is_empty()
+    }
+}
+
+impl<'a> FromIterator<ListItem<'a>> for StyledList<'a, Vec<ListItem<'a>>> {
+    fn from_iter<T: IntoIterator<Item = ListItem<'a>>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<'a> Extend<ListItem<'a>> for StyledList<'a, Vec<ListItem<'a>>> {
+    fn extend<T: IntoIterator<Item = ListItem<'a>>>(&mut self, iter: T) {
+        self.items.extend(iter)
+    }
+}
+
+impl<'a, I> std::fmt::Debug for StyledList<'a, I>
+where
+    I: IntoIterator<Item = ListItem<'a>> + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // the closures (detail, expansion, overflow format) can't be printed
+        f.debug_struct("StyledList")
+            .field("block", &self.block)
+            .field("default_style", &self.default_style)
+            .field("selected_style", &self.selected_style)
+            .field("marked_style", &self.marked_style)
+            .field("selected_indicator", &self.selected_indicator)
+            .field("show_left_indicator", &self.show_left_indicator)
+            .field("show_right_indicator", &self.show_right_indicator)
+            .field("window_type", &self.window_type)
+            .field("item_display", &self.item_display)
+            .field("truncate", &self.truncate)
+            .field("items", &self.items)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, I> StyledList<'a, I>
+where
+    I: IntoIterator<Item = ListItem<'a>>,
+{
+    /// Render the list into a scratch buffer of `width` x `height` and return its rows. Each row
+    /// is a [`Spans`] with a [`Span`] for each run of cells with the same style.
+    ///
+    /// This runs the normal render path, so it is useful for asserting on the output of a list
+    /// configuration in tests.
+    pub fn render_to_lines(
+        self,
+        state: &mut ListState,
+        width: u16,
+        height: u16,
+    ) -> Vec<Spans<'static>> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        StatefulWidget::render(self, area, &mut buf, state);
+        buffer_lines(&buf)
+    }
+}
+
+/// Convert each row of a buffer to [`Spans`], grouping runs of cells with the same style.
+fn buffer_lines(buf: &Buffer) -> Vec<Spans<'static>> {
+    let area = buf.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut spans: Vec<Span<'static>> = Vec::new();
+            let mut x = area.left();
+            while x < area.right() {
+                let cell = buf.get(x, y);
+                let style = cell.style();
+                match spans.last_mut() {
+                    Some(last) if last.style == style => {
+                        last.content.to_mut().push_str(&cell.symbol)
+                    }
+                    _ => spans.push(Span::styled(cell.symbol.clone(), style)),
+                }
+                // skip the cells hidden by a wide symbol
+                x += (unicode_width::UnicodeWidthStr::width(cell.symbol.as_str()) as u16).max(1);
+            }
+            Spans(spans)
+        })
+        .collect()
 }
 
 impl<'a, I> StatefulWidget for StyledList<'a, I>
@@ -168,6 +486,36 @@ where
     type State = ListState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let selected = state.selected;
+        self.render_list(area, buf, state, Some(selected));
+    }
+}
+
+/// Render the list from the top, without a selection. Since there is no selection nothing is
+/// highlighted, and the list doesn't scroll, so only the lines that fit in the area are drawn.
+impl<'a, I> Widget for StyledList<'a, I>
+where
+    I: IntoIterator<Item = ListItem<'a>>,
+{
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut state = ListState::default();
+        self.render_list(area, buf, &mut state, None);
+    }
+}
+
+impl<'a, I> StyledList<'a, I>
+where
+    I: IntoIterator<Item = ListItem<'a>>,
+{
+    /// The render path shared by the stateful and stateless widgets. When `selected` is `None`
+    /// no item is styled as selected, and the window is fixed to the top of the list.
+    fn render_list(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut ListState,
+        selected: Option<usize>,
+    ) {
         // Block is used for borders and such
         // Draw that first, and use the blank area inside the block for our own purposes
         let area = match self.block {
@@ -179,38 +527,132 @@ where
             }
         };
 
+        // Nothing fits in an empty area (e.g. a pane collapsed by a resize, or a block with borders
+        // larger than the area).
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
         // set style for whole area
         buf.set_style(area, self.default_style);
 
+        // The detail footer takes the last row, the list gets the rest. A single row is left to
+        // the list, so the selection is still shown. Without a selection there is no detail to
+        // show, so the list keeps the row.
+        let (area, footer_area) = match self.detail {
+            Some(_) if selected.is_some() && area.height > 1 => {
+                let list_area = Rect {
+                    height: area.height - 1,
+                    ..area
+                };
+                let footer_area = Rect {
+                    y: area.y + list_area.height,
+                    height: 1,
+                    ..area
+                };
+                (list_area, Some(footer_area))
+            }
+            _ => (area, None),
+        };
+        let detail_line = Cell::new(None);
+
+        // Drop the items that are too far before the selection to be shown, so no lines are made
+        // for them. The items after the window are never reached, as the window stops reading
+        // lines once it is settled.
+        let mut items = self.items.into_iter().enumerate();
+        let (kept, skipped_lines) = match selected {
+            Some(selected) => window_type::skip_hidden(
+                &mut items,
+                selected,
+                area.height as usize,
+                matches!(self.item_display, ItemDisplay::Separated),
+            ),
+            None => (VecDeque::new(), 0),
+        };
+
         let sep = Separator::new(area.width as usize, self.default_style);
 
         // Start the pipeline: appy indicators and patch in appropriate stylings.
         // Then convert to a ToLines.
-        let selected = state.selected;
-        let iter = self.items.into_iter().enumerate().map(|(i, mut it)| {
-            if i == selected {
+        let marked = &state.marked;
+        let iter = kept.into_iter().chain(items).map(|(i, mut it)| {
+            let is_selected = Some(i) == selected;
+            let mut expansion = None;
+            if is_selected {
+                if let Some(detail) = &self.detail {
+                    detail_line.set(Some(detail(i, &it)));
+                }
+                expansion = self.expand.as_ref().map(|expand| expand(i, &it));
+            }
+            let mut style = self.default_style.patch(it.style);
+            if marked.contains(&i) {
+                style = style.patch(self.marked_style);
+            }
+            if is_selected {
                 it = it.indicators(self.selected_indicator);
-                it.style = self
-                    .default_style
-                    .patch(it.style.patch(self.selected_style));
-            } else {
-                it.style = self.default_style.patch(it.style);
+                style = style.patch(self.selected_style);
             }
+            it.style = style;
 
-            line_iters::ToLines::new(it, i == selected)
+            let lines = line_iters::ToLines::new(it, is_selected).index(i);
+            match expansion {
+                Some(text) => lines.expand(text, self.expanded_style),
+                None => lines,
+            }
         });
 
-        // Next step of pipeline, apply DisplayLine renderer
-        let item_display = self.item_display.display_iter(iter, sep);
+        // Next step of pipeline, apply DisplayLine renderer, then add the loading row after the
+        // last line if needed.
+        let loading_row = match self.loading_row {
+            Some((line, style)) if state.loading => Some(DisplayLine {
+                style: self.default_style.patch(style),
+                line,
+                must_display: false,
+                left_indicator: " ",
+                right_indicator: " ",
+                item: None,
+                badges: Vec::new(),
+            }),
+            _ => None,
+        };
+        let item_display = self.item_display.display_iter(iter, sep).chain(loading_row);
+
+        // Filter the lines to those in the current view window. The window counts lines from the
+        // first line kept.
+        let storage = state.window.take();
+        let mut window_first = state.window_first.saturating_sub(skipped_lines);
+        let mut lines = match selected {
+            Some(_) => self.window_type.line_iter(
+                item_display,
+                area.height as usize,
+                &mut window_first,
+                storage,
+            ),
+            None => window_type::top(item_display, area.height as usize, storage),
+        };
+        window_first += skipped_lines;
 
-        // Filter the lines to those in the current view window
-        let lines = self
-            .window_type
-            .line_iter(item_display, area.height as usize, state);
+        // Make room for the overflow indicators. The window position is moved to match the lines
+        // drawn, so the next render shows the same lines if the selection doesn't move.
+        let mut first_row = area.y;
+        let mut overflow = None;
+        if self.overflow_indicators && area.height >= 3 {
+            let o = overflow::place(lines.make_contiguous(), area.height as usize, state.size);
+            lines.truncate(o.lines.end);
+            lines.drain(..o.lines.start);
+            window_first =
+                (window_first + o.lines.start).saturating_sub(o.above.is_some() as usize);
+            if o.above.is_some() {
+                first_row += 1;
+            }
+            overflow = Some(o);
+        }
 
         // Draw the lines into the window.
-        for (i, l) in lines.into_iter().enumerate() {
-            let y = area.y + i as u16;
+        let mut last_item = None;
+        for (i, l) in lines.drain(..).enumerate() {
+            last_item = l.item.or(last_item);
+            let y = first_row + i as u16;
             // first fill the whole line area
             let d_area = Rect {
                 x: area.x,
@@ -224,44 +666,110 @@ where
             let mut line_width = area.width;
 
             // show the left indicator and adjust the display area for the item text
-            if self.show_left_indicator {
-                buf.set_spans(x, y, &l.left_indicator, 1);
+            if self.show_left_indicator && line_width > 0 {
+                buf.set_stringn(x, y, l.left_indicator, 1, Style::default());
                 x += 1;
                 line_width -= 1;
             }
 
             // show the right indicator and adjust the display area for the item text
-            if self.show_right_indicator {
+            if self.show_right_indicator && line_width > 0 {
                 line_width -= 1;
-                buf.set_spans(x + line_width, y, &l.right_indicator, 1);
+                buf.set_stringn(x + line_width, y, l.right_indicator, 1, Style::default());
             }
 
+            // show the badges right aligned, dropping them if the text would get too narrow
+            let badges_width = |badges: &[Span]| -> u16 {
+                badges
+                    .iter()
+                    .map(|b| b.width() as u16 + self.badge_gap)
+                    .sum()
+            };
+            let mut badges = &l.badges[..];
+            while !badges.is_empty() && badges_width(badges) + self.min_content_width > line_width {
+                badges = &badges[1..];
+            }
+            let mut badge_x = x + line_width;
+            for b in badges.iter().rev() {
+                let width = b.width() as u16;
+                badge_x -= width;
+                buf.set_span(badge_x, y, b, width);
+                badge_x -= self.badge_gap;
+            }
+            line_width -= badges_width(badges);
+
             // show the item text
-            buf.set_spans(x, y, &l.line, line_width);
+            let line = match self.highlight_text {
+                Some(needle) if l.item.is_some() => {
+                    highlight::highlight(l.line, needle, self.match_style, self.case_sensitive)
+                }
+                _ => l.line,
+            };
+            let line = match self.truncate {
+                Some(at) if l.item.is_some() => {
+                    truncate::truncate(line, line_width as usize, self.ellipsis, at)
+                }
+                _ => line,
+            };
+            buf.set_spans(x, y, &line, line_width);
         }
-    }
-}
 
-impl<'a, I> Widget for StyledList<'a, I>
-where
-    I: IntoIterator<Item = ListItem<'a>>,
-{
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let mut state = ListState::default();
-        StatefulWidget::render(self, area, buf, &mut state);
+        state.window.put(lines);
+        state.set_pos(window_first);
+
+        // Draw the overflow indicators
+        if let Some(o) = overflow {
+            let rows = [
+                (OverflowSide::Above, o.above, area.y),
+                (OverflowSide::Below, o.below, area.bottom() - 1),
+            ];
+            for (side, hidden, y) in rows {
+                if let Some(n) = hidden {
+                    let row = Rect {
+                        y,
+                        height: 1,
+                        ..area
+                    };
+                    buf.set_style(row, self.overflow_style);
+                    let line = truncate::truncate_end(
+                        (self.overflow_format)(side, n),
+                        area.width as usize,
+                        self.ellipsis,
+                    );
+                    buf.set_spans(area.x, y, &line, area.width);
+                }
+            }
+        }
+
+        // Draw the detail footer for the selected item
+        if let (Some(footer_area), Some(line)) = (footer_area, detail_line.take()) {
+            buf.set_style(footer_area, self.detail_style);
+            let line = truncate::truncate_end(line, footer_area.width as usize, self.ellipsis);
+            buf.set_spans(footer_area.x, footer_area.y, &line, footer_area.width);
+        }
+
+        if let Some(threshold) = self.near_end {
+            let remaining = match last_item {
+                Some(last) => state.size.saturating_sub(last + 1),
+                None => state.size,
+            };
+            state.needs_more = !state.loading && remaining < threshold;
+        }
     }
 }
 
 // Private impls of conveience functions in render
 impl<'a> DisplayLine<'a> {
-    /// Construct an empty DisplayLine (e.g as a placeholder)
+    /// Construct an empty DisplayLine (e.g as a placeholder). This doesn't allocate.
     fn filler(x: &'static str) -> Self {
         Self {
             style: Style::default(),
-            line: Spans::from(x),
+            line: Spans::default(),
             must_display: false,
-            left_indicator: Spans::from(x),
-            right_indicator: Spans::from(x),
+            left_indicator: x,
+            right_indicator: x,
+            item: None,
+            badges: Vec::new(),
         }
     }
 }
@@ -300,21 +808,483 @@ where
 }
 
 impl WindowType {
-    /// Iterate through the rendered display lines and produce the ones that should be shown in the
-    /// window.
+    /// Iterate through the rendered display lines and fill the (empty) `lines` storage with the
+    /// ones that should be shown in the window.
     fn line_iter<'a, I>(
         self,
         items: I,
         window_size: usize,
-        list_state: &mut ListState,
-    ) -> impl Iterator<Item = DisplayLine<'a>>
+        window_first: &mut usize,
+        lines: VecDeque<DisplayLine<'a>>,
+    ) -> VecDeque<DisplayLine<'a>>
     where
         I: Iterator<Item = DisplayLine<'a>>,
     {
         use WindowType::*;
         match self {
-            SelectionScroll => window_type::selection_scroll(items, window_size, list_state),
-            Fixed(at) => window_type::fixed(items, at, window_size, list_state),
+            SelectionScroll => {
+                window_type::selection_scroll_into(items, window_size, window_first, lines)
+            }
+            Fixed(at) => window_type::fixed_into(items, at, window_size, window_first, lines),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render<'a>(
+        list: StyledList<'a, Vec<ListItem<'a>>>,
+        state: &mut ListState,
+        height: u16,
+    ) -> Buffer {
+        let area = Rect::new(0, 0, 5, height);
+        let mut buf = Buffer::empty(area);
+        StatefulWidget::render(list, area, &mut buf, state);
+        buf
+    }
+
+    fn items<'a>(n: usize) -> Vec<ListItem<'a>> {
+        (0..n).map(|i| ListItem::new(i.to_string())).collect()
+    }
+
+    #[test]
+    fn render_to_lines() {
+        use ratatui::style::Color;
+        let blue = Style::default().bg(Color::Blue);
+        // cells are reset when the buffer is created
+        let reset = Style::default().fg(Color::Reset).bg(Color::Reset);
+        let mut state = ListState::new(2);
+        state.select(1);
+        let lines = StyledList::new(vec![ListItem::new("a"), ListItem::new("日b")])
+            .selected_style(blue)
+            .render_to_lines(&mut state, 4, 3);
+        assert_eq!(
+            lines,
+            vec![
+                Spans::from(Span::styled("a   ", reset)),
+                Spans::from(Span::styled("日b ", reset.patch(blue))),
+                Spans::from(Span::styled("    ", reset)),
+            ]
+        );
+    }
+
+    #[test]
+    fn item_measurements() {
+        let red = Style::default().fg(ratatui::style::Color::Red);
+        let items = vec![
+            ListItem::new("abc"),
+            ListItem::new(vec![
+                Spans::from(vec![Span::raw("日本"), Span::styled("x", red)]),
+                Spans::from("ab"),
+            ]),
+        ];
+        assert_eq!(items[0].width(), 3);
+        assert_eq!(items[0].height(), 1);
+        assert_eq!(items[1].width(), 5);
+        assert_eq!(items[1].height(), 2);
+
+        let list = StyledList::new(items);
+        assert_eq!(list.max_item_width(), 5);
+        assert_eq!(StyledList::new(vec![]).max_item_width(), 0);
+
+        // the measured size is exactly what the render draws
+        let mut state = ListState::new(2);
+        let lines = list.render_to_lines(&mut state, 5, 3);
+        let drawn: Vec<String> = lines
+            .iter()
+            .map(|l| l.0.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(drawn, vec!["abc  ", "日本x", "ab   "]);
+    }
+
+    #[test]
+    fn stateless_render() {
+        use ratatui::style::Color;
+        let blue = Style::default().bg(Color::Blue);
+        let list = StyledList::new(items(5)).selected_style(blue);
+        let area = Rect::new(0, 0, 5, 3);
+        let mut buf = Buffer::empty(area);
+        Widget::render(list, area, &mut buf);
+        // no line is highlighted, and the window is at the top
+        assert_eq!(buf, Buffer::with_lines(vec!["0    ", "1    ", "2    "]));
+    }
+
+    #[test]
+    fn expand_selected() {
+        let expand =
+            |_: usize, it: &ListItem| Text::from(format!("w{}\nh{}", it.width(), it.height()));
+        let mut state = ListState::new(4);
+        state.select(1);
+        let list = StyledList::new(items(4)).expand_selected(expand);
+        let buf = render(list, &mut state, 5);
+        assert_eq!(
+            buf,
+            Buffer::with_lines(vec!["0    ", "1    ", "w1   ", "h1   ", "2    "])
+        );
+
+        // moving down to the last item scrolls to show the whole expansion
+        state.select(3);
+        let list = StyledList::new(items(4)).expand_selected(expand);
+        let buf = render(list, &mut state, 5);
+        assert_eq!(
+            buf,
+            Buffer::with_lines(vec!["1    ", "2    ", "3    ", "w1   ", "h1   "])
+        );
+
+        // when the expansion doesn't fit, the window starts at the item
+        let list = StyledList::new(items(4)).expand_selected(expand);
+        let buf = render(list, &mut state, 2);
+        assert_eq!(buf, Buffer::with_lines(vec!["3    ", "w1   "]));
+    }
+
+    #[test]
+    fn overflow_indicators() {
+        let list = || {
+            StyledList::new(items(10))
+                .overflow_indicators(true)
+                .overflow_format(|side, n| Spans::from(format!("{:?}{}", side, n)))
+        };
+        let mut state = ListState::new(10);
+        let buf = render(list(), &mut state, 4);
+        assert_eq!(
+            buf,
+            Buffer::with_lines(vec!["0    ", "1    ", "2    ", "Belo…"])
+        );
+
+        // moving past the bottom keeps the selection above the indicator
+        state.select(3);
+        let buf = render(list(), &mut state, 4);
+        assert_eq!(
+            buf,
+            Buffer::with_lines(vec!["Abov…", "2    ", "3    ", "Belo…"])
+        );
+
+        // the window doesn't move while the selection stays in view
+        state.select(2);
+        let buf = render(list(), &mut state, 4);
+        assert_eq!(
+            buf,
+            Buffer::with_lines(vec!["Abov…", "2    ", "3    ", "Belo…"])
+        );
+
+        state.select(9);
+        let buf = render(list(), &mut state, 4);
+        assert_eq!(
+            buf,
+            Buffer::with_lines(vec!["Abov…", "7    ", "8    ", "9    "])
+        );
+    }
+
+    #[test]
+    fn collect_and_inspect() {
+        let mut list: StyledList<_> = items(3).into_iter().collect();
+        assert_eq!(list.len(), 3);
+        assert!(!list.is_empty());
+        list.extend(items(2));
+        assert_eq!(list.items()[3], ListItem::new("0"));
+        assert_eq!(list.len(), 5);
+        assert!(StyledList::new(vec![]).is_empty());
+        assert!(format!("{:?}", list).starts_with("StyledList {"));
+    }
+
+    #[test]
+    fn tiny_areas() {
+        use ratatui::widgets::Borders;
+        let lists = |n: usize| {
+            let items = || {
+                (0..n)
+                    .map(|i| ListItem::new(format!("{}\nx", i)).badge(Span::raw("b")))
+                    .collect::<Vec<_>>()
+            };
+            vec![
+                StyledList::new(items()),
+                StyledList::new(items())
+                    .show_left_indicator()
+                    .show_right_indicator()
+                    .item_display(ItemDisplay::Separated),
+                StyledList::new(items()).window_type(WindowType::Fixed(5)),
+                StyledList::new(items())
+                    .block(Block::default().borders(Borders::ALL))
+                    .truncate(TruncateAt::Middle)
+                    .overflow_indicators(true)
+                    .detail(|_, _| Spans::from("detail")),
+            ]
+        };
+        for n in [0, 1, 2, 5] {
+            for width in 0..=3 {
+                for height in 0..=3 {
+                    for list in lists(n) {
+                        // draw in the middle of a larger buffer to catch drawing outside the area
+                        let area = Rect::new(2, 2, width, height);
+                        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 8));
+                        let mut state = ListState::new(n.max(1));
+                        state.select(n / 2);
+                        StatefulWidget::render(list, area, &mut buf, &mut state);
+                        for y in 0..8 {
+                            for x in 0..8 {
+                                if !(area.left()..area.right()).contains(&x)
+                                    || !(area.top()..area.bottom()).contains(&y)
+                                {
+                                    assert_eq!(
+                                        buf.get(x, y).symbol,
+                                        " ",
+                                        "{}x{} with {} items",
+                                        width,
+                                        height,
+                                        n
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wide_separators() {
+        let area = Rect::new(0, 0, 300, 3);
+        let mut buf = Buffer::empty(area);
+        let list = StyledList::new(items(1)).item_display(ItemDisplay::Separated);
+        StatefulWidget::render(list, area, &mut buf, &mut ListState::new(1));
+        assert_eq!(buf.get(299, 0).symbol, ratatui::symbols::bar::HALF);
+    }
+
+    #[test]
+    fn stale_window_position() {
+        // A small xorshift generator, so the cases are random but repeatable
+        let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut rand = |n: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % n as u64) as usize
+        };
+        for _ in 0..3000 {
+            let len = rand(30) + 1;
+            let height = rand(8) + 1;
+            let selected = rand(len);
+            let pos = rand(100);
+            let lines_per_item = rand(3) + 1;
+            let separated = rand(2) == 0;
+            let overflow = rand(2) == 0;
+
+            let items = (0..len).map(|i| {
+                let text: Vec<String> = (0..lines_per_item)
+                    .map(|l| format!("{}.{}", i, l))
+                    .collect();
+                ListItem::new(text.join("\n"))
+            });
+            let list = StyledList::new(items.collect::<Vec<_>>())
+                .overflow_indicators(overflow)
+                .item_display(if separated {
+                    ItemDisplay::Separated
+                } else {
+                    ItemDisplay::Basic
+                });
+            let mut state = ListState::new(len);
+            state.select(selected);
+            state.window_first = pos;
+
+            let lines = list.render_to_lines(&mut state, 8, height as u16);
+            let rows: Vec<String> = lines
+                .iter()
+                .map(|l| l.0.iter().map(|s| s.content.as_ref()).collect())
+                .collect();
+            let first_line = format!("{}.0", selected);
+            assert!(
+                rows.iter().any(|r| r.trim_end() == first_line),
+                "len {} height {} selected {} pos {} lines {} separated {} overflow {}: {:?}",
+                len,
+                height,
+                selected,
+                pos,
+                lines_per_item,
+                separated,
+                overflow,
+                rows
+            );
+        }
+    }
+
+    #[test]
+    fn single_row_shows_selection() {
+        let mut state = ListState::new(5);
+        state.select(3);
+        let list = StyledList::new(items(5)).detail(|_, _| Spans::from("detail"));
+        let buf = render(list, &mut state, 1);
+        assert_eq!(buf, Buffer::with_lines(vec!["3    "]));
+
+        let list = StyledList::new(items(5)).window_type(WindowType::Fixed(2));
+        let buf = render(list, &mut state, 1);
+        assert_eq!(buf, Buffer::with_lines(vec!["3    "]));
+    }
+
+    #[test]
+    fn needs_more() {
+        let mut state = ListState::new(10);
+        render(StyledList::new(items(10)).on_near_end(3), &mut state, 4);
+        assert!(!state.needs_more());
+
+        state.select(7);
+        render(StyledList::new(items(10)).on_near_end(3), &mut state, 4);
+        assert!(state.needs_more());
+
+        state.set_loading(true);
+        render(StyledList::new(items(10)).on_near_end(3), &mut state, 4);
+        assert!(!state.needs_more());
+    }
+
+    #[test]
+    fn badges() {
+        let items = || {
+            vec![ListItem::new("abcdef")
+                .badge(Span::raw("3"))
+                .badge(Span::styled(
+                    "●",
+                    Style::default().fg(ratatui::style::Color::Red),
+                ))]
+        };
+        let mut state = ListState::new(1);
+        let buf = render(StyledList::new(items()), &mut state, 1);
+        assert_eq!(buf, {
+            let mut b = Buffer::with_lines(vec!["a 3 ●"]);
+            b.get_mut(4, 0).set_fg(ratatui::style::Color::Red);
+            b
+        });
+
+        // the first badge is dropped once the text would be narrower than the minimum
+        let buf = render(StyledList::new(items()).min_content_width(2), &mut state, 1);
+        assert_eq!(
+            buf.content()[0..3],
+            Buffer::with_lines(vec!["abc"]).content()[..]
+        );
+        assert_eq!(buf.get(4, 0).symbol, "●");
+
+        let buf = render(StyledList::new(items()).min_content_width(4), &mut state, 1);
+        assert_eq!(buf, Buffer::with_lines(vec!["abcde"]));
+    }
+
+    #[test]
+    fn truncation() {
+        let items = || vec![ListItem::new("src/lib.rs"), ListItem::new("a.rs")];
+        let mut state = ListState::new(2);
+        let buf = render(StyledList::new(items()), &mut state, 2);
+        assert_eq!(buf, Buffer::with_lines(vec!["src/l", "a.rs "]));
+
+        let list = StyledList::new(items()).truncate(TruncateAt::Start);
+        let buf = render(list, &mut state, 2);
+        assert_eq!(buf, Buffer::with_lines(vec!["…b.rs", "a.rs "]));
+
+        let list = StyledList::new(items())
+            .truncate(TruncateAt::Middle)
+            .ellipsis("~");
+        let buf = render(list, &mut state, 2);
+        assert_eq!(buf, Buffer::with_lines(vec!["sr~rs", "a.rs "]));
+    }
+
+    #[test]
+    fn detail_footer() {
+        let mut state = ListState::new(3);
+        state.select(2);
+        let list = StyledList::new(items(3)).detail(|i, _| Spans::from(format!("item {} of 3", i)));
+        let buf = render(list, &mut state, 3);
+        assert_eq!(buf, Buffer::with_lines(vec!["1    ", "2    ", "item…"]));
+
+        // without a selection there is no footer, so the list keeps the row
+        let list = StyledList::new(items(3)).detail(|i, _| Spans::from(format!("item {} of 3", i)));
+        let area = Rect::new(0, 0, 5, 3);
+        let mut buf = Buffer::empty(area);
+        Widget::render(list, area, &mut buf);
+        assert_eq!(buf, Buffer::with_lines(vec!["0    ", "1    ", "2    "]));
+    }
+
+    #[test]
+    fn loading_row() {
+        let mut state = ListState::new(2);
+        let list = StyledList::new(items(2)).loading_row("...", Style::default());
+        let buf = render(list, &mut state, 4);
+        assert_eq!(
+            buf,
+            Buffer::with_lines(vec!["0    ", "1    ", "     ", "     "])
+        );
+
+        state.set_loading(true);
+        state.select(1);
+        let list = StyledList::new(items(2)).loading_row("...", Style::default());
+        let buf = render(list, &mut state, 4);
+        assert_eq!(
+            buf,
+            Buffer::with_lines(vec!["0    ", "1    ", "...  ", "     "])
+        );
+        assert_eq!(state.selected(), 1);
+    }
+
+    #[test]
+    fn window_matches_whole_list() {
+        // The items before the window are dropped without being drawn, which mustn't change the
+        // rows drawn: they match the rows of the whole list from the top of the window.
+        use ratatui::style::Color;
+        let colors = [Color::Red, Color::Green, Color::Blue];
+        let items = || {
+            (0..60)
+                .map(|i| {
+                    let text = match i % 4 {
+                        0 => String::new(),
+                        1 => format!("{}\n{}b", i, i),
+                        _ => i.to_string(),
+                    };
+                    ListItem::new(text).style(Style::default().bg(colors[i % 3]))
+                })
+                .collect::<Vec<_>>()
+        };
+        for display in [ItemDisplay::Basic, ItemDisplay::Separated] {
+            let list = || {
+                StyledList::new(items())
+                    .item_display(display)
+                    .selected_style(Style::default().bg(Color::Yellow))
+                    .marked_style(Style::default().fg(Color::White))
+            };
+            let mut state = ListState::new(60);
+            state.toggle_mark(41);
+            let mut expected_top = 0;
+            for selected in [50, 10, 45, 59, 0, 30, 31] {
+                state.select(selected);
+                let buf = render(list(), &mut state, 6);
+
+                // the window is where it would be over the lines of all the items
+                let lines = display.display_iter(
+                    items()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, it)| line_iters::ToLines::new(it, i == selected).index(i)),
+                    Separator::new(5, Style::default()),
+                );
+                window_type::selection_scroll(lines, 6, &mut expected_top).for_each(drop);
+                assert_eq!(state.window_first, expected_top);
+
+                let mut whole_state = ListState::new(60);
+                whole_state.toggle_mark(41);
+                whole_state.select(selected);
+                let whole = render(list(), &mut whole_state, 150);
+                let top = state.window_first as u16;
+                for y in 0..6 {
+                    for x in 0..5 {
+                        assert_eq!(
+                            buf.get(x, y),
+                            whole.get(x, top + y),
+                            "{:?} selected {} at {}, {}",
+                            display,
+                            selected,
+                            x,
+                            y
+                        );
+                    }
+                }
+            }
         }
     }
 }
@@ -0,0 +1,136 @@
+use std::ops::Range;
+
+use super::DisplayLine;
+
+/// Where the rows of the display window go when overflow indicators are shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Overflow {
+    /// The window lines to draw, below the top indicator row (if there is one).
+    pub(super) lines: Range<usize>,
+    /// The number of items hidden above the drawn lines, if the top row is an indicator.
+    pub(super) above: Option<usize>,
+    /// The number of items hidden below the drawn lines, if the bottom row is an indicator.
+    pub(super) below: Option<usize>,
+}
+
+/// Place the window `lines` in `height` rows, leaving the first and last rows for indicators when
+/// items are hidden above or below. `size` is the number of items in the list.
+///
+/// The window was selected to fit `height` rows, so each indicator takes a row from one end of
+/// it. The top indicator covers the first line of the window, unless the selection starts there,
+/// in which case the lines are pushed down and the bottom line is dropped instead. The drawn
+/// lines always include the first line of the selected item, and as much of the selection as
+/// fits.
+pub(super) fn place(lines: &[DisplayLine], height: usize, size: usize) -> Overflow {
+    let selection = lines.iter().position(|l| l.must_display).map(|start| {
+        let len = lines[start..].iter().take_while(|l| l.must_display).count();
+        // skip a separator leading the selection, like the window does
+        let item_start = lines[start..start + len]
+            .iter()
+            .position(|l| l.item.is_some())
+            .map_or(start, |i| start + i);
+        item_start..start + len
+    });
+
+    let mut top = false;
+    let mut bottom = false;
+    loop {
+        let rows = height.saturating_sub(top as usize + bottom as usize);
+        let mut start = top as usize;
+        if let Some(sel) = &selection {
+            if sel.start < start {
+                start = sel.start;
+            } else if sel.end > start + rows {
+                start = std::cmp::min(sel.end - rows, sel.start);
+            }
+        }
+        let start = std::cmp::min(start, lines.len());
+        let end = std::cmp::min(start + rows, lines.len());
+
+        let (above, below) = hidden(&lines[start..end], !lines.is_empty(), size);
+        // Adding an indicator only takes rows away, so this settles after at most two more passes.
+        if (above > 0, below > 0) == (top, bottom) || (top && bottom) {
+            return Overflow {
+                lines: start..end,
+                above: top.then_some(above),
+                below: bottom.then_some(below),
+            };
+        }
+        top |= above > 0;
+        bottom |= below > 0;
+    }
+}
+
+/// Count the items entirely above and below `shown`. Items that are partly shown aren't counted.
+fn hidden(shown: &[DisplayLine], any_lines: bool, size: usize) -> (usize, usize) {
+    let first = shown.iter().find_map(|l| l.item);
+    let last = shown.iter().rev().find_map(|l| l.item);
+    match (first, last) {
+        (Some(first), Some(last)) => (first, size.saturating_sub(last + 1)),
+        _ if any_lines => (0, 0),
+        _ => (0, size),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{style::Style, text::Spans};
+
+    use super::*;
+
+    /// Lines for items `first..` with `sel` selected, one line per item.
+    fn lines<'a>(first: usize, n: usize, sel: Option<usize>) -> Vec<DisplayLine<'a>> {
+        (first..first + n)
+            .map(|i| DisplayLine {
+                style: Style::default(),
+                line: Spans::from(""),
+                must_display: Some(i) == sel,
+                left_indicator: " ",
+                right_indicator: " ",
+                item: Some(i),
+                badges: Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nothing_hidden() {
+        let o = place(&lines(0, 4, Some(1)), 5, 4);
+        assert_eq!(
+            o,
+            Overflow {
+                lines: 0..4,
+                above: None,
+                below: None
+            }
+        );
+    }
+
+    #[test]
+    fn hidden_below() {
+        // items 0-4 in the window, selection at the top
+        let o = place(&lines(0, 5, Some(0)), 5, 20);
+        assert_eq!(o.lines, 0..4);
+        assert_eq!((o.above, o.below), (None, Some(16)));
+    }
+
+    #[test]
+    fn hidden_both() {
+        // scrolled down to the selection on the last row
+        let o = place(&lines(10, 5, Some(14)), 5, 20);
+        assert_eq!(o.lines, 2..5);
+        assert_eq!((o.above, o.below), (Some(12), Some(5)));
+
+        // scrolled up to the selection on the first row
+        let o = place(&lines(10, 5, Some(10)), 5, 20);
+        assert_eq!(o.lines, 0..3);
+        assert_eq!((o.above, o.below), (Some(10), Some(7)));
+    }
+
+    #[test]
+    fn hidden_above() {
+        let o = place(&lines(15, 5, Some(19)), 5, 20);
+        assert_eq!(o.lines, 1..5);
+        assert_eq!((o.above, o.below), (Some(16), None));
+    }
+}
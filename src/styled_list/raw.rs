@@ -0,0 +1,96 @@
+//! The line windowing used by [`StyledList`](super::StyledList), for building custom list
+//! widgets.
+//!
+//! A [`StyledList`](super::StyledList) renders its items into a stream of [`DisplayLine`]s, and
+//! then uses a window function to pick the lines that fit on screen. The window functions here
+//! can be used with lines from any source, so a custom widget (e.g. one pane of a miller-column
+//! browser) gets the same scrolling behavior as the list.
+//!
+//! The contract between the lines and the window is the [`must_display`](DisplayLine::must_display)
+//! flag. The lines with the flag set are the selection, and must be a single contiguous run. The
+//! window keeps the selection in view: if the selection fits, all of it is shown, and if it doesn't
+//! the window starts at the first line of the selection with an [`item`](DisplayLine::item) (or
+//! the first line of the selection, if none have one). This holds whatever the remembered window
+//! position is. Lines without the flag are shown or dropped as the window moves.
+//!
+//! See `examples/raw_lines` for drawing the lines selected by the window into a [`Buffer`].
+//!
+//! [`Buffer`]: ratatui::buffer::Buffer
+use ratatui::{
+    style::Style,
+    text::{Span, Spans},
+};
+
+use super::window_type;
+
+/// A line of text to display in a list. A [`StyledList`](super::StyledList) creates one for
+/// each line of each [`ListItem`](super::ListItem), and for any separators between them.
+#[derive(Clone, Debug)]
+pub struct DisplayLine<'a> {
+    /// The style of the whole row the line is drawn in.
+    pub style: Style,
+    /// The text of the line.
+    pub line: Spans<'a>,
+    /// Is this line part of the selection? The window always shows these lines, see the
+    /// [module docs](self) for details.
+    pub must_display: bool,
+    /// The symbol drawn in the left indicator column.
+    pub left_indicator: &'static str,
+    /// The symbol drawn in the right indicator column.
+    pub right_indicator: &'static str,
+    /// Index of the item this line was rendered from, if any.
+    pub item: Option<usize>,
+    /// Badges to draw right aligned on this line.
+    pub badges: Vec<Span<'a>>,
+}
+
+impl<'a> DisplayLine<'a> {
+    /// Create a line displaying `line`, that is not part of the selection.
+    pub fn new<T>(line: T) -> Self
+    where
+        T: Into<Spans<'a>>,
+    {
+        Self {
+            style: Style::default(),
+            line: line.into(),
+            must_display: false,
+            left_indicator: " ",
+            right_indicator: " ",
+            item: None,
+            badges: Vec::new(),
+        }
+    }
+}
+
+/// Pick the lines to show in a window of `window_size` rows, scrolling to keep the selection in
+/// view. This is the window used by [`WindowType::SelectionScroll`](super::WindowType::SelectionScroll).
+///
+/// `window_first` is the index of the line at the top of the window in the previous render, and is
+/// updated to the index of the top line for this one. Keeping it between renders means the window
+/// only moves when the selection would otherwise be out of view.
+///
+/// Lines are consumed until the window is settled, so lines far below the window aren't visited.
+pub fn selection_scroll<'a, I>(
+    lines: I,
+    window_size: usize,
+    window_first: &mut usize,
+) -> impl Iterator<Item = DisplayLine<'a>>
+where
+    I: IntoIterator<Item = DisplayLine<'a>>,
+{
+    window_type::selection_scroll(lines, window_size, window_first)
+}
+
+/// Pick the lines to show in a window of `window_size` rows, so that the selection starts on row
+/// `at`. Rows before the start of the lines are filled with blank lines. This is the window used
+/// by [`WindowType::Fixed`](super::WindowType::Fixed).
+pub fn fixed<'a, I>(
+    lines: I,
+    at: usize,
+    window_size: usize,
+) -> impl Iterator<Item = DisplayLine<'a>>
+where
+    I: IntoIterator<Item = DisplayLine<'a>>,
+{
+    window_type::fixed(lines, at, window_size, &mut 0)
+}
@@ -1,4 +1,4 @@
-use ratatui::{style::Style, symbols::bar::HALF};
+use ratatui::{style::Style, symbols::bar::HALF, text::Spans};
 
 use super::DisplayLine;
 
@@ -47,10 +47,12 @@ impl<'a> Separator {
 
         DisplayLine {
             style: self.curr_style,
-            line: gen_line(self.width).into(),
+            line: gen_line(self.width),
             must_display,
-            left_indicator: HALF.into(),
-            right_indicator: HALF.into(),
+            left_indicator: HALF,
+            right_indicator: HALF,
+            item: None,
+            badges: Vec::new(),
         }
     }
 }
@@ -65,10 +67,14 @@ lazy_static::lazy_static! {
 }
 
 // Number of bytes in the HALF codepoint
-const HALF_SIZE: usize = HALF.as_bytes().len();
+const HALF_SIZE: usize = HALF.len();
 
 #[inline]
-fn gen_line(width: usize) -> &'static str {
-    // split takes the byte offset, but must be on codepoint boundaries
-    HALF_LINE.split_at(width * HALF_SIZE).0
+fn gen_line<'a>(width: usize) -> Spans<'a> {
+    // split takes the byte offset, but must be on codepoint boundaries. Very wide lists don't fit
+    // in the preallocated line, so they get their own.
+    match HALF_LINE.get(..width * HALF_SIZE) {
+        Some(line) => line.into(),
+        None => HALF.repeat(width).into(),
+    }
 }
@@ -0,0 +1,331 @@
+use std::collections::BTreeSet;
+
+use ratatui::text::{Span, Text};
+
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+use super::{ListItem, ListState};
+
+/// Guide drawn before a node that has siblings after it.
+const BRANCH: &str = "├─";
+/// Guide drawn before the last node among its siblings.
+const LAST_BRANCH: &str = "└─";
+/// Guide continuing an ancestor's branch past a node.
+const PIPE: &str = "│ ";
+/// Blank guide, for ancestors with no more siblings.
+const BLANK: &str = "  ";
+/// Marker for a node with children that is expanded.
+const EXPANDED: &str = "▾ ";
+/// Marker for a node with children that is collapsed.
+const COLLAPSED: &str = "▸ ";
+
+/// A node in a tree. Each node holds the [`ListItem`] that is displayed for it, and its children.
+///
+/// Trees are displayed by flattening the visible nodes into [`ListItem`]s with
+/// [`TreeState::flatten`], and rendering the result with a [`StyledList`](super::StyledList).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeItem<'a> {
+    item: ListItem<'a>,
+    children: Vec<TreeItem<'a>>,
+}
+
+impl<'a> TreeItem<'a> {
+    /// Create a leaf node displaying `content`.
+    pub fn new<T>(content: T) -> Self
+    where
+        T: Into<Text<'a>>,
+    {
+        Self::from(ListItem::new(content))
+    }
+
+    /// Add a child to this node.
+    pub fn child(mut self, child: TreeItem<'a>) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Set the children of this node, replacing any it already has.
+    pub fn children(mut self, children: Vec<TreeItem<'a>>) -> Self {
+        self.children = children;
+        self
+    }
+
+    /// Does this node have any children?
+    pub fn has_children(&self) -> bool {
+        !self.children.is_empty()
+    }
+}
+
+impl<'a> From<ListItem<'a>> for TreeItem<'a> {
+    fn from(item: ListItem<'a>) -> Self {
+        Self {
+            item,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// A node that was visible the last time the tree was flattened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VisibleNode {
+    path: Vec<usize>,
+    has_children: bool,
+}
+
+/// State for a tree displayed in a [`StyledList`](super::StyledList).
+///
+/// Nodes are identified by their path: the index of the node among its siblings, for each
+/// level of the tree starting at the roots. The set of expanded nodes is stored by path, so it
+/// will follow the position of a node rather than its content if the tree changes.
+///
+/// The wrapped [`ListState`] tracks the selection within the visible nodes, and is the state to
+/// render the [`StyledList`](super::StyledList) with.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct TreeState {
+    list: ListState,
+    expanded: BTreeSet<Vec<usize>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    visible: Vec<VisibleNode>,
+}
+
+impl TreeState {
+    /// Create a state with all nodes collapsed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The list state tracking the selection among visible nodes.
+    pub fn list(&self) -> &ListState {
+        &self.list
+    }
+
+    /// Mutable access to the list state, for navigation and rendering.
+    pub fn list_mut(&mut self) -> &mut ListState {
+        &mut self.list
+    }
+
+    /// Is the node at `path` expanded?
+    pub fn is_expanded(&self, path: &[usize]) -> bool {
+        self.expanded.contains(path)
+    }
+
+    /// The path of the selected node, if the tree has been flattened and is not empty.
+    pub fn selected_path(&self) -> Option<&[usize]> {
+        self.visible
+            .get(self.list.selected())
+            .map(|n| n.path.as_slice())
+    }
+
+    /// Expand the selected node. Does nothing if the node has no children.
+    pub fn expand(&mut self) {
+        if let Some(node) = self.visible.get(self.list.selected()) {
+            if node.has_children {
+                self.expanded.insert(node.path.clone());
+            }
+        }
+    }
+
+    /// Collapse the selected node. If the node is not expanded, the selection moves to its parent
+    /// instead.
+    pub fn collapse(&mut self) {
+        let path = match self.selected_path() {
+            Some(p) => p.to_vec(),
+            None => return,
+        };
+        if !self.expanded.remove(&path) {
+            self.goto_parent();
+        }
+    }
+
+    /// Expand the selected node if it is collapsed, and collapse it if it is expanded.
+    pub fn toggle(&mut self) {
+        let path = match self.selected_path() {
+            Some(p) => p.to_vec(),
+            None => return,
+        };
+        if !self.expanded.remove(&path) {
+            self.expand();
+        }
+    }
+
+    /// Select the parent of the selected node. Does nothing for root nodes.
+    pub fn goto_parent(&mut self) {
+        let parent = match self.selected_path() {
+            Some([parent @ .., _]) if !parent.is_empty() => parent.to_vec(),
+            _ => return,
+        };
+        if let Some(idx) = self.visible.iter().position(|n| n.path == parent) {
+            self.list.select(idx);
+        }
+    }
+
+    /// Flatten the visible nodes of the tree into [`ListItem`]s, decorated with indentation and
+    /// branch guides. The state records which nodes are visible so navigation methods work on
+    /// the next update, and the list state is resized to the number of visible nodes.
+    pub fn flatten<'a>(&mut self, roots: Vec<TreeItem<'a>>) -> Vec<ListItem<'a>> {
+        self.visible.clear();
+        let mut res = Vec::new();
+        let mut path = Vec::new();
+        let mut guides = String::new();
+        for (i, node) in roots.into_iter().enumerate() {
+            path.push(i);
+            self.flatten_node(node, &mut path, &mut guides, None, &mut res);
+            path.pop();
+        }
+        self.list.resize(res.len().max(1));
+        res
+    }
+
+    /// Flatten a node and its visible descendants. `guides` holds the guides drawn for this
+    /// node's ancestors, and `last` is whether the node is the last of its siblings (`None` for
+    /// roots, which have no branch guide).
+    fn flatten_node<'a>(
+        &mut self,
+        node: TreeItem<'a>,
+        path: &mut Vec<usize>,
+        guides: &mut String,
+        last: Option<bool>,
+        res: &mut Vec<ListItem<'a>>,
+    ) {
+        let has_children = node.has_children();
+        let expanded = has_children && self.expanded.contains(path.as_slice());
+
+        let (branch, continuation) = match last {
+            None => ("", ""),
+            Some(false) => (BRANCH, PIPE),
+            Some(true) => (LAST_BRANCH, BLANK),
+        };
+        let marker = match (has_children, expanded) {
+            (false, _) => BLANK,
+            (true, false) => COLLAPSED,
+            (true, true) => EXPANDED,
+        };
+
+        let mut item = node.item;
+        for (i, line) in item.content.lines.iter_mut().enumerate() {
+            let prefix = if i == 0 {
+                format!("{}{}{}", guides, branch, marker)
+            } else {
+                format!("{}{}{}", guides, continuation, BLANK)
+            };
+            line.0.insert(0, Span::raw(prefix));
+        }
+        res.push(item);
+        self.visible.push(VisibleNode {
+            path: path.clone(),
+            has_children,
+        });
+
+        if expanded {
+            let guides_len = guides.len();
+            guides.push_str(continuation);
+            let n_children = node.children.len();
+            for (i, child) in node.children.into_iter().enumerate() {
+                path.push(i);
+                self.flatten_node(child, path, guides, Some(i + 1 == n_children), res);
+                path.pop();
+            }
+            guides.truncate(guides_len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::text::Spans;
+
+    use super::*;
+
+    fn tree<'a>() -> Vec<TreeItem<'a>> {
+        vec![
+            TreeItem::new("src")
+                .child(TreeItem::new("lib.rs"))
+                .child(TreeItem::new("widgets").child(TreeItem::new("list.rs"))),
+            TreeItem::new("README"),
+        ]
+    }
+
+    fn lines(items: &[ListItem]) -> Vec<String> {
+        items
+            .iter()
+            .flat_map(|it| it.content.lines.iter())
+            .map(|l: &Spans| l.0.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn collapsed() {
+        let mut state = TreeState::new();
+        let items = state.flatten(tree());
+        assert_eq!(lines(&items), vec!["▸ src", "  README"]);
+        assert_eq!(state.list().size, 2);
+    }
+
+    #[test]
+    fn expanded_guides() {
+        let mut state = TreeState::new();
+        state.flatten(tree());
+        state.expand();
+        state.flatten(tree());
+        state.list_mut().select(2);
+        state.expand();
+        let items = state.flatten(tree());
+        assert_eq!(
+            lines(&items),
+            vec![
+                "▾ src",
+                "├─  lib.rs",
+                "└─▾ widgets",
+                "  └─  list.rs",
+                "  README"
+            ]
+        );
+    }
+
+    #[test]
+    fn multi_line_items() {
+        let mut state = TreeState::new();
+        let roots = vec![TreeItem::new("a")
+            .child(TreeItem::new("b\nc"))
+            .child(TreeItem::new("d"))];
+        state.flatten(roots.clone());
+        state.expand();
+        let items = state.flatten(roots);
+        assert_eq!(lines(&items), vec!["▾ a", "├─  b", "│   c", "└─  d"]);
+    }
+
+    #[test]
+    fn navigation() {
+        let mut state = TreeState::new();
+        state.flatten(tree());
+        // leaves and collapsed roots don't move on goto_parent
+        state.goto_parent();
+        assert_eq!(state.selected_path(), Some(&[0][..]));
+
+        state.expand();
+        assert!(state.is_expanded(&[0]));
+        state.flatten(tree());
+        state.list_mut().select(2);
+        state.expand();
+        state.flatten(tree());
+        state.list_mut().select(3);
+        assert_eq!(state.selected_path(), Some(&[0, 1, 0][..]));
+
+        // collapsing a leaf moves to the parent
+        state.collapse();
+        assert_eq!(state.selected_path(), Some(&[0, 1][..]));
+        // collapsing an expanded node collapses it in place
+        state.collapse();
+        assert!(!state.is_expanded(&[0, 1]));
+        assert_eq!(state.selected_path(), Some(&[0, 1][..]));
+        state.goto_parent();
+        assert_eq!(state.selected_path(), Some(&[0][..]));
+
+        state.toggle();
+        assert!(!state.is_expanded(&[0]));
+        state.toggle();
+        assert!(state.is_expanded(&[0]));
+    }
+}
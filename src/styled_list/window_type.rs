@@ -1,12 +1,85 @@
-use std::fmt::Display;
+use std::{collections::VecDeque, fmt::Display};
 
 use bounded_vec_deque::BoundedVecDeque;
 
-use super::{DisplayLine, ListState};
+use super::{DisplayLine, ListItem};
+
+/// The size of the display window storage. This is kept in the [`ListState`](super::ListState)
+/// between renders, so each render allocates its window once at the size the last one needed,
+/// rather than growing it line by line.
+#[derive(Debug, Default)]
+pub(super) struct WindowBuffer {
+    capacity: usize,
+}
+
+impl WindowBuffer {
+    /// Make the storage to fill with the lines of a render.
+    pub(super) fn take<'a>(&self) -> VecDeque<DisplayLine<'a>> {
+        VecDeque::with_capacity(self.capacity)
+    }
+
+    /// Remember the size of the storage once the lines are drawn.
+    pub(super) fn put(&mut self, lines: VecDeque<DisplayLine>) {
+        self.capacity = lines.capacity();
+    }
+}
+
+/// Bound the (empty) `lines` storage to `max_len` lines. Unlike
+/// [`BoundedVecDeque::from_unbounded`], this doesn't shrink storage larger than the bound.
+fn bounded(lines: VecDeque<DisplayLine>, max_len: usize) -> BoundedVecDeque<DisplayLine> {
+    let capacity = lines.capacity();
+    let mut lines = BoundedVecDeque::from_unbounded(lines, capacity);
+    lines.set_max_len(max_len);
+    lines
+}
+
+/// Drop the items before the `selected` item that can't be shown in a window of `window_size`
+/// rows, so no lines are made or styled for them. The items kept before the selection and the
+/// selected item itself are returned with the number of lines the dropped items would have made,
+/// and the items after the selection are left in `items`. When `separated` is set, each item is
+/// counted with the separator line before it.
+///
+/// Whatever the window type, the top of the window is never more than `window_size - 1` lines
+/// before the first line of the selection. So an item is dropped once the items after it, up to
+/// the selection, have at least `window_size` lines. This also keeps the separator before the
+/// first kept item out of the window, so it doesn't matter that it can't blend with the item
+/// before it.
+pub(super) fn skip_hidden<'a, I>(
+    items: &mut I,
+    selected: usize,
+    window_size: usize,
+    separated: bool,
+) -> (VecDeque<(usize, ListItem<'a>)>, usize)
+where
+    I: Iterator<Item = (usize, ListItem<'a>)>,
+{
+    let line_count = |item: &ListItem| item.height() as usize + separated as usize;
+    let mut kept = VecDeque::new();
+    let mut kept_lines = 0;
+    let mut skipped = 0;
+    for (i, item) in items {
+        kept_lines += line_count(&item);
+        kept.push_back((i, item));
+        if i == selected {
+            break;
+        }
+        while let Some((_, front)) = kept.front() {
+            let front_lines = line_count(front);
+            if kept_lines - front_lines < window_size {
+                break;
+            }
+            kept_lines -= front_lines;
+            skipped += front_lines;
+            kept.pop_front();
+        }
+    }
+    (kept, skipped)
+}
 
 /// A small state machine to track the display of selected items.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 enum SelectionState {
+    #[default]
     NotSeen,
     Started(usize),
     Complete,
@@ -23,12 +96,6 @@ impl SelectionState {
     }
 }
 
-impl Default for SelectionState {
-    fn default() -> Self {
-        SelectionState::NotSeen
-    }
-}
-
 /// Tracking for the display window used in selection scroll. The display window is the slice of
 /// lines that should be rendered to that screen. `top` is the first element of that slice.
 ///
@@ -38,10 +105,14 @@ impl Default for SelectionState {
 ///
 /// Restricting the window to an index prevents the top from going any further than that index.
 /// This is set to the first line of the selection, so that the selection can be displayed in full.
+/// If the selection starts with lines that aren't part of an item (e.g. the separator before the
+/// selected item), the restriction is moved to the first line of the item once it is seen, so
+/// the item is shown even when the window is too small for the whole selection.
 struct Window {
     goal: usize,
     top: usize,
     restriction: Option<usize>,
+    item_restricted: bool,
 }
 
 impl Window {
@@ -50,6 +121,15 @@ impl Window {
             goal,
             top: 0,
             restriction: None,
+            item_restricted: false,
+        }
+    }
+
+    /// Idempotent method to restrict the window to the first selected line of an item.
+    fn restrict_item(&mut self, line: &DisplayLine, index: usize) {
+        if !self.item_restricted && line.must_display && line.item.is_some() {
+            self.restriction = Some(index);
+            self.item_restricted = true;
         }
     }
 
@@ -93,23 +173,40 @@ impl Display for Window {
 }
 
 /// Line selector for [`WindowType::SelectionScroll`](super::WindowType::SelectionScroll).
+///
+/// `window_first` is the remembered position of the top of the window, and is updated to the
+/// position used for this render.
 pub(super) fn selection_scroll<'a, I>(
     items: I,
     window_size: usize,
-    list_state: &mut ListState,
-) -> <BoundedVecDeque<I::Item> as IntoIterator>::IntoIter
+    window_first: &mut usize,
+) -> std::collections::vec_deque::IntoIter<DisplayLine<'a>>
+where
+    I: IntoIterator<Item = DisplayLine<'a>>,
+{
+    selection_scroll_into(items, window_size, window_first, VecDeque::new()).into_iter()
+}
+
+/// [`selection_scroll`], filling the (empty) `lines` storage with the lines to display.
+pub(super) fn selection_scroll_into<'a, I>(
+    items: I,
+    window_size: usize,
+    window_first: &mut usize,
+    lines: VecDeque<DisplayLine<'a>>,
+) -> VecDeque<DisplayLine<'a>>
 where
     I: IntoIterator<Item = DisplayLine<'a>>,
 {
-    let mut window = Window::new(list_state.window_first);
+    let mut window = Window::new(*window_first);
     let mut sel_state = SelectionState::NotSeen;
 
     // This stores the lines that will be displayed.
-    let mut buffer = BoundedVecDeque::<I::Item>::new(window_size);
+    let mut buffer = bounded(lines, window_size);
 
     for (i, l) in items.into_iter().enumerate() {
         sel_state.toggle(l.must_display, i);
         window.restrict(sel_state);
+        window.restrict_item(&l, i);
         // Fill the window before advancing it.
         if !buffer.is_full() {
             buffer.push_back(l);
@@ -149,8 +246,8 @@ where
         }
     }
 
-    list_state.set_pos(window.top);
-    buffer.into_iter()
+    *window_first = window.top;
+    buffer.into_unbounded()
 }
 
 /// line selector for [`WindowType::Fixed`](super::WindowType::Fixed).
@@ -158,13 +255,28 @@ pub(super) fn fixed<'a, I>(
     items: I,
     at: usize,
     window_size: usize,
-    _list_state: &mut ListState,
-) -> <BoundedVecDeque<I::Item> as IntoIterator>::IntoIter
+    window_first: &mut usize,
+) -> std::collections::vec_deque::IntoIter<DisplayLine<'a>>
+where
+    I: IntoIterator<Item = DisplayLine<'a>>,
+{
+    fixed_into(items, at, window_size, window_first, VecDeque::new()).into_iter()
+}
+
+/// [`fixed`], filling the (empty) `lines` storage with the lines to display.
+pub(super) fn fixed_into<'a, I>(
+    items: I,
+    at: usize,
+    window_size: usize,
+    _window_first: &mut usize,
+    lines: VecDeque<DisplayLine<'a>>,
+) -> VecDeque<DisplayLine<'a>>
 where
     I: IntoIterator<Item = DisplayLine<'a>>,
 {
-    // TODO: what if at > window size? set "at" to window size that
-    // the window actually shows the selection?
+    // If the fixed position is past the end of the window, put the selection on the last row so
+    // that it is still shown.
+    let at = std::cmp::min(at, window_size.saturating_sub(1));
 
     let mut sel_state = SelectionState::default();
 
@@ -172,8 +284,8 @@ where
     // if the iterator encounters a scenario when the selection starts with
     // (e.g.) the first display line, the selection will still be drawn in the
     // correct place.
-    let mut buffer =
-        BoundedVecDeque::from_iter(std::iter::repeat(DisplayLine::filler("")).take(at), at);
+    let mut buffer = bounded(lines, at);
+    buffer.extend(std::iter::repeat_n(DisplayLine::filler(""), at));
 
     for (i, dl) in items.into_iter().enumerate() {
         sel_state.toggle(dl.must_display, i);
@@ -194,7 +306,21 @@ where
             }
         }
     }
-    buffer.into_iter()
+    buffer.into_unbounded()
+}
+
+/// Line selector for lists without a selection: the window always starts at the first line. The
+/// lines are put in the (empty) `lines` storage.
+pub(super) fn top<'a, I>(
+    items: I,
+    window_size: usize,
+    mut lines: VecDeque<DisplayLine<'a>>,
+) -> VecDeque<DisplayLine<'a>>
+where
+    I: IntoIterator<Item = DisplayLine<'a>>,
+{
+    lines.extend(items.into_iter().take(window_size));
+    lines
 }
 
 #[cfg(test)]
@@ -234,19 +360,199 @@ mod test {
                 style: Style::default(),
                 line: Spans::from(s),
                 must_display,
-                left_indicator: " ".into(),
-                right_indicator: " ".into(),
+                left_indicator: " ",
+                right_indicator: " ",
+                item: Some(i),
+                badges: Vec::new(),
             }
         })
     }
 
+    /// Lines `0..len`, with `sel` selected. The text of each line is its index.
+    fn numbered_lines<'a>(len: usize, sel: std::ops::Range<usize>) -> Vec<DisplayLine<'a>> {
+        (0..len)
+            .map(|i| DisplayLine {
+                style: Style::default(),
+                line: Spans::from(i.to_string()),
+                must_display: sel.contains(&i),
+                left_indicator: " ",
+                right_indicator: " ",
+                item: Some(i),
+                badges: Vec::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn selection_always_visible() {
+        // A small xorshift generator, so the cases are random but repeatable
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut rand = |n: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % n as u64) as usize
+        };
+        for _ in 0..5000 {
+            let len = rand(40) + 1;
+            let height = rand(12) + 1;
+            let sel_start = rand(len);
+            let sel_end = sel_start + 1 + rand(std::cmp::min(len - sel_start, 6));
+            let mut pos = rand(100);
+            let stale = pos;
+
+            let lines = numbered_lines(len, sel_start..sel_end);
+            let shown: Vec<usize> = selection_scroll(lines, height, &mut pos)
+                .map(|l| l.line.0[0].content.parse().unwrap())
+                .collect();
+            let case = format!(
+                "len {} height {} selection {}..{} pos {}: {:?}",
+                len, height, sel_start, sel_end, stale, shown
+            );
+
+            assert_eq!(shown.len(), std::cmp::min(height, len), "{}", case);
+            assert_eq!(shown[0], pos, "{}", case);
+            if sel_end - sel_start <= height {
+                assert!(shown.contains(&sel_start), "{}", case);
+                assert!(shown.contains(&(sel_end - 1)), "{}", case);
+            } else {
+                assert_eq!(shown[0], sel_start, "{}", case);
+            }
+        }
+    }
+
+    /// The lines of items with the given heights, from item `first` on. Separator lines are
+    /// labelled `s<item after>`, and item lines `<item>.<line>`.
+    fn item_lines<'a>(
+        heights: &[usize],
+        first: usize,
+        selected: usize,
+        separated: bool,
+    ) -> Vec<DisplayLine<'a>> {
+        let line = |label: String, must_display| DisplayLine {
+            must_display,
+            ..DisplayLine::new(label)
+        };
+        let mut lines = Vec::new();
+        for (i, height) in heights.iter().enumerate().skip(first) {
+            if separated {
+                lines.push(line(format!("s{}", i), i == selected || i == selected + 1));
+            }
+            for j in 0..*height {
+                lines.push(line(format!("{}.{}", i, j), i == selected));
+            }
+        }
+        if separated {
+            let i = heights.len();
+            lines.push(line(format!("s{}", i), i == selected + 1));
+        }
+        lines
+    }
+
+    #[test]
+    fn skipped_items_are_never_shown() {
+        // A small xorshift generator, so the cases are random but repeatable
+        let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut rand = |n: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % n as u64) as usize
+        };
+        // fillers have no spans
+        let labels = |lines: VecDeque<DisplayLine>| -> Vec<String> {
+            lines
+                .iter()
+                .map(|l| l.line.0.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+        for _ in 0..5000 {
+            // items can be empty, so there may be fewer lines than items
+            let len = rand(30) + 1;
+            let heights: Vec<usize> = (0..len).map(|_| rand(4)).collect();
+            let selected = rand(len);
+            let window_size = rand(8) + 1;
+            let separated = rand(2) == 0;
+            let pos = rand(60);
+            let case = format!(
+                "heights {:?} selected {} window {} separated {} pos {}",
+                heights, selected, window_size, separated, pos
+            );
+
+            let mut items = heights
+                .iter()
+                .map(|h| ListItem::new(vec![Spans::from("x"); *h]))
+                .enumerate();
+            let (kept, skipped) = skip_hidden(&mut items, selected, window_size, separated);
+            let first = kept[0].0;
+            let kept: Vec<usize> = kept.iter().map(|(i, _)| *i).collect();
+            assert_eq!(kept, (first..=selected).collect::<Vec<_>>(), "{}", case);
+            let next = Some(selected + 1).filter(|i| *i < len);
+            assert_eq!(items.next().map(|(i, _)| i), next, "{}", case);
+            let lines_before: usize = heights[..first]
+                .iter()
+                .map(|h| h + separated as usize)
+                .sum();
+            assert_eq!(skipped, lines_before, "{}", case);
+
+            // the windows over the kept lines match the windows over all of them
+            let all = || item_lines(&heights, 0, selected, separated);
+            let rest = || item_lines(&heights, first, selected, separated);
+            let mut all_pos = pos;
+            let mut rest_pos = pos.saturating_sub(skipped);
+            assert_eq!(
+                labels(selection_scroll_into(
+                    all(),
+                    window_size,
+                    &mut all_pos,
+                    VecDeque::new()
+                )),
+                labels(selection_scroll_into(
+                    rest(),
+                    window_size,
+                    &mut rest_pos,
+                    VecDeque::new()
+                )),
+                "{}",
+                case
+            );
+            assert_eq!(all_pos, rest_pos + skipped, "{}", case);
+            for at in 0..window_size {
+                assert_eq!(
+                    labels(fixed_into(all(), at, window_size, &mut 0, VecDeque::new())),
+                    labels(fixed_into(rest(), at, window_size, &mut 0, VecDeque::new())),
+                    "{} at {}",
+                    case,
+                    at
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn skips_items_before_window() {
+        let mut items = (0..1000).map(|i| ListItem::new(i.to_string())).enumerate();
+        let (kept, skipped) = skip_hidden(&mut items, 900, 10, false);
+        let kept: Vec<usize> = kept.iter().map(|(i, _)| *i).collect();
+        // only the items that can share the window with the selection are kept
+        assert_eq!(kept, (890..=900).collect::<Vec<_>>());
+        assert_eq!(skipped, 890);
+        // and the items after the selection are left for the window
+        assert_eq!(items.next().map(|(i, _)| i), Some(901));
+
+        // each separator is a line too
+        let mut items = (0..1000).map(|i| ListItem::new(i.to_string())).enumerate();
+        let (kept, skipped) = skip_hidden(&mut items, 900, 10, true);
+        assert_eq!(kept.front().map(|(i, _)| *i), Some(895));
+        assert_eq!(skipped, 2 * 895);
+    }
+
     #[test]
     fn starts_fitting() {
         // starts: |a B c| d e f g h i j
         // result: a B c
-        let mut state = ListState::new(10);
-        state.set_pos(0);
-        let res: Vec<DisplayLine> = selection_scroll(make_list(1, 1), 3, &mut state).collect();
+        let mut pos = 0;
+        let res: Vec<DisplayLine> = selection_scroll(make_list(1, 1), 3, &mut pos).collect();
 
         assert_eq!(res[0].line.0[0].content, "a");
         assert_eq!(res[1].line.0[0].content, "b");
@@ -261,9 +567,8 @@ mod test {
     fn fits_end() {
         // starts: |a b C| d e f g h i j
         // result: a b C
-        let mut state = ListState::new(10);
-        state.set_pos(0);
-        let res: Vec<DisplayLine> = selection_scroll(make_list(2, 2), 3, &mut state).collect();
+        let mut pos = 0;
+        let res: Vec<DisplayLine> = selection_scroll(make_list(2, 2), 3, &mut pos).collect();
 
         assert_eq!(res[0].line.0[0].content, "a");
         assert_eq!(res[1].line.0[0].content, "b");
@@ -278,9 +583,8 @@ mod test {
     fn slides_to_selection() {
         // starts: |a b c| D E f g h i j
         // result: c D E
-        let mut state = ListState::new(10);
-        state.set_pos(0);
-        let res: Vec<DisplayLine> = selection_scroll(make_list(3, 4), 3, &mut state).collect();
+        let mut pos = 0;
+        let res: Vec<DisplayLine> = selection_scroll(make_list(3, 4), 3, &mut pos).collect();
 
         assert_eq!(res[0].line.0[0].content, "c");
         assert_eq!(res[1].line.0[0].content, "d");
@@ -295,9 +599,8 @@ mod test {
     fn stops_at_fixed() {
         // starts: a b c D E |f g h| i j
         // result: D E f
-        let mut state = ListState::new(10);
-        state.set_pos(5);
-        let res: Vec<DisplayLine> = selection_scroll(make_list(3, 4), 3, &mut state).collect();
+        let mut pos = 5;
+        let res: Vec<DisplayLine> = selection_scroll(make_list(3, 4), 3, &mut pos).collect();
 
         assert_eq!(res[0].line.0[0].content, "d");
         assert_eq!(res[1].line.0[0].content, "e");
@@ -312,9 +615,8 @@ mod test {
     fn stops_at_fixed_sel_too_big() {
         // starts: a b c D E |F G h| i j
         // result: D E F
-        let mut state = ListState::new(10);
-        state.set_pos(5);
-        let res: Vec<DisplayLine> = selection_scroll(make_list(3, 6), 3, &mut state).collect();
+        let mut pos = 5;
+        let res: Vec<DisplayLine> = selection_scroll(make_list(3, 6), 3, &mut pos).collect();
 
         assert_eq!(res[0].line.0[0].content, "d");
         assert_eq!(res[1].line.0[0].content, "e");
@@ -329,9 +631,8 @@ mod test {
     fn stops_at_sliding_sel_too_big() {
         // starts: |a b c| D E F G h i j
         // result: D E F
-        let mut state = ListState::new(10);
-        state.set_pos(0);
-        let res: Vec<DisplayLine> = selection_scroll(make_list(3, 6), 3, &mut state).collect();
+        let mut pos = 0;
+        let res: Vec<DisplayLine> = selection_scroll(make_list(3, 6), 3, &mut pos).collect();
 
         assert_eq!(res[0].line.0[0].content, "d");
         assert_eq!(res[1].line.0[0].content, "e");
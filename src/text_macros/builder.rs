@@ -0,0 +1,203 @@
+use ratatui::{
+    style::Style,
+    text::{Span, Spans, Text},
+};
+
+use super::AddLines;
+
+/// Builds a [Text] with methods rather than the [`text!`](crate::text!) macro, e.g. in loops.
+///
+/// Lines are added with the same rules as `text!`, which is built on this, so each line can be
+/// anything `text!` takes.
+///
+/// ```
+/// # use extra_widgets::text_macros::TextBuilder;
+/// # use ratatui::style::{Color, Style};
+/// let mut builder = TextBuilder::new().line("Items:");
+/// for item in ["a", "b"] {
+///     builder = builder.line("- ").span(item);
+/// }
+/// let text = builder
+///     .blank()
+///     .styled_line(Style::default().fg(Color::Red), "done")
+///     .build();
+/// assert_eq!(text.height(), 5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TextBuilder<'a> {
+    text: Text<'a>,
+}
+
+impl<'a> TextBuilder<'a> {
+    /// Create a builder with no lines.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add lines, as a line of [`text!`](crate::text!) does.
+    pub fn line<T>(mut self, line: T) -> Self
+    where
+        Text<'a>: AddLines<T>,
+    {
+        self.text.add_lines(line);
+        self
+    }
+
+    /// Add lines with `style` under them, as [`line!`](crate::line!) does with a style: each span's
+    /// own style is patched over it.
+    pub fn styled_line<T>(mut self, style: Style, line: T) -> Self
+    where
+        Text<'a>: AddLines<T>,
+    {
+        let mut lines = Text::default();
+        lines.add_lines(line);
+        for span in lines.lines.iter_mut().flat_map(|line| line.0.iter_mut()) {
+            span.style = style.patch(span.style);
+        }
+        self.text.lines.append(&mut lines.lines);
+        self
+    }
+
+    /// Add a span to the end of the last line, starting one if there are no lines yet.
+    pub fn span<S>(mut self, span: S) -> Self
+    where
+        S: Into<Span<'a>>,
+    {
+        match self.text.lines.last_mut() {
+            Some(line) => line.0.push(span.into()),
+            None => self.text.lines.push(Spans::from(span.into())),
+        }
+        self
+    }
+
+    /// Add the lines of `text`, keeping their styles.
+    pub fn push_text(self, text: Text<'a>) -> Self {
+        self.line(text)
+    }
+
+    /// Add an empty line.
+    pub fn blank(mut self) -> Self {
+        self.text.lines.push(Spans::default());
+        self
+    }
+
+    /// Get the built text.
+    pub fn build(self) -> Text<'a> {
+        self.text
+    }
+}
+
+impl<'a> From<TextBuilder<'a>> for Text<'a> {
+    fn from(builder: TextBuilder<'a>) -> Self {
+        builder.build()
+    }
+}
+
+/// Make text of `lines`, anything [`text!`](crate::text!) takes as a line, with each line
+/// starting with `n` spaces and then `prefix`, if given. See [`indent!`](crate::indent!).
+///
+/// Strings with newlines are split into lines first, so every line is indented.
+pub fn indent<'a, T>(n: usize, lines: T, prefix: Option<Span<'a>>) -> Text<'a>
+where
+    Text<'a>: AddLines<T>,
+{
+    let mut text = Text::default();
+    text.add_lines(lines);
+    let lines = text.lines.into_iter().flat_map(split_newlines);
+    let lines = lines.map(|mut line| {
+        line.0.splice(
+            0..0,
+            std::iter::once(Span::raw(" ".repeat(n)))
+                .filter(|_| n > 0)
+                .chain(prefix.clone()),
+        );
+        line
+    });
+    Text::from(lines.collect::<Vec<_>>())
+}
+
+/// Split a line at the newlines in its spans, keeping their styles.
+fn split_newlines(line: Spans) -> Vec<Spans> {
+    let mut lines = vec![Spans::default()];
+    for span in line.0 {
+        if !span.content.contains('\n') {
+            lines.last_mut().unwrap().0.push(span);
+            continue;
+        }
+        for (i, part) in span.content.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Spans::default());
+            }
+            let part = part.strip_suffix('\r').unwrap_or(part);
+            if !part.is_empty() {
+                let part = Span::styled(part.to_string(), span.style);
+                lines.last_mut().unwrap().0.push(part);
+            }
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Modifier};
+
+    use super::*;
+
+    #[test]
+    fn builds() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let red = Style::default().fg(Color::Red);
+        let text = TextBuilder::new()
+            .span("a")
+            .span(Span::styled("b", bold))
+            .line("c\nd")
+            .blank()
+            .styled_line(red, vec![Span::raw("e"), Span::styled("f", bold)])
+            .push_text(Text::from("g"))
+            .line(None::<&str>)
+            .build();
+        assert_eq!(
+            text,
+            Text::from(vec![
+                Spans::from(vec![Span::raw("a"), Span::styled("b", bold)]),
+                Spans::from("c\nd"),
+                Spans::default(),
+                Spans::from(vec![
+                    Span::styled("e", red),
+                    Span::styled("f", red.patch(bold))
+                ]),
+                Spans::from("g"),
+            ])
+        );
+        assert_eq!(Text::from(TextBuilder::new()), Text::default());
+    }
+
+    #[test]
+    fn indents() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        assert_eq!(
+            indent(2, "a\r\n\nb", None),
+            Text::from(vec![
+                Spans::from(vec![Span::raw("  "), Span::raw("a")]),
+                Spans::from(vec![Span::raw("  ")]),
+                Spans::from(vec![Span::raw("  "), Span::raw("b")]),
+            ])
+        );
+        // newlines inside styled spans keep the style on each part
+        let gutter = Span::styled("│ ", bold);
+        let line = Spans::from(vec![Span::raw("x"), Span::styled("y\nz", bold)]);
+        assert_eq!(
+            indent(0, line, Some(gutter.clone())),
+            Text::from(vec![
+                Spans::from(vec![
+                    gutter.clone(),
+                    Span::raw("x"),
+                    Span::styled("y", bold)
+                ]),
+                Spans::from(vec![gutter.clone(), Span::styled("z", bold)]),
+            ])
+        );
+        assert_eq!(indent(4, None::<&str>, Some(gutter)), Text::default());
+    }
+}
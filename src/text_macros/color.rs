@@ -0,0 +1,147 @@
+use ratatui::style::Color;
+
+/// The names read by [`parse_color`], without separators.
+const NAMES: [(&str, Color); 21] = [
+    ("reset", Color::Reset),
+    ("black", Color::Black),
+    ("red", Color::Red),
+    ("green", Color::Green),
+    ("yellow", Color::Yellow),
+    ("blue", Color::Blue),
+    ("magenta", Color::Magenta),
+    ("cyan", Color::Cyan),
+    ("gray", Color::Gray),
+    ("grey", Color::Gray),
+    ("lightgray", Color::Gray),
+    ("lightgrey", Color::Gray),
+    ("darkgray", Color::DarkGray),
+    ("darkgrey", Color::DarkGray),
+    ("lightred", Color::LightRed),
+    ("lightgreen", Color::LightGreen),
+    ("lightyellow", Color::LightYellow),
+    ("lightblue", Color::LightBlue),
+    ("lightmagenta", Color::LightMagenta),
+    ("lightcyan", Color::LightCyan),
+    ("white", Color::White),
+];
+
+/// Read a color written by name, e.g. in a config file, returning `None` for unknown names.
+///
+/// The names of the [`Color`] variants are read in any case, with or without spaces, `_` or `-`
+/// between words, so `"light blue"`, `"light_blue"` and `"LightBlue"` are all
+/// [`Color::LightBlue`]. `grey` is read as `gray`, and hex colors starting with `#`, like
+/// `"#ff8000"`, are read by [`parse_hex`].
+///
+/// This is what [`fg!`](crate::fg!) and [`bg!`](crate::bg!) use for colors given as string
+/// literals. It's a `const fn`, so they can check those names at compile time.
+pub const fn parse_color(name: &str) -> Option<Color> {
+    let name = name.as_bytes();
+    if let [b'#', digits @ ..] = name {
+        return hex_digits(digits);
+    }
+    let mut i = 0;
+    while i < NAMES.len() {
+        if name_eq(name, NAMES[i].0.as_bytes()) {
+            return Some(NAMES[i].1);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Does `input` spell `name`, ignoring case and separators?
+const fn name_eq(input: &[u8], name: &[u8]) -> bool {
+    let (mut i, mut j) = (0, 0);
+    loop {
+        while i < input.len() && matches!(input[i], b' ' | b'_' | b'-') {
+            i += 1;
+        }
+        if i == input.len() || j == name.len() {
+            return i == input.len() && j == name.len();
+        }
+        if input[i].to_ascii_lowercase() != name[j] {
+            return false;
+        }
+        i += 1;
+        j += 1;
+    }
+}
+
+/// Read a hex color, like `"#ff8800"` or its short form `"#f80"`, as a [`Color::Rgb`], returning
+/// `None` if it isn't one. The `#` is optional, and the digits can be in any case.
+///
+/// This is what [`hex!`](crate::hex!) uses, and [`fg!`](crate::fg!) and [`bg!`](crate::bg!) for
+/// string literals starting with `#`. It's a `const fn`, so they can check colors at compile time.
+pub const fn parse_hex(hex: &str) -> Option<Color> {
+    match hex.as_bytes() {
+        [b'#', digits @ ..] => hex_digits(digits),
+        digits => hex_digits(digits),
+    }
+}
+
+/// Read the digits of a hex color, 3 or 6 of them.
+const fn hex_digits(digits: &[u8]) -> Option<Color> {
+    let short = match digits.len() {
+        3 => true,
+        6 => false,
+        _ => return None,
+    };
+    let mut rgb = [0; 3];
+    let mut i = 0;
+    while i < digits.len() {
+        let digit = match digits[i] {
+            b @ b'0'..=b'9' => b - b'0',
+            b @ b'a'..=b'f' => b - b'a' + 10,
+            b @ b'A'..=b'F' => b - b'A' + 10,
+            _ => return None,
+        };
+        if short {
+            rgb[i] = digit * 17;
+        } else {
+            rgb[i / 2] = rgb[i / 2] * 16 + digit;
+        }
+        i += 1;
+    }
+    Some(Color::Rgb(rgb[0], rgb[1], rgb[2]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        assert_eq!(parse_color("cyan"), Some(Color::Cyan));
+        assert_eq!(parse_color("light blue"), Some(Color::LightBlue));
+        assert_eq!(parse_color("Light_Blue"), Some(Color::LightBlue));
+        assert_eq!(parse_color("LightBlue"), Some(Color::LightBlue));
+        assert_eq!(parse_color("dark-grey"), Some(Color::DarkGray));
+        assert_eq!(parse_color(" white "), Some(Color::White));
+        assert_eq!(parse_color("#FF8000"), Some(Color::Rgb(255, 128, 0)));
+
+        assert_eq!(parse_color(""), None);
+        assert_eq!(parse_color("light"), None);
+        assert_eq!(parse_color("blue green"), None);
+        assert_eq!(parse_color("#ff80"), None);
+        assert_eq!(parse_color("#ff800g"), None);
+        // hex colors need a # to be told apart from names
+        assert_eq!(parse_color("ff8000"), None);
+    }
+
+    #[test]
+    fn hex() {
+        assert_eq!(parse_hex("#ff8800"), Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse_hex("FF8800"), Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse_hex("#f80"), Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(parse_hex("0aF"), Some(Color::Rgb(0, 170, 255)));
+        assert_eq!(parse_color("#f80"), Some(Color::Rgb(255, 136, 0)));
+
+        assert_eq!(parse_hex(""), None);
+        assert_eq!(parse_hex("#"), None);
+        assert_eq!(parse_hex("#ff88"), None);
+        assert_eq!(parse_hex("#ff88000"), None);
+        assert_eq!(parse_hex("##f80"), None);
+        assert_eq!(parse_hex("#ff88zz"), None);
+        assert_eq!(parse_hex("red"), None);
+    }
+}
@@ -0,0 +1,171 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Span, Spans},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Make a span for each grapheme of `text`, with foregrounds fading from `from` to `to`. See
+/// [`gradient!`](crate::gradient!).
+pub fn gradient(text: &str, from: Color, to: Color) -> Spans<'static> {
+    gradient_with(text, from, to, Style::fg)
+}
+
+/// Make a span for each grapheme of `text`, with backgrounds fading from `from` to `to`. See
+/// [`bg_gradient!`](crate::bg_gradient!).
+pub fn bg_gradient(text: &str, from: Color, to: Color) -> Spans<'static> {
+    gradient_with(text, from, to, Style::bg)
+}
+
+/// The colors [`rainbow`] cycles through by default.
+pub const RAINBOW: [Color; 6] = [
+    Color::Red,
+    Color::Yellow,
+    Color::Green,
+    Color::Cyan,
+    Color::Blue,
+    Color::Magenta,
+];
+
+/// Make a span for each grapheme of `text`, with foregrounds cycling through `palette`. See
+/// [`rainbow!`](crate::rainbow!).
+///
+/// If `skip_whitespace` is set, whitespace is left unstyled and doesn't use up a color, so each
+/// word continues the cycle where the last left off. An empty palette leaves the text unstyled.
+pub fn rainbow(text: &str, palette: &[Color], skip_whitespace: bool) -> Spans<'static> {
+    let mut colored = 0;
+    per_grapheme(text, |_, grapheme| {
+        if palette.is_empty() || skip_whitespace && grapheme.chars().all(char::is_whitespace) {
+            return Style::default();
+        }
+        let color = palette[colored % palette.len()];
+        colored += 1;
+        Style::default().fg(color)
+    })
+}
+
+/// Make a span for each grapheme of `text`, setting the interpolated colors with `set`.
+fn gradient_with(
+    text: &str,
+    from: Color,
+    to: Color,
+    set: fn(Style, Color) -> Style,
+) -> Spans<'static> {
+    let steps = text.graphemes(true).count().saturating_sub(1).max(1);
+    per_grapheme(text, |i, _| {
+        let color = match (from, to) {
+            (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => Color::Rgb(
+                mix(r1, r2, i, steps),
+                mix(g1, g2, i, steps),
+                mix(b1, b2, i, steps),
+            ),
+            _ => from,
+        };
+        set(Style::default(), color)
+    })
+}
+
+/// Make a span for each grapheme of `text`, so emoji and combining marks stay whole, styled by
+/// `style` from the grapheme and its index.
+fn per_grapheme<F>(text: &str, mut style: F) -> Spans<'static>
+where
+    F: FnMut(usize, &str) -> Style,
+{
+    let spans = text
+        .graphemes(true)
+        .enumerate()
+        .map(|(i, grapheme)| Span::styled(grapheme.to_string(), style(i, grapheme)))
+        .collect::<Vec<_>>();
+    Spans::from(spans)
+}
+
+/// The channel `step` steps of `steps` from `from` to `to`, rounded.
+fn mix(from: u8, to: u8, step: usize, steps: usize) -> u8 {
+    let mixed = (usize::from(from) * (steps - step) + usize::from(to) * step + steps / 2) / steps;
+    mixed as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colors(spans: &Spans, fg: bool) -> Vec<Option<Color>> {
+        spans
+            .0
+            .iter()
+            .map(|span| if fg { span.style.fg } else { span.style.bg })
+            .collect()
+    }
+
+    #[test]
+    fn interpolates() {
+        let spans = gradient("abc", Color::Rgb(0, 0, 255), Color::Rgb(255, 100, 0));
+        assert_eq!(
+            colors(&spans, true),
+            [
+                Some(Color::Rgb(0, 0, 255)),
+                Some(Color::Rgb(128, 50, 128)),
+                Some(Color::Rgb(255, 100, 0)),
+            ]
+        );
+        assert_eq!(spans.width(), 3);
+
+        let spans = bg_gradient("ab", Color::Rgb(10, 10, 10), Color::Rgb(20, 20, 20));
+        assert_eq!(
+            colors(&spans, false),
+            [Some(Color::Rgb(10, 10, 10)), Some(Color::Rgb(20, 20, 20))]
+        );
+        assert_eq!(spans.0[0].style.fg, None);
+
+        // a single grapheme takes the start color
+        let spans = gradient("a", Color::Rgb(0, 0, 0), Color::Rgb(255, 255, 255));
+        assert_eq!(colors(&spans, true), [Some(Color::Rgb(0, 0, 0))]);
+        assert_eq!(gradient("", Color::Red, Color::Blue), Spans::default());
+    }
+
+    #[test]
+    fn rainbow_cycles() {
+        let palette = [Color::Red, Color::Blue];
+        let spans = rainbow("ab c", &palette, true);
+        assert_eq!(
+            colors(&spans, true),
+            [Some(Color::Red), Some(Color::Blue), None, Some(Color::Red)]
+        );
+        let spans = rainbow("ab c", &palette, false);
+        assert_eq!(
+            colors(&spans, true),
+            [
+                Some(Color::Red),
+                Some(Color::Blue),
+                Some(Color::Red),
+                Some(Color::Blue)
+            ]
+        );
+        let spans = rainbow("👍🏽x", &RAINBOW, true);
+        assert_eq!(spans.0[0].content, "👍🏽");
+        assert_eq!(colors(&spans, true), [Some(RAINBOW[0]), Some(RAINBOW[1])]);
+        assert_eq!(
+            rainbow("ab", &[], true),
+            Spans::from(vec![Span::raw("a"), Span::raw("b")])
+        );
+    }
+
+    #[test]
+    fn fallback_and_graphemes() {
+        let spans = gradient("ab", Color::Red, Color::Rgb(0, 0, 0));
+        assert_eq!(colors(&spans, true), [Some(Color::Red), Some(Color::Red)]);
+
+        // emoji and combining marks stay in one span
+        let text = "e\u{301}👍🏽!";
+        let spans = gradient(text, Color::Rgb(0, 0, 0), Color::Rgb(2, 2, 2));
+        let contents: Vec<_> = spans.0.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(contents, ["e\u{301}", "👍🏽", "!"]);
+        assert_eq!(
+            colors(&spans, true),
+            [
+                Some(Color::Rgb(0, 0, 0)),
+                Some(Color::Rgb(1, 1, 1)),
+                Some(Color::Rgb(2, 2, 2)),
+            ]
+        );
+    }
+}
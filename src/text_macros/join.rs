@@ -0,0 +1,87 @@
+use ratatui::text::{Span, Spans};
+
+/// Joining spans into a line without [`line!`](crate::line!), e.g.
+/// `bold!("a").join(" ").join(italic!("b"))`.
+///
+/// Joining a [Span] makes a [Spans] of it and the other span, and joining a [Spans] appends the
+/// other span to it. Both take anything that implements [`Into<Span>`](Span), borrowing text with
+/// the same lifetime, like the rest of ratatui.
+pub trait SpanJoin<'a> {
+    /// Join `other` after this, making a line.
+    fn join<S: Into<Span<'a>>>(self, other: S) -> Spans<'a>;
+}
+
+impl<'a> SpanJoin<'a> for Span<'a> {
+    fn join<S: Into<Span<'a>>>(self, other: S) -> Spans<'a> {
+        Spans::from(vec![self, other.into()])
+    }
+}
+
+impl<'a> SpanJoin<'a> for Spans<'a> {
+    fn join<S: Into<Span<'a>>>(mut self, other: S) -> Spans<'a> {
+        self.0.push(other.into());
+        self
+    }
+}
+
+/// Make a line of `spans` with `separator` between them, e.g. `join_spans(" / ", path)` for a
+/// breadcrumb path.
+pub fn join_spans<'a, S, I>(separator: S, spans: I) -> Spans<'a>
+where
+    S: Into<Span<'a>>,
+    I: IntoIterator,
+    I::Item: Into<Span<'a>>,
+{
+    let separator = separator.into();
+    let mut joined = Vec::new();
+    for (i, span) in spans.into_iter().enumerate() {
+        if i > 0 {
+            joined.push(separator.clone());
+        }
+        joined.push(span.into());
+    }
+    Spans::from(joined)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Modifier, Style};
+
+    use super::*;
+
+    #[test]
+    fn join() {
+        let bold = Span::styled("a", Style::default().add_modifier(Modifier::BOLD));
+        let line = bold.clone().join(" ").join(String::from("b"));
+        assert_eq!(
+            line,
+            Spans::from(vec![bold.clone(), Span::raw(" "), Span::raw("b")])
+        );
+        let line = line.join(bold.clone());
+        assert_eq!(line.0.len(), 4);
+        assert_eq!(line.0[3], bold);
+
+        // borrowed text keeps its lifetime
+        let owned = String::from("borrowed");
+        let line: Spans = Span::raw(owned.as_str()).join(&owned[..4]);
+        assert_eq!(line.width(), 12);
+    }
+
+    #[test]
+    fn join_spans_separates() {
+        let sep = Span::styled(" › ", Style::default().add_modifier(Modifier::DIM));
+        let path = ["home", "user", "src"];
+        assert_eq!(
+            join_spans(sep.clone(), path),
+            Spans::from(vec![
+                Span::raw("home"),
+                sep.clone(),
+                Span::raw("user"),
+                sep.clone(),
+                Span::raw("src"),
+            ])
+        );
+        assert_eq!(join_spans(sep.clone(), ["one"]), Spans::from("one"));
+        assert_eq!(join_spans(sep, Vec::<Span>::new()), Spans::default());
+    }
+}
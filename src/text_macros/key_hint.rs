@@ -0,0 +1,149 @@
+use std::borrow::Cow;
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Span, Spans},
+};
+
+/// The look of the key hints made by [`key_hint!`](crate::key_hint!) and
+/// [`key_hints!`](crate::key_hints!), like `j - down, k - up` in a control bar.
+///
+/// By default keys are bold, descriptions italic, a key is joined to its description by `" - "`,
+/// and hints are joined by `", "`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyHintStyle {
+    key: Style,
+    description: Style,
+    key_separator: String,
+    hint_separator: String,
+}
+
+impl Default for KeyHintStyle {
+    fn default() -> Self {
+        Self {
+            key: Style::default().add_modifier(Modifier::BOLD),
+            description: Style::default().add_modifier(Modifier::ITALIC),
+            key_separator: " - ".to_string(),
+            hint_separator: ", ".to_string(),
+        }
+    }
+}
+
+impl KeyHintStyle {
+    /// Create the default look.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the style of keys, e.g. a background color to draw them as keycaps.
+    pub fn key(mut self, style: Style) -> Self {
+        self.key = style;
+        self
+    }
+
+    /// Set the style of descriptions.
+    pub fn description(mut self, style: Style) -> Self {
+        self.description = style;
+        self
+    }
+
+    /// Set the text between a key and its description.
+    pub fn key_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.key_separator = separator.into();
+        self
+    }
+
+    /// Set the text between hints.
+    pub fn hint_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.hint_separator = separator.into();
+        self
+    }
+
+    /// Make the spans of a single hint: the key, the key separator and the description.
+    pub fn hint<'a, K, D>(&self, key: K, description: D) -> Vec<Span<'a>>
+    where
+        K: Into<Cow<'a, str>>,
+        D: Into<Cow<'a, str>>,
+    {
+        vec![
+            Span::styled(key, self.key),
+            Span::raw(self.key_separator.clone()),
+            Span::styled(description, self.description),
+        ]
+    }
+
+    /// Make a line of hints from `(key, description)` pairs, joined by the hint separator.
+    pub fn hints<'a, I, K, D>(&self, hints: I) -> Spans<'a>
+    where
+        I: IntoIterator<Item = (K, D)>,
+        K: Into<Cow<'a, str>>,
+        D: Into<Cow<'a, str>>,
+    {
+        self.join(
+            hints
+                .into_iter()
+                .map(|(key, description)| self.hint(key, description)),
+        )
+    }
+
+    /// Join hints made by [`hint`](Self::hint) into a line, with the hint separator between them.
+    pub fn join<'a, I>(&self, hints: I) -> Spans<'a>
+    where
+        I: IntoIterator<Item = Vec<Span<'a>>>,
+    {
+        let mut spans = Vec::new();
+        for (i, mut hint) in hints.into_iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(self.hint_separator.clone()));
+            }
+            spans.append(&mut hint);
+        }
+        Spans::from(spans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+
+    use super::*;
+
+    #[test]
+    fn hints() {
+        let style = KeyHintStyle::new();
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let italic = Style::default().add_modifier(Modifier::ITALIC);
+        assert_eq!(
+            style.hints([("j", "down"), ("k", "up")]),
+            Spans::from(vec![
+                Span::styled("j", bold),
+                Span::raw(" - "),
+                Span::styled("down", italic),
+                Span::raw(", "),
+                Span::styled("k", bold),
+                Span::raw(" - "),
+                Span::styled("up", italic),
+            ])
+        );
+        assert_eq!(style.hints(Vec::<(&str, &str)>::new()), Spans::default());
+
+        let keycap = Style::default().bg(Color::Gray).fg(Color::Black);
+        let style = style
+            .key(keycap)
+            .description(Style::default())
+            .key_separator(" ")
+            .hint_separator("  ");
+        assert_eq!(
+            style.hints([("q", "quit"), ("?", "help")]),
+            Spans::from(vec![
+                Span::styled("q", keycap),
+                Span::raw(" "),
+                Span::raw("quit"),
+                Span::raw("  "),
+                Span::styled("?", keycap),
+                Span::raw(" "),
+                Span::raw("help"),
+            ])
+        );
+    }
+}
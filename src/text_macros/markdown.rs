@@ -0,0 +1,218 @@
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Span, Spans},
+};
+
+/// The emphasis markers, longest first so `**` isn't read as two `*`.
+const MARKERS: [(&str, Modifier); 4] = [
+    ("**", Modifier::BOLD),
+    ("~~", Modifier::CROSSED_OUT),
+    ("*", Modifier::ITALIC),
+    ("_", Modifier::ITALIC),
+];
+
+/// The characters that can be escaped with a `\`.
+const ESCAPABLE: [char; 5] = ['\\', '*', '_', '`', '~'];
+
+/// A piece of the input, before markers are paired.
+enum Token {
+    Text(String),
+    Code(String),
+    /// A marker from [`MARKERS`], with whether it could open or close emphasis.
+    Marker {
+        index: usize,
+        open: bool,
+        close: bool,
+    },
+}
+
+/// What a marker turned out to do once paired.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Open,
+    Close,
+    Literal,
+}
+
+/// Style a line of markdown-ish text, with inline code reversed. See [`parse_inline_with`] for
+/// the syntax read.
+pub fn parse_inline(input: &str) -> Spans<'static> {
+    parse_inline_with(input, Style::default().add_modifier(Modifier::REVERSED))
+}
+
+/// Style a line of markdown-ish text into spans, reading:
+/// * `**bold**`
+/// * `*italic*` or `_italic_`
+/// * `~~strikethrough~~`
+/// * `` `code` ``, styled with `code_style` and shown as it is, without reading markers in it
+///
+/// Emphasis can be nested, like `**bold _and italic_**`. As in markdown, a marker only opens
+/// before text and closes after it, so `2 * 3 * 4` is left alone, and `_` doesn't mark emphasis
+/// inside words, like in `snake_case_name`. A `\` before any of `\*_`~` writes it literally.
+///
+/// Markers that are never closed, and a `` ` `` without a closing one, are kept as text, so any
+/// input can be read.
+pub fn parse_inline_with(input: &str, code_style: Style) -> Spans<'static> {
+    let tokens = tokenize(input);
+
+    // pair each closing marker with the latest open one of its kind
+    let mut roles = vec![Role::Literal; tokens.len()];
+    let mut opened: [Vec<usize>; MARKERS.len()] = Default::default();
+    for (i, token) in tokens.iter().enumerate() {
+        if let Token::Marker { index, open, close } = *token {
+            match opened[index].pop() {
+                Some(j) if close => {
+                    roles[j] = Role::Open;
+                    roles[i] = Role::Close;
+                }
+                popped => {
+                    opened[index].extend(popped);
+                    if open {
+                        opened[index].push(i);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut push = |text: &str, style: Style| match spans.last_mut() {
+        Some(last) if last.style == style => last.content.to_mut().push_str(text),
+        _ => spans.push(Span::styled(text.to_string(), style)),
+    };
+    let mut depth = [0; MARKERS.len()];
+    for (token, role) in tokens.iter().zip(roles) {
+        let style = MARKERS
+            .iter()
+            .zip(depth)
+            .filter(|(_, depth)| *depth > 0)
+            .fold(Style::default(), |style, ((_, modifier), _)| {
+                style.add_modifier(*modifier)
+            });
+        match token {
+            Token::Text(text) => push(text, style),
+            Token::Code(code) => push(code, style.patch(code_style)),
+            Token::Marker { index, .. } => match role {
+                Role::Open => depth[*index] += 1,
+                Role::Close => depth[*index] -= 1,
+                Role::Literal => push(MARKERS[*index].0, style),
+            },
+        }
+    }
+    Spans::from(spans)
+}
+
+/// Split the input into text, code and markers, resolving escapes.
+fn tokenize(input: &str) -> Vec<Token> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\\' && chars.get(i + 1).is_some_and(|c| ESCAPABLE.contains(c)) {
+            text.push(chars[i + 1]);
+            i += 2;
+            continue;
+        }
+        if c == '`' {
+            if let Some(len) = chars[i + 1..].iter().position(|c| *c == '`') {
+                tokens.push(Token::Text(std::mem::take(&mut text)));
+                tokens.push(Token::Code(chars[i + 1..i + 1 + len].iter().collect()));
+                i += len + 2;
+                continue;
+            }
+        }
+        let marker = MARKERS.iter().position(|(marker, _)| {
+            marker
+                .chars()
+                .enumerate()
+                .all(|(j, m)| chars.get(i + j) == Some(&m))
+        });
+        let Some(index) = marker else {
+            text.push(c);
+            i += 1;
+            continue;
+        };
+        let len = MARKERS[index].0.len();
+        let before = i.checked_sub(1).map(|j| chars[j]);
+        let after = chars.get(i + len).copied();
+        let mut open = after.is_some_and(|c| !c.is_whitespace());
+        let mut close = before.is_some_and(|c| !c.is_whitespace());
+        if MARKERS[index].0 == "_" {
+            open &= !before.is_some_and(char::is_alphanumeric);
+            close &= !after.is_some_and(char::is_alphanumeric);
+        }
+        tokens.push(Token::Text(std::mem::take(&mut text)));
+        tokens.push(Token::Marker { index, open, close });
+        i += len;
+    }
+    tokens.push(Token::Text(text));
+    tokens.retain(|token| !matches!(token, Token::Text(text) if text.is_empty()));
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+
+    use super::*;
+
+    fn styled(text: &str, modifier: Modifier) -> Span<'static> {
+        Span::styled(text.to_string(), Style::default().add_modifier(modifier))
+    }
+
+    #[test]
+    fn emphasis() {
+        assert_eq!(
+            parse_inline("This is **bold**, _italic_, ~~gone~~ and `code`"),
+            Spans::from(vec![
+                Span::raw("This is "),
+                styled("bold", Modifier::BOLD),
+                Span::raw(", "),
+                styled("italic", Modifier::ITALIC),
+                Span::raw(", "),
+                styled("gone", Modifier::CROSSED_OUT),
+                Span::raw(" and "),
+                styled("code", Modifier::REVERSED),
+            ])
+        );
+        assert_eq!(
+            parse_inline("**bold *both***"),
+            Spans::from(vec![
+                styled("bold ", Modifier::BOLD),
+                styled("both", Modifier::BOLD | Modifier::ITALIC),
+            ])
+        );
+        // code isn't read as markdown, and takes the emphasis around it
+        let code = Style::default().fg(Color::Yellow);
+        assert_eq!(
+            parse_inline_with("**see `a*b*c`**", code),
+            Spans::from(vec![
+                styled("see ", Modifier::BOLD),
+                Span::styled("a*b*c", code.add_modifier(Modifier::BOLD)),
+            ])
+        );
+    }
+
+    #[test]
+    fn literals() {
+        let raw = |text: &str| Spans::from(text.to_string());
+        assert_eq!(
+            parse_inline(r"\*not italic\* \_ \\ \n"),
+            raw(r"*not italic* _ \ \n")
+        );
+        assert_eq!(parse_inline("2 * 3 * 4"), raw("2 * 3 * 4"));
+        assert_eq!(parse_inline("snake_case_name"), raw("snake_case_name"));
+        // unterminated markers are kept
+        assert_eq!(parse_inline("**open"), raw("**open"));
+        assert_eq!(parse_inline("`code"), raw("`code"));
+        assert_eq!(parse_inline("~~"), raw("~~"));
+        assert_eq!(parse_inline("a\\"), raw("a\\"));
+        assert_eq!(parse_inline(""), Spans::default());
+        assert_eq!(
+            parse_inline("*a **b*"),
+            Spans::from(vec![styled("a **b", Modifier::ITALIC)])
+        );
+    }
+}
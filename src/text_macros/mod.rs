@@ -1,7 +1,32 @@
 //! macros for building and styling text for tui.
 
+mod builder;
+mod color;
+mod gradient;
+mod join;
+mod key_hint;
+mod markdown;
+mod pad;
+mod truncate;
+mod wrap;
+
+pub use builder::{indent, TextBuilder};
+pub use color::{parse_color, parse_hex};
+pub use gradient::{bg_gradient, gradient, rainbow, RAINBOW};
+pub use join::{join_spans, SpanJoin};
+pub use key_hint::KeyHintStyle;
+pub use markdown::{parse_inline, parse_inline_with};
+pub use pad::{pad, repeat_to_width};
+pub use truncate::{truncate_line, truncate_line_at};
+pub use wrap::wrap_line;
+
+pub use crate::truncate::TruncateAt;
+
 /// styles text into a span with the bold modifier set. The argument must evaluate to something
 /// that implements [`Into<Span>`](ratatui::text::Span)
+///
+/// A format string and its arguments can be passed instead, as with [`format!`], e.g.
+/// `bold!("{} items", n)`. A lone string is used as it is, so `bold!("{n}")` isn't formatted.
 #[macro_export]
 macro_rules! bold {
     ($e:expr) => {{
@@ -9,10 +34,16 @@ macro_rules! bold {
         s.style = s.style.add_modifier(::ratatui::style::Modifier::BOLD);
         s
     }};
+    ($fmt:literal, $($args:tt)+) => {
+        $crate::bold!(::std::format!($fmt, $($args)+))
+    };
 }
 
 /// styles text into a span with the italic modifier set. The argument must evaluate to something
 /// that implements [`Into<Span>`](ratatui::text::Span)
+///
+/// A format string and its arguments can be passed instead, as with [`format!`], e.g.
+/// `italic!("{} items", n)`. A lone string is used as it is, so `italic!("{n}")` isn't formatted.
 #[macro_export]
 macro_rules! italic {
     ($e:expr) => {{
@@ -20,10 +51,19 @@ macro_rules! italic {
         s.style = s.style.add_modifier(::ratatui::style::Modifier::ITALIC);
         s
     }};
+    ($fmt:literal, $($args:tt)+) => {
+        $crate::italic!(::std::format!($fmt, $($args)+))
+    };
 }
 
 /// styles text into a span with the underlined modifier set. The argument must evaluate to something
 /// that implements [`Into<Span>`](ratatui::text::Span)
+///
+/// A format string and its arguments can be passed instead, as with [`format!`], e.g.
+/// `underlined!("{} items", n)`. A lone string is used as it is, so `underlined!("{n}")` isn't formatted.
+///
+/// The underline is drawn in the text's foreground color, as the version of ratatui used here has
+/// no separate underline color.
 #[macro_export]
 macro_rules! underlined {
     ($e:expr) => {{
@@ -31,28 +71,305 @@ macro_rules! underlined {
         s.style = s.style.add_modifier(::ratatui::style::Modifier::UNDERLINED);
         s
     }};
+    ($fmt:literal, $($args:tt)+) => {
+        $crate::underlined!(::std::format!($fmt, $($args)+))
+    };
+}
+
+/// styles text into a span with the dim modifier set. The argument must evaluate to something
+/// that implements [`Into<Span>`](ratatui::text::Span)
+#[macro_export]
+macro_rules! dim {
+    ($e:expr) => {{
+        let mut s = ::ratatui::text::Span::from($e);
+        s.style = s.style.add_modifier(::ratatui::style::Modifier::DIM);
+        s
+    }};
+}
+
+/// styles text into a span with the reversed modifier set, swapping its foreground and background.
+/// The argument must evaluate to something that implements [`Into<Span>`](ratatui::text::Span)
+#[macro_export]
+macro_rules! reversed {
+    ($e:expr) => {{
+        let mut s = ::ratatui::text::Span::from($e);
+        s.style = s.style.add_modifier(::ratatui::style::Modifier::REVERSED);
+        s
+    }};
+}
+
+/// styles text into a span with the slow blink modifier set. The argument must evaluate to something
+/// that implements [`Into<Span>`](ratatui::text::Span)
+///
+/// Many terminals don't blink text, or blink it at a single rate, so this shouldn't be the only way
+/// something stands out.
+#[macro_export]
+macro_rules! blink {
+    ($e:expr) => {{
+        let mut s = ::ratatui::text::Span::from($e);
+        s.style = s.style.add_modifier(::ratatui::style::Modifier::SLOW_BLINK);
+        s
+    }};
+}
+
+/// styles text into a span with the rapid blink modifier set. The argument must evaluate to
+/// something that implements [`Into<Span>`](ratatui::text::Span)
+///
+/// Fewer terminals support rapid blinking than [`blink!`](crate::blink!), and those that don't
+/// may blink the text slowly, or not at all.
+#[macro_export]
+macro_rules! rapid_blink {
+    ($e:expr) => {{
+        let mut s = ::ratatui::text::Span::from($e);
+        s.style = s
+            .style
+            .add_modifier(::ratatui::style::Modifier::RAPID_BLINK);
+        s
+    }};
+}
+
+/// removes the bold modifier from a span's style, keeping the rest of it. The argument must
+/// evaluate to something that implements [`Into<Span>`](ratatui::text::Span)
+///
+/// The span's style then also removes bold from any style it is patched over.
+#[macro_export]
+macro_rules! not_bold {
+    ($e:expr) => {{
+        let mut s = ::ratatui::text::Span::from($e);
+        s.style = s.style.remove_modifier(::ratatui::style::Modifier::BOLD);
+        s
+    }};
+}
+
+/// removes the italic modifier from a span's style, keeping the rest of it. See
+/// [`not_bold!`](crate::not_bold!).
+#[macro_export]
+macro_rules! not_italic {
+    ($e:expr) => {{
+        let mut s = ::ratatui::text::Span::from($e);
+        s.style = s.style.remove_modifier(::ratatui::style::Modifier::ITALIC);
+        s
+    }};
+}
+
+/// removes the underlined modifier from a span's style, keeping the rest of it. See
+/// [`not_bold!`](crate::not_bold!).
+#[macro_export]
+macro_rules! not_underlined {
+    ($e:expr) => {{
+        let mut s = ::ratatui::text::Span::from($e);
+        s.style = s
+            .style
+            .remove_modifier(::ratatui::style::Modifier::UNDERLINED);
+        s
+    }};
+}
+
+/// resets a span's style, keeping its text. The argument must evaluate to something that
+/// implements [`Into<Span>`](ratatui::text::Span)
+#[macro_export]
+macro_rules! plain {
+    ($e:expr) => {{
+        let mut s = ::ratatui::text::Span::from($e);
+        s.style = ::ratatui::style::Style::default();
+        s
+    }};
 }
 
 /// styles text into a span with the foreground set. The first argument must evaluate to something
 /// that implements [`Into<Span>`](ratatui::text::Span), and the second a [`Color`](ratatui::style::Color)
+///
+/// To format the text, pass the color first, followed by a format string and at least one argument,
+/// as with [`format!`], e.g. `fg!(Color::Red, "err: {}", e)`. The color leads because the format
+/// arguments take up the rest of the list.
+///
+/// The color can also be a string literal naming it, or a hex color like `"#ff8800"`, read by
+/// [`parse_color`](crate::text_macros::parse_color) when compiling, so unknown names don't
+/// compile:
+///
+/// ```
+/// # use extra_widgets::fg;
+/// # use ratatui::style::Color;
+/// assert_eq!(fg!("hi", "light blue"), fg!("hi", Color::LightBlue));
+/// ```
+///
+/// ```compile_fail
+/// # use extra_widgets::fg;
+/// let span = fg!("hi", "shiny");
+/// ```
 #[macro_export]
 macro_rules! fg {
+    ($t:expr, $c:literal) => {
+        $crate::fg!($t, $crate::__color_literal!($c))
+    };
     ($t:expr, $c: expr) => {{
         let mut s = ::ratatui::text::Span::from($t);
         s.style = s.style.fg($c);
         s
     }};
+    ($c:literal, $fmt:literal, $($args:tt)+) => {
+        $crate::fg!(
+            ::std::format!($fmt, $($args)+),
+            $crate::__color_literal!($c)
+        )
+    };
+    ($c:expr, $fmt:literal, $($args:tt)+) => {
+        $crate::fg!(::std::format!($fmt, $($args)+), $c)
+    };
 }
 
 /// Styles text into a span with the background set. The first argument must evaluate to something
 /// that implements [`Into<Span>`](ratatui::text::Span), and the second a [Color](ratatui::style::Color)
+///
+/// Like [`fg!`](crate::fg!), the text can be formatted by passing the color first, e.g.
+/// `bg!(Color::Red, "{} failed", n)`, and the color can be a string literal naming it.
 #[macro_export]
 macro_rules! bg {
+    ($t:expr, $c:literal) => {
+        $crate::bg!($t, $crate::__color_literal!($c))
+    };
     ($t:expr, $c: expr) => {{
         let mut s = ::ratatui::text::Span::from($t);
         s.style = s.style.bg($c);
         s
     }};
+    ($c:literal, $fmt:literal, $($args:tt)+) => {
+        $crate::bg!(
+            ::std::format!($fmt, $($args)+),
+            $crate::__color_literal!($c)
+        )
+    };
+    ($c:expr, $fmt:literal, $($args:tt)+) => {
+        $crate::bg!(::std::format!($fmt, $($args)+), $c)
+    };
+}
+
+/// styles text into a span with a list of attributes, rather than nesting the other macros. The
+/// first argument must evaluate to something that implements [`Into<Span>`](ratatui::text::Span),
+/// and is followed by the attributes, separated by commas:
+/// * the modifiers `bold`, `dim`, `italic`, `underlined`, `reversed`, `blink`, `rapid_blink`,
+///   `crossed_out` and `hidden`
+/// * `fg = color` and `bg = color`, with a [Color](ratatui::style::Color) or a color name read by
+///   [`parse_color`](crate::text_macros::parse_color)
+/// * `style = style`, patching in a [Style](ratatui::style::Style), e.g. one chosen at runtime
+///
+/// The attributes are applied in order, so a `style` at the end can override the others.
+///
+/// ```
+/// # use extra_widgets::styled;
+/// # use ratatui::style::{Color, Modifier, Style};
+/// let warning = Style::default().add_modifier(Modifier::UNDERLINED);
+/// let span = styled!("hi", bold, italic, fg = Color::Red, bg = Color::Black, style = warning);
+/// assert_eq!(
+///     span.style,
+///     Style::default()
+///         .fg(Color::Red)
+///         .bg(Color::Black)
+///         .add_modifier(Modifier::BOLD | Modifier::ITALIC | Modifier::UNDERLINED)
+/// );
+/// ```
+///
+/// Unknown attributes don't compile:
+///
+/// ```compile_fail
+/// # use extra_widgets::styled;
+/// let span = styled!("hi", shiny);
+/// ```
+#[macro_export]
+macro_rules! styled {
+    (@attrs $s:ident;) => {};
+    (@attrs $s:ident; fg = $c:literal $(, $($rest:tt)*)?) => {
+        $crate::styled!(@attrs $s; fg = $crate::__color_literal!($c) $(, $($rest)*)?);
+    };
+    (@attrs $s:ident; bg = $c:literal $(, $($rest:tt)*)?) => {
+        $crate::styled!(@attrs $s; bg = $crate::__color_literal!($c) $(, $($rest)*)?);
+    };
+    (@attrs $s:ident; fg = $c:expr $(, $($rest:tt)*)?) => {
+        $s.style = $s.style.fg($c);
+        $crate::styled!(@attrs $s; $($($rest)*)?);
+    };
+    (@attrs $s:ident; bg = $c:expr $(, $($rest:tt)*)?) => {
+        $s.style = $s.style.bg($c);
+        $crate::styled!(@attrs $s; $($($rest)*)?);
+    };
+    (@attrs $s:ident; style = $style:expr $(, $($rest:tt)*)?) => {
+        $s.style = $s.style.patch($style);
+        $crate::styled!(@attrs $s; $($($rest)*)?);
+    };
+    (@attrs $s:ident; $name:ident $(, $($rest:tt)*)?) => {
+        $s.style = $s.style.add_modifier($crate::styled!(@modifier $name));
+        $crate::styled!(@attrs $s; $($($rest)*)?);
+    };
+    (@modifier bold) => { ::ratatui::style::Modifier::BOLD };
+    (@modifier dim) => { ::ratatui::style::Modifier::DIM };
+    (@modifier italic) => { ::ratatui::style::Modifier::ITALIC };
+    (@modifier underlined) => { ::ratatui::style::Modifier::UNDERLINED };
+    (@modifier reversed) => { ::ratatui::style::Modifier::REVERSED };
+    (@modifier blink) => { ::ratatui::style::Modifier::SLOW_BLINK };
+    (@modifier rapid_blink) => { ::ratatui::style::Modifier::RAPID_BLINK };
+    (@modifier crossed_out) => { ::ratatui::style::Modifier::CROSSED_OUT };
+    (@modifier hidden) => { ::ratatui::style::Modifier::HIDDEN };
+    (@modifier $other:ident) => {
+        compile_error!(concat!(
+            "unknown styled! attribute `",
+            stringify!($other),
+            "`, expected bold, dim, italic, underlined, reversed, blink, rapid_blink, crossed_out, \
+             hidden, fg = .., bg = .. or style = .."
+        ))
+    };
+    ($e:expr $(,)?) => {
+        ::ratatui::text::Span::from($e)
+    };
+    ($e:expr, $($attrs:tt)+) => {{
+        let mut s = ::ratatui::text::Span::from($e);
+        $crate::styled!(@attrs s; $($attrs)+);
+        s
+    }};
+}
+
+/// Creates a [`Color::Rgb`](ratatui::style::Color::Rgb) from a hex color string literal, like
+/// `"#ff8800"` or `"#f80"`, with or without the `#`. The color is read when compiling, so typos
+/// don't compile. Use [`parse_hex`](crate::text_macros::parse_hex) for colors only known at
+/// runtime.
+///
+/// ```
+/// # use extra_widgets::hex;
+/// # use ratatui::style::Color;
+/// assert_eq!(hex!("#ff8800"), Color::Rgb(255, 136, 0));
+/// assert_eq!(hex!("f80"), Color::Rgb(255, 136, 0));
+/// ```
+///
+/// ```compile_fail
+/// # use extra_widgets::hex;
+/// let color = hex!("#ff880");
+/// ```
+#[macro_export]
+macro_rules! hex {
+    ($c:literal) => {{
+        const COLOR: ::ratatui::style::Color = match $crate::text_macros::parse_hex($c) {
+            ::std::option::Option::Some(color) => color,
+            ::std::option::Option::None => {
+                ::std::panic!(::std::concat!("`", $c, "` isn't a hex color"))
+            }
+        };
+        COLOR
+    }};
+}
+
+/// Resolves a color name given to [fg!](crate::fg!) or [bg!](crate::bg!) at compile time, failing to
+/// compile for unknown names. This is a helper for those macros, and should not be used directly.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __color_literal {
+    ($c:literal) => {{
+        const COLOR: ::ratatui::style::Color = match $crate::text_macros::parse_color($c) {
+            ::std::option::Option::Some(color) => color,
+            ::std::option::Option::None => {
+                ::std::panic!(::std::concat!("unknown color `", $c, "`"))
+            }
+        };
+        COLOR
+    }};
 }
 
 /// Trait to allow all the overloading of the add_lines method
@@ -73,6 +390,18 @@ impl<'a> AddLines<String> for ::ratatui::text::Text<'a> {
     }
 }
 
+impl<'a> AddLines<&'a String> for ::ratatui::text::Text<'a> {
+    fn add_lines(&mut self, to_add: &'a String) {
+        self.lines.push(to_add.as_str().into());
+    }
+}
+
+impl<'a> AddLines<std::borrow::Cow<'a, str>> for ::ratatui::text::Text<'a> {
+    fn add_lines(&mut self, to_add: std::borrow::Cow<'a, str>) {
+        self.lines.push(::ratatui::text::Span::raw(to_add).into());
+    }
+}
+
 impl<'a> AddLines<::ratatui::text::Span<'a>> for ::ratatui::text::Text<'a> {
     fn add_lines(&mut self, to_add: ::ratatui::text::Span<'a>) {
         self.lines.push(to_add.into());
@@ -91,7 +420,83 @@ impl<'a> AddLines<Vec<::ratatui::text::Spans<'a>>> for ::ratatui::text::Text<'a>
     }
 }
 
+impl<'a> AddLines<Vec<::ratatui::text::Span<'a>>> for ::ratatui::text::Text<'a> {
+    fn add_lines(&mut self, to_add: Vec<::ratatui::text::Span<'a>>) {
+        self.lines.push(to_add.into());
+    }
+}
+
+impl<'a, 'b> AddLines<&'b [::ratatui::text::Span<'a>]> for ::ratatui::text::Text<'a> {
+    fn add_lines(&mut self, to_add: &'b [::ratatui::text::Span<'a>]) {
+        self.lines.push(to_add.to_vec().into());
+    }
+}
+
+impl<'a> AddLines<::ratatui::text::Text<'a>> for ::ratatui::text::Text<'a> {
+    fn add_lines(&mut self, mut to_add: ::ratatui::text::Text<'a>) {
+        self.lines.append(&mut to_add.lines);
+    }
+}
+
+impl<'a, T> AddLines<Option<T>> for ::ratatui::text::Text<'a>
+where
+    ::ratatui::text::Text<'a>: AddLines<T>,
+{
+    fn add_lines(&mut self, to_add: Option<T>) {
+        if let Some(to_add) = to_add {
+            self.add_lines(to_add);
+        }
+    }
+}
+
+impl<'a, I, F> AddLines<std::iter::Map<I, F>> for ::ratatui::text::Text<'a>
+where
+    std::iter::Map<I, F>: Iterator<Item = ::ratatui::text::Spans<'a>>,
+{
+    fn add_lines(&mut self, to_add: std::iter::Map<I, F>) {
+        self.lines.extend(to_add);
+    }
+}
+
+impl<'a, I, F> AddLines<std::iter::FilterMap<I, F>> for ::ratatui::text::Text<'a>
+where
+    std::iter::FilterMap<I, F>: Iterator<Item = ::ratatui::text::Spans<'a>>,
+{
+    fn add_lines(&mut self, to_add: std::iter::FilterMap<I, F>) {
+        self.lines.extend(to_add);
+    }
+}
+
+/// The lines of an iterator, made by [`lines`].
+#[derive(Debug, Clone)]
+pub struct Lines<I>(I);
+
+/// Add a line for each item of `iter` in [text!](crate::text!) or a [`TextBuilder`], e.g.
+/// `text! { "Files:"; lines(names.iter().copied().rev()) }`. The items can be anything that
+/// implements [`Into<Spans>`](ratatui::text::Spans).
+pub fn lines<I>(iter: I) -> Lines<I::IntoIter>
+where
+    I: IntoIterator,
+{
+    Lines(iter.into_iter())
+}
+
+impl<'a, I> AddLines<Lines<I>> for ::ratatui::text::Text<'a>
+where
+    I: Iterator,
+    I::Item: Into<::ratatui::text::Spans<'a>>,
+{
+    fn add_lines(&mut self, to_add: Lines<I>) {
+        self.lines.extend(to_add.0.map(Into::into));
+    }
+}
+
 /// Create a [`Vec<Spans>`](ratatui::text::Spans) from lines of a string separated by '\n'
+///
+/// Lines are split as by [`str::lines`], so `\r\n` endings are dropped along with the `\n`, and a
+/// trailing newline doesn't add an empty last line. A [Style](ratatui::style::Style) can be given
+/// after the string, e.g. `split!(quote, Style::default().add_modifier(Modifier::DIM))`, to style
+/// each line with it.
 #[macro_export]
 macro_rules! split {
     ($e:expr) => {{
@@ -99,38 +504,288 @@ macro_rules! split {
             .map(|l| ::ratatui::text::Spans::from(l))
             .collect::<Vec<::ratatui::text::Spans>>()
     }};
+    ($e:expr, $style:expr) => {{
+        let style: ::ratatui::style::Style = $style;
+        $e.lines()
+            .map(|l| ::ratatui::text::Spans::from(::ratatui::text::Span::styled(l, style)))
+            .collect::<Vec<::ratatui::text::Spans>>()
+    }};
+}
+
+/// Create a [Spans](ratatui::text::Spans) from markdown-ish text, like
+/// `md!("This is **bold**, _italic_, ~~gone~~ and `code`")`, styling inline code with the
+/// [Style](ratatui::style::Style) given after the text, or reversed without one. The text can be
+/// any `&str`, including one only known at runtime, and is read by
+/// [`parse_inline_with`](crate::text_macros::parse_inline_with), which describes the syntax.
+#[macro_export]
+macro_rules! md {
+    ($e:expr $(,)?) => {
+        $crate::text_macros::parse_inline(::std::convert::AsRef::<str>::as_ref(&$e))
+    };
+    ($e:expr, $code:expr $(,)?) => {
+        $crate::text_macros::parse_inline_with(::std::convert::AsRef::<str>::as_ref(&$e), $code)
+    };
+}
+
+/// Create the spans of a key hint for a control bar, like `j - down`, with the key bold and the
+/// description italic. A [`KeyHintStyle`](crate::text_macros::KeyHintStyle) can be given first,
+/// followed by a `;`, to change the look, e.g. `key_hint!(hint_style; "j", "down")`.
+///
+/// Makes a `Vec<Span>`, which [`text!`](crate::text!) adds as a line, and which can be joined with
+/// other hints by [`KeyHintStyle::join`](crate::text_macros::KeyHintStyle::join). Use
+/// [`key_hints!`](crate::key_hints!) to make a line of hints at once.
+#[macro_export]
+macro_rules! key_hint {
+    ($key:expr, $description:expr $(,)?) => {
+        $crate::key_hint!($crate::text_macros::KeyHintStyle::default(); $key, $description)
+    };
+    ($style:expr; $key:expr, $description:expr $(,)?) => {
+        $crate::text_macros::KeyHintStyle::hint(&$style, $key, $description)
+    };
+}
+
+/// Create a [Spans](ratatui::text::Spans) of key hints for a control bar from `(key, description)`
+/// pairs, like `key_hints![("j", "down"), ("k", "up")]` for `j - down, k - up`. As with
+/// [`key_hint!`](crate::key_hint!), a [`KeyHintStyle`](crate::text_macros::KeyHintStyle) can be
+/// given first, followed by a `;`, to change the look, including the separator between hints.
+#[macro_export]
+macro_rules! key_hints {
+    ($(($key:expr, $description:expr)),* $(,)?) => {
+        $crate::key_hints![$crate::text_macros::KeyHintStyle::default(); $(($key, $description)),*]
+    };
+    ($style:expr; $(($key:expr, $description:expr)),* $(,)?) => {{
+        let style: $crate::text_macros::KeyHintStyle = $style;
+        style.join(::std::vec![$(style.hint($key, $description)),*])
+    }};
+}
+
+/// Pads text into a span `width` columns wide, with the text on the left, or cuts it with a `…` if
+/// it's wider. The first argument must evaluate to something that implements
+/// [`Into<Span>`](ratatui::text::Span), and keeps its style, which the padding takes too. Widths
+/// are measured in columns, so double width text lines up. The same as
+/// [`pad_right!`](crate::pad_right!); see [`pad`](crate::text_macros::pad).
+#[macro_export]
+macro_rules! pad {
+    ($e:expr, $width:expr) => {
+        $crate::pad_right!($e, $width)
+    };
+}
+
+/// Pads text into a span `width` columns wide with spaces on the left, aligning the text right.
+/// See [`pad!`](crate::pad!).
+#[macro_export]
+macro_rules! pad_left {
+    ($e:expr, $width:expr) => {
+        $crate::text_macros::pad($e, $width, ::ratatui::layout::Alignment::Right)
+    };
+}
+
+/// Pads text into a span `width` columns wide with spaces on the right, aligning the text left.
+/// See [`pad!`](crate::pad!).
+#[macro_export]
+macro_rules! pad_right {
+    ($e:expr, $width:expr) => {
+        $crate::text_macros::pad($e, $width, ::ratatui::layout::Alignment::Left)
+    };
+}
+
+/// Pads text into a span `width` columns wide with spaces on both sides, centering the text, with
+/// the odd space on the right. See [`pad!`](crate::pad!).
+#[macro_export]
+macro_rules! pad_center {
+    ($e:expr, $width:expr) => {
+        $crate::text_macros::pad($e, $width, ::ratatui::layout::Alignment::Center)
+    };
+}
+
+/// Creates a span of text repeated `count` times, e.g. `repeat!('─', 10)` for a rule. The text can
+/// be a `char`, `&str` or `String`, and a [Style](ratatui::style::Style) can be given after the
+/// count to style the span. A count of 0 gives an empty span.
+///
+/// The text is repeated as it is, so `"━━"` repeated 10 times is 20 columns wide.
+/// Use [`repeat_to_width!`](crate::repeat_to_width!) to fill a number of columns instead.
+#[macro_export]
+macro_rules! repeat {
+    ($fill:expr, $count:expr $(,)?) => {
+        $crate::repeat!($fill, $count, ::ratatui::style::Style::default())
+    };
+    ($fill:expr, $count:expr, $style:expr $(,)?) => {
+        ::ratatui::text::Span::styled(
+            ::std::iter::repeat($fill)
+                .take($count)
+                .collect::<::std::string::String>(),
+            $style,
+        )
+    };
+}
+
+/// Creates a span of text repeated to fill `width` columns, e.g. `repeat_to_width!('━', area.width
+/// as usize)`, measuring double width text in columns. Like [`repeat!`](crate::repeat!), the text
+/// can be a `char`, `&str` or `String`, and a [Style](ratatui::style::Style) can be given last.
+/// See [`repeat_to_width`](crate::text_macros::repeat_to_width) for fills that don't divide the
+/// width.
+#[macro_export]
+macro_rules! repeat_to_width {
+    ($fill:expr, $width:expr $(,)?) => {
+        $crate::repeat_to_width!($fill, $width, ::ratatui::style::Style::default())
+    };
+    ($fill:expr, $width:expr, $style:expr $(,)?) => {
+        ::ratatui::text::Span::styled(
+            $crate::text_macros::repeat_to_width(
+                &::std::string::ToString::to_string(&$fill),
+                $width,
+            ),
+            $style,
+        )
+    };
+}
+
+/// Creates a [Spans](ratatui::text::Spans) of text whose foreground fades between two colors,
+/// e.g. `gradient!("SOME TITLE", Color::Rgb(255, 0, 0), Color::Rgb(0, 0, 255))` for a header.
+///
+/// Each grapheme is its own span, so emoji and combining marks keep their parts together, and
+/// the colors are interpolated linearly from the first grapheme to the last. The fade needs two
+/// [Rgb](ratatui::style::Color::Rgb) colors, and other colors give all the text the first color.
+/// The text can be anything that is [`AsRef<str>`], and is read by
+/// [`gradient`](crate::text_macros::gradient).
+#[macro_export]
+macro_rules! gradient {
+    ($e:expr, $from:expr, $to:expr $(,)?) => {
+        $crate::text_macros::gradient(::std::convert::AsRef::<str>::as_ref(&$e), $from, $to)
+    };
+}
+
+/// Creates a [Spans](ratatui::text::Spans) of text whose background fades between two colors.
+/// Otherwise the same as [`gradient!`](crate::gradient!).
+#[macro_export]
+macro_rules! bg_gradient {
+    ($e:expr, $from:expr, $to:expr $(,)?) => {
+        $crate::text_macros::bg_gradient(::std::convert::AsRef::<str>::as_ref(&$e), $from, $to)
+    };
+}
+
+/// Creates a [Spans](ratatui::text::Spans) of text with each grapheme's foreground taking the next
+/// color of a palette, e.g. `rainbow!("party time")`. Like [`gradient!`](crate::gradient!), each
+/// grapheme is its own span.
+///
+/// The palette is [`RAINBOW`](crate::text_macros::RAINBOW) unless a slice of colors is given
+/// after the text, e.g. `rainbow!(s, &[Color::Red, Color::White])`. Whitespace is left unstyled
+/// and skipped over by the colors, unless `false` is given after the palette. See
+/// [`rainbow`](crate::text_macros::rainbow).
+#[macro_export]
+macro_rules! rainbow {
+    ($e:expr $(,)?) => {
+        $crate::rainbow!($e, &$crate::text_macros::RAINBOW)
+    };
+    ($e:expr, $palette:expr $(,)?) => {
+        $crate::rainbow!($e, $palette, true)
+    };
+    ($e:expr, $palette:expr, $skip_whitespace:expr $(,)?) => {
+        $crate::text_macros::rainbow(
+            ::std::convert::AsRef::<str>::as_ref(&$e),
+            $palette,
+            $skip_whitespace,
+        )
+    };
 }
 
 /// Create a single [Spans](ratatui::text::Spans) from many
 /// [Span](ratatui::text::Span) structs. Useful with [`text!`](crate::text!)
 /// for having multiple stylings in a single line
+///
+/// A [Style](ratatui::style::Style) for the whole line can be given before the spans, followed by
+/// a `;`, e.g. `line![Style::default().bg(Color::Blue); "a", bold!("b")]`. Each span's own style
+/// is patched over it, so the spans keep their styles, and take the line's for anything they
+/// don't set. As [Spans](ratatui::text::Spans) have no style of their own, the style only covers
+/// the spans, not the rest of the row.
 #[macro_export]
 macro_rules! line {
-    ($($e:expr),* $(,)?) => {{
-        let mut res = ::ratatui::text::Spans::default();
-        $(res.0.push(::ratatui::text::Span::from($e));)*;
+    ($($e:expr),* $(,)?) => {
+        ::ratatui::text::Spans::from(::std::vec![$(::ratatui::text::Span::from($e)),*])
+    };
+    ($style:expr; $($e:expr),* $(,)?) => {{
+        let style: ::ratatui::style::Style = $style;
+        let mut res = $crate::line!($($e),*);
+        for span in &mut res.0 {
+            span.style = style.patch(span.style);
+        }
         res
     }};
 }
 
+/// Creates a [Text](ratatui::text::Text) of lines indented by `n` spaces, e.g. for trees or quoted
+/// blocks. The lines can be anything a line of [`text!`](crate::text!) can be, including a
+/// [Text](ratatui::text::Text) or a string with newlines, and every line is indented.
+///
+/// A span can be given after the lines to draw after the spaces on each line, e.g. a gutter bar
+/// like `indent!(2, quote, fg!("│ ", Color::DarkGray))`. See
+/// [`indent`](crate::text_macros::indent).
+#[macro_export]
+macro_rules! indent {
+    ($n:expr, $lines:expr $(,)?) => {
+        $crate::text_macros::indent($n, $lines, ::std::option::Option::None)
+    };
+    ($n:expr, $lines:expr, $prefix:expr $(,)?) => {
+        $crate::text_macros::indent(
+            $n,
+            $lines,
+            ::std::option::Option::Some(::ratatui::text::Span::from($prefix)),
+        )
+    };
+}
+
+/// Cuts text to at most `width` columns, ending it with `…`, or the ellipsis given after the
+/// width, if anything was removed, e.g. `truncate!(bold!(title), 20)` or
+/// `truncate!(path, 20, "...")`. The text must evaluate to something that implements
+/// [`Into<Spans>`](ratatui::text::Spans), and makes a [Spans](ratatui::text::Spans) keeping its
+/// styles. Graphemes are never split; see [`truncate_line`](crate::text_macros::truncate_line).
+#[macro_export]
+macro_rules! truncate {
+    ($e:expr, $width:expr $(,)?) => {
+        $crate::truncate!($e, $width, "…")
+    };
+    ($e:expr, $width:expr, $ellipsis:expr $(,)?) => {
+        $crate::text_macros::truncate_line(&::ratatui::text::Spans::from($e), $width, $ellipsis)
+    };
+}
+
+/// Word wraps text into a `Vec<Spans>` of lines at most `width` columns wide, e.g.
+/// `wrap!(bold!("some long text"), 20)`, keeping the styles of spans across the breaks. The text
+/// must evaluate to something that implements [`Into<Spans>`](ratatui::text::Spans), and the width
+/// to a `u16`. Newlines in the text also break lines.
+///
+/// Unlike a wrapped [Paragraph](ratatui::widgets::Paragraph), the lines are made up front, so they
+/// can be counted, or made into list items. See
+/// [`wrap_line`](crate::text_macros::wrap_line) for how lines are broken.
+#[macro_export]
+macro_rules! wrap {
+    ($e:expr, $width:expr $(,)?) => {
+        $crate::text_macros::wrap_line(&::ratatui::text::Spans::from($e), $width)
+    };
+}
+
 /// Creates a `Vec<Spans>` from each line of the enclosed block
+///
+/// Each line can be a `&str`, `String`, `&String` or `Cow<str>`, a [Span](ratatui::text::Span), a
+/// [Spans](ratatui::text::Spans), a `Vec<Span>` or `&[Span]` joined into one line, or a
+/// `Vec<Spans>` or [Text](ratatui::text::Text) of several lines. A line can also be an
+/// [Option] of any of those, adding nothing when it is `None`, or the [`map`](Iterator::map) or
+/// [`filter_map`](Iterator::filter_map) of an iterator into `Spans`, adding a line for each item.
+/// Other iterators can be wrapped in [`lines`](crate::text_macros::lines).
+///
+/// To build text in a loop, use a [`TextBuilder`](crate::text_macros::TextBuilder), which this is
+/// built on.
 #[macro_export]
 macro_rules! text {
-    ($t:expr) => {
-        res.push(Spans::from($t));
+    ($($t:expr);* $(;)?) => {
+        $crate::text_macros::TextBuilder::new()$(.line($t))*.build()
     };
-    ($($t:expr);* $(;)?) => {{
-        use $crate::text_macros::AddLines;
-        let mut res = ::ratatui::text::Text::default();
-        $(res.add_lines($t);)*
-        res
-    }};
 }
 
 #[cfg(test)]
 mod tests {
     use ratatui::{
-        style::{Modifier, Style},
+        style::{Color, Modifier, Style},
         text::{Span, Spans, Text},
     };
 
@@ -155,6 +810,203 @@ mod tests {
         assert_eq!(expected, test);
     }
 
+    #[test]
+    fn dim() {
+        let expected = Span::styled("foo", Style::default().add_modifier(Modifier::DIM));
+        let test = dim!("foo");
+        assert_eq!(expected, test);
+    }
+
+    #[test]
+    fn dim_bold() {
+        let expected = Span::styled(
+            "x",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .add_modifier(Modifier::DIM),
+        );
+        let test = dim!(bold!("x"));
+        assert_eq!(expected, test);
+        assert_eq!(expected, bold!(dim!("x")));
+    }
+
+    #[test]
+    fn reversed() {
+        let expected = Span::styled("foo", Style::default().add_modifier(Modifier::REVERSED));
+        let test = reversed!("foo");
+        assert_eq!(expected, test);
+    }
+
+    #[test]
+    fn reversed_colors() {
+        let expected = Span::styled(
+            "foo",
+            Style::default()
+                .fg(Color::Yellow)
+                .bg(Color::Blue)
+                .add_modifier(Modifier::REVERSED),
+        );
+        assert_eq!(
+            expected,
+            reversed!(fg!(bg!("foo", Color::Blue), Color::Yellow))
+        );
+        assert_eq!(
+            expected,
+            fg!(bg!(reversed!("foo"), Color::Blue), Color::Yellow)
+        );
+        assert_eq!(
+            expected,
+            bg!(reversed!(fg!("foo", Color::Yellow)), Color::Blue)
+        );
+    }
+
+    #[test]
+    fn blink() {
+        let expected = Span::styled("foo", Style::default().add_modifier(Modifier::SLOW_BLINK));
+        let test = blink!("foo");
+        assert_eq!(expected, test);
+    }
+
+    #[test]
+    fn rapid_blink() {
+        let expected = Span::styled("foo", Style::default().add_modifier(Modifier::RAPID_BLINK));
+        let test = rapid_blink!("foo");
+        assert_eq!(expected, test);
+    }
+
+    #[test]
+    fn blink_bold() {
+        let expected = Span::styled(
+            "foo",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .add_modifier(Modifier::SLOW_BLINK),
+        );
+        let test = blink!(bold!("foo"));
+        assert_eq!(expected, test);
+        let test = rapid_blink!(test);
+        assert_eq!(
+            expected.style.add_modifier(Modifier::RAPID_BLINK),
+            test.style
+        );
+    }
+
+    #[test]
+    fn styled() {
+        let expected = bold!(italic!(fg!("hi", Color::Red)));
+        assert_eq!(expected, styled!("hi", bold, italic, fg = Color::Red));
+        assert_eq!(expected, styled!("hi", fg = Color::Red, italic, bold,));
+        assert_eq!(Span::raw("hi"), styled!("hi"));
+
+        // a runtime style is patched over the attributes before it
+        let runtime = Style::default().fg(Color::Blue).add_modifier(Modifier::DIM);
+        let test = styled!(
+            String::from("hi"),
+            bold,
+            fg = Color::Red,
+            bg = Color::Black,
+            style = runtime
+        );
+        assert_eq!(
+            test.style,
+            Style::default()
+                .fg(Color::Blue)
+                .bg(Color::Black)
+                .add_modifier(Modifier::BOLD | Modifier::DIM)
+        );
+        // and keeps modifiers of spans passed in
+        assert_eq!(
+            styled!(underlined!("hi"), reversed).style,
+            Style::default().add_modifier(Modifier::UNDERLINED | Modifier::REVERSED)
+        );
+    }
+
+    #[test]
+    fn formatted() {
+        let n = 3;
+        let e = "oops";
+        assert_eq!(bold!("{} items", n), bold!(String::from("3 items")));
+        assert_eq!(italic!("{}-{}", n, e,), italic!("3-oops"));
+        assert_eq!(underlined!("{:>3}", n), underlined!("  3"));
+        assert_eq!(fg!(Color::Red, "err: {}", e), fg!("err: oops", Color::Red));
+        assert_eq!(bg!(Color::Blue, "{e}{}", n), bg!("oops3", Color::Blue));
+        // a lone string isn't formatted
+        assert_eq!(bold!("{n}").content, "{n}");
+        // and formatted spans nest like any other
+        assert_eq!(
+            bold!(fg!(Color::Red, "{} items", n)),
+            Span::styled(
+                "3 items",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            )
+        );
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(fg!("foo", "cyan"), fg!("foo", Color::Cyan));
+        assert_eq!(bg!("foo", "Light Red"), bg!("foo", Color::LightRed));
+        assert_eq!(fg!("#102030", "{}", 1), fg!("1", Color::Rgb(16, 32, 48)));
+        assert_eq!(bg!("dark_gray", "{}", 1), bg!("1", Color::DarkGray));
+        assert_eq!(
+            styled!("foo", fg = "red", bg = "white", bold),
+            bold!(bg!(fg!("foo", Color::Red), Color::White))
+        );
+        assert_eq!(fg!("foo", "#f80"), fg!("foo", hex!("ff8800")));
+        // names only known at runtime go through parse_color
+        let name = String::from("green");
+        let color = super::parse_color(&name).unwrap_or(Color::Reset);
+        assert_eq!(fg!("foo", color), fg!("foo", Color::Green));
+    }
+
+    #[test]
+    fn line() {
+        let expected = Spans::from(vec![Span::raw("a"), bold!("b")]);
+        assert_eq!(expected, line!["a", bold!("b")]);
+        assert_eq!(expected, line!["a", bold!("b"),]);
+        assert_eq!(Spans::default(), line![]);
+    }
+
+    #[test]
+    fn line_style() {
+        let style = Style::default().fg(Color::White).bg(Color::Blue);
+        let test = line![style; "a", bold!(fg!("b", Color::Red))];
+        let expected = Spans::from(vec![
+            Span::styled("a", style),
+            Span::styled("b", style.fg(Color::Red).add_modifier(Modifier::BOLD)),
+        ]);
+        assert_eq!(expected, test);
+        assert_eq!(Spans::default(), line![style;]);
+    }
+
+    #[test]
+    fn remove_modifiers() {
+        let heading = fg!(bold!(italic!(underlined!("foo"))), Color::Red);
+        let test = not_bold!(heading.clone());
+        assert_eq!(test.content, "foo");
+        assert_eq!(test.style.fg, Some(Color::Red));
+        assert_eq!(
+            test.style.add_modifier,
+            Modifier::ITALIC | Modifier::UNDERLINED
+        );
+        assert_eq!(
+            not_italic!(heading.clone()).style.add_modifier,
+            Modifier::BOLD | Modifier::UNDERLINED
+        );
+        let test = not_underlined!(bg!(heading.clone(), Color::Blue));
+        assert_eq!(test.style.add_modifier, Modifier::BOLD | Modifier::ITALIC);
+        assert_eq!(test.style.bg, Some(Color::Blue));
+        // the removal carries over when patched onto a bold style
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        assert_eq!(
+            bold.patch(not_bold!("foo").style),
+            Style::default().remove_modifier(Modifier::BOLD)
+        );
+
+        assert_eq!(plain!(heading), Span::raw("foo"));
+        assert_eq!(plain!("foo"), Span::raw("foo"));
+    }
+
     #[test]
     fn bold_italic() {
         let expected = Span::styled(
@@ -208,6 +1060,300 @@ mod tests {
         assert_eq!(expected, test);
     }
 
+    #[test]
+    fn text_options_and_iterators() {
+        let items = ["a", "b"];
+        let counts = [("c", 0), ("d", 2)];
+        let warning: Option<Span> = None;
+        let test = text! {
+            "header";
+            warning;
+            Some(bold!("note"));
+            vec![Span::raw("x"), italic!("y")];
+            items.iter().map(|item| Spans::from(*item));
+            counts
+                .iter()
+                .filter_map(|(item, n)| (*n > 0).then_some(line![*item, ": ", n.to_string()]));
+        };
+        let expected = Text::from(vec![
+            Spans::from("header"),
+            Spans::from(bold!("note")),
+            Spans::from(vec![Span::raw("x"), italic!("y")]),
+            Spans::from("a"),
+            Spans::from("b"),
+            line!["d", ": ", "2"],
+        ]);
+        // None adds no line, rather than an empty one
+        assert_eq!(expected, test);
+    }
+
+    #[test]
+    fn text_lines() {
+        use super::lines;
+
+        let names = vec![String::from("a"), String::from("b")];
+        let test = text! {
+            "header";
+            lines(["x", "y"].iter().copied().rev());
+            lines(names);
+            lines(std::iter::empty::<Span>());
+        };
+        let expected = Text::from(vec![
+            Spans::from("header"),
+            Spans::from("y"),
+            Spans::from("x"),
+            Spans::from("a"),
+            Spans::from("b"),
+        ]);
+        assert_eq!(expected, test);
+    }
+
+    #[test]
+    fn text_borrowed_and_nested() {
+        let owned = String::from("owned");
+        let spans = [bold!("a"), Span::raw("b")];
+        let nested = text! {
+            italic!("x");
+            "y";
+        };
+        let test = text! {
+            &owned;
+            std::borrow::Cow::Borrowed("cow");
+            std::borrow::Cow::<str>::Owned(String::from("moo"));
+            &spans[..];
+            nested.clone();
+        };
+        let expected = Text::from(vec![
+            Spans::from("owned"),
+            Spans::from("cow"),
+            Spans::from("moo"),
+            Spans::from(vec![bold!("a"), Span::raw("b")]),
+            Spans::from(italic!("x")),
+            Spans::from("y"),
+        ]);
+        assert_eq!(expected, test);
+    }
+
+    #[test]
+    fn split() {
+        let expected = vec![Spans::from("a"), Spans::from(""), Spans::from("b")];
+        assert_eq!(expected, split!("a\n\nb"));
+        assert_eq!(expected, split!("a\r\n\r\nb\r\n"));
+        assert_eq!(split!(String::from("a\n")), vec![Spans::from("a")]);
+        assert_eq!(split!(""), Vec::<Spans>::new());
+
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        assert_eq!(
+            split!("> a\r\n> b\n", dim),
+            vec![
+                Spans::from(Span::styled("> a", dim)),
+                Spans::from(Span::styled("> b", dim)),
+            ]
+        );
+    }
+
+    #[test]
+    fn md() {
+        let owned = String::from("a **b**");
+        assert_eq!(md!(owned), line!["a ", bold!("b")]);
+        assert_eq!(md!("`c`"), reversed!("c").into());
+        let code = Style::default().fg(Color::Green);
+        assert_eq!(
+            md!("_i_ `c`", code),
+            line![italic!("i"), " ", fg!("c", Color::Green)]
+        );
+        let test = text! {
+            md!("# not a heading");
+            md!("**done**");
+        };
+        assert_eq!(test, text! { "# not a heading"; bold!("done"); });
+    }
+
+    #[test]
+    fn key_hints() {
+        use super::KeyHintStyle;
+
+        let down = vec![bold!("j"), Span::raw(" - "), italic!("down")];
+        assert_eq!(key_hint!("j", "down"), down);
+        let owned = String::from("up");
+        assert_eq!(
+            key_hints![("j", "down"), ("k", owned)],
+            line![
+                bold!("j"),
+                " - ",
+                italic!("down"),
+                ", ",
+                bold!("k"),
+                " - ",
+                italic!("up")
+            ]
+        );
+        assert_eq!(key_hints![], Spans::default());
+
+        let style = KeyHintStyle::new()
+            .key(Style::default().fg(Color::Yellow))
+            .hint_separator(" | ");
+        assert_eq!(
+            key_hint!(style; "q", "quit"),
+            vec![fg!("q", Color::Yellow), Span::raw(" - "), italic!("quit")]
+        );
+        assert_eq!(
+            key_hints![style.clone(); ("q", "quit"), ("?", "help")],
+            style.hints([("q", "quit"), ("?", "help")])
+        );
+        assert_eq!(
+            text! { key_hint!("j", "down"); },
+            Text::from(Spans::from(down))
+        );
+    }
+
+    #[test]
+    fn pad() {
+        assert_eq!(pad!("ab", 4), Span::raw("ab  "));
+        assert_eq!(pad_right!(String::from("ab"), 4), Span::raw("ab  "));
+        assert_eq!(pad_left!("ab", 4), Span::raw("  ab"));
+        assert_eq!(pad_center!("ab", 5), Span::raw(" ab  "));
+        assert_eq!(pad!(bold!("abcdef"), 3), bold!("ab…"));
+        assert_eq!(
+            line![pad!("名前", 6), pad_left!("7", 3)],
+            line!["名前  ", "  7"]
+        );
+        let status = bg!("ok", Color::Green);
+        assert_eq!(pad_center!(status, 4), bg!(" ok ", Color::Green));
+    }
+
+    #[test]
+    fn repeat() {
+        assert_eq!(repeat!('─', 3), Span::raw("───"));
+        assert_eq!(repeat!("ab", 2), Span::raw("abab"));
+        assert_eq!(repeat!(String::from("日"), 2), Span::raw("日日"));
+        assert_eq!(repeat!('─', 0), Span::raw(""));
+        let style = Style::default().fg(Color::Green);
+        assert_eq!(repeat!('█', 2, style), Span::styled("██", style));
+
+        assert_eq!(repeat_to_width!('─', 3), Span::raw("───"));
+        assert_eq!(repeat_to_width!("日", 3), Span::raw("日 "));
+        assert_eq!(
+            repeat_to_width!("━━", 5, style),
+            Span::styled("━━━━━", style)
+        );
+        assert_eq!(repeat_to_width!('─', 0), Span::raw(""));
+    }
+
+    #[test]
+    fn gradient() {
+        let from = Color::Rgb(0, 0, 0);
+        let to = Color::Rgb(4, 4, 4);
+        assert_eq!(
+            gradient!("abc", from, to),
+            line![fg!("a", from), fg!("b", Color::Rgb(2, 2, 2)), fg!("c", to)]
+        );
+        assert_eq!(
+            bg_gradient!(String::from("ab"), from, to),
+            line![bg!("a", from), bg!("b", to)]
+        );
+    }
+
+    #[test]
+    fn rainbow() {
+        assert_eq!(
+            rainbow!("a b"),
+            line![fg!("a", Color::Red), " ", fg!("b", Color::Yellow)]
+        );
+        let palette = [Color::White, Color::Blue];
+        assert_eq!(
+            rainbow!(String::from("a b"), &palette),
+            line![fg!("a", Color::White), " ", fg!("b", Color::Blue)]
+        );
+        assert_eq!(
+            rainbow!("a b", &palette, false),
+            line![
+                fg!("a", Color::White),
+                fg!(" ", Color::Blue),
+                fg!("b", Color::White)
+            ]
+        );
+    }
+
+    #[test]
+    fn join() {
+        use super::SpanJoin;
+
+        assert_eq!(
+            bold!("a").join(" ").join(italic!("b")),
+            line![bold!("a"), " ", italic!("b")]
+        );
+    }
+
+    #[test]
+    fn text_builder() {
+        use super::TextBuilder;
+
+        let test = text! {
+            italic!("foo");
+            Some("bar");
+            split!("a\nb")
+        };
+        let built = TextBuilder::new()
+            .line(italic!("foo"))
+            .line(Some("bar"))
+            .line(split!("a\nb"))
+            .build();
+        assert_eq!(test, built);
+        // a single line needs no semicolon
+        assert_eq!(text! { "x" }, Text::from("x"));
+    }
+
+    #[test]
+    fn indent() {
+        let nested = text! {
+            "parent";
+            indent!(2, text! { "child"; "other\nchild"; });
+        };
+        assert_eq!(
+            nested,
+            text! {
+                "parent";
+                line!["  ", "child"];
+                line!["  ", "other"];
+                line!["  ", "child"];
+            }
+        );
+        let gutter = fg!("│ ", Color::DarkGray);
+        assert_eq!(
+            indent!(1, bold!("quote"), gutter.clone()),
+            text! { line![" ", gutter, bold!("quote")]; }
+        );
+    }
+
+    #[test]
+    fn wrap() {
+        assert_eq!(
+            wrap!(line!["one ", bold!("two three")], 7),
+            vec![line!["one ", bold!("two")], line![bold!("three")]]
+        );
+        assert_eq!(
+            wrap!("one two", 3),
+            vec![Spans::from("one"), Spans::from("two")]
+        );
+        let text = text! { wrap!(String::from("a b"), 1); };
+        assert_eq!(text.height(), 2);
+    }
+
+    #[test]
+    fn truncate() {
+        assert_eq!(truncate!("abcdef", 4), line!["abc", "…"]);
+        assert_eq!(
+            truncate!(bold!("abcdef"), 4, ".."),
+            line![bold!("ab"), bold!("..")]
+        );
+        assert_eq!(truncate!(String::from("abc"), 4), Spans::from("abc"));
+        assert_eq!(
+            truncate!(line!["ab", italic!("cd")], 3),
+            line!["ab", italic!("…")]
+        );
+    }
+
     #[test]
     fn text_single_line() {
         let expected = Text::from(vec![Spans::from(Span::styled(
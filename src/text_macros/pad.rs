@@ -0,0 +1,110 @@
+use ratatui::{
+    layout::Alignment,
+    text::{Span, Spans},
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::truncate;
+
+/// Pad a span with spaces to `width` columns, placing its text by `alignment`, or cut it to fit
+/// with a `…` at the end if it's wider, as by [`truncate_line`](super::truncate_line). Widths are
+/// measured in columns, so double width text, like `日本`, lines up with other text.
+///
+/// The spaces are part of the span, so they take its style, e.g. filling its background color
+/// across the padded area. This is what [`pad!`](crate::pad!), [`pad_left!`](crate::pad_left!),
+/// [`pad_right!`](crate::pad_right!) and [`pad_center!`](crate::pad_center!) use.
+pub fn pad<'a, S>(span: S, width: usize, alignment: Alignment) -> Span<'a>
+where
+    S: Into<Span<'a>>,
+{
+    let mut span = span.into();
+    if span.content.width() > width {
+        let cut = truncate::truncate_end(Spans::from(span.clone()), width, "…");
+        span.content = cut
+            .0
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect::<String>()
+            .into();
+    }
+    let gap = width - span.content.width();
+    let (left, right) = match alignment {
+        Alignment::Left => (0, gap),
+        Alignment::Center => (gap / 2, gap - gap / 2),
+        Alignment::Right => (gap, 0),
+    };
+    if gap > 0 {
+        span.content = format!("{}{}{}", " ".repeat(left), span.content, " ".repeat(right)).into();
+    }
+    span
+}
+
+/// Repeat `fill` to fill `width` columns, e.g. for a rule or the bar of a progress bar. Widths
+/// are measured in columns, so a fill two columns wide, like `━━` or `日`, is repeated half as
+/// many times as a one column fill.
+///
+/// If the width isn't a multiple of the fill's width, the fill is cut short at the end, and a
+/// space fills in for a double width character that doesn't fit. An empty fill gives an empty
+/// string. This is what [`repeat_to_width!`](crate::repeat_to_width!) uses.
+pub fn repeat_to_width(fill: &str, width: usize) -> String {
+    let fill_width = fill.width();
+    if fill_width == 0 {
+        return String::new();
+    }
+    let mut text = fill.repeat(width / fill_width);
+    let mut used = width - width % fill_width;
+    for c in fill.chars() {
+        let c_width = c.width().unwrap_or(0);
+        if used + c_width > width {
+            break;
+        }
+        text.push(c);
+        used += c_width;
+    }
+    text.push_str(&" ".repeat(width - used));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Style};
+
+    use super::*;
+
+    #[test]
+    fn pad_and_truncate() {
+        let content = |span: Span| span.content.into_owned();
+        assert_eq!(content(pad("ab", 5, Alignment::Left)), "ab   ");
+        assert_eq!(content(pad("ab", 5, Alignment::Right)), "   ab");
+        assert_eq!(content(pad("ab", 5, Alignment::Center)), " ab  ");
+        assert_eq!(content(pad("ab", 2, Alignment::Center)), "ab");
+        // double width text is measured in columns
+        assert_eq!(content(pad("日本", 6, Alignment::Right)), "  日本");
+        assert_eq!(content(pad("日本", 4, Alignment::Left)), "日本");
+
+        assert_eq!(content(pad("abcdef", 4, Alignment::Left)), "abc…");
+        assert_eq!(content(pad("abcdef", 1, Alignment::Right)), "…");
+        assert_eq!(content(pad("abcdef", 0, Alignment::Left)), "");
+        assert_eq!(content(pad("日本語", 5, Alignment::Left)), "日本…");
+        // a double width character that doesn't fit leaves a space
+        assert_eq!(content(pad("日本語", 4, Alignment::Left)), "日… ");
+
+        // the padding takes the span's style
+        let style = Style::default().bg(Color::Blue);
+        assert_eq!(
+            pad(Span::styled("ab", style), 4, Alignment::Right),
+            Span::styled("  ab", style)
+        );
+    }
+
+    #[test]
+    fn repeat() {
+        assert_eq!(repeat_to_width("─", 4), "────");
+        assert_eq!(repeat_to_width("ab", 5), "ababa");
+        assert_eq!(repeat_to_width("日", 4), "日日");
+        assert_eq!(repeat_to_width("日", 5), "日日 ");
+        assert_eq!(repeat_to_width("a日", 5), "a日a ");
+        assert_eq!(repeat_to_width("─", 0), "");
+        assert_eq!(repeat_to_width("", 3), "");
+    }
+}
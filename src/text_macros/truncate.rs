@@ -0,0 +1,68 @@
+use ratatui::text::Spans;
+
+use crate::truncate::{self, TruncateAt};
+
+/// Cut `line` to at most `width` columns, ending it with `ellipsis` if anything was removed. This
+/// is what [`truncate!`](crate::truncate!) uses, and the same truncation as a
+/// [`StyledList`](crate::styled_list::StyledList) with the `styled_list` feature.
+///
+/// Graphemes are never split, so a double width character straddling the cut is removed, leaving
+/// the line narrower than `width`. The kept text keeps its styles, and the ellipsis takes the
+/// style of the text it replaces. If even the ellipsis doesn't fit, the line is cut without it.
+pub fn truncate_line<'a>(line: &Spans<'a>, width: u16, ellipsis: &str) -> Spans<'a> {
+    truncate_line_at(line, width, ellipsis, TruncateAt::End)
+}
+
+/// Cut `line` to at most `width` columns like [`truncate_line`], removing text from the start,
+/// middle or end of it, as given by `at`.
+pub fn truncate_line_at<'a>(
+    line: &Spans<'a>,
+    width: u16,
+    ellipsis: &str,
+    at: TruncateAt,
+) -> Spans<'a> {
+    truncate::truncate(line.clone(), usize::from(width), ellipsis, at)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{
+        style::{Color, Style},
+        text::Span,
+    };
+
+    use super::*;
+
+    #[test]
+    fn truncates() {
+        let red = Style::default().fg(Color::Red);
+        let line = Spans::from(vec![Span::raw("ab"), Span::styled("cdef", red)]);
+        assert_eq!(truncate_line(&line, 6, "…"), line);
+        assert_eq!(
+            truncate_line(&line, 4, "…"),
+            Spans::from(vec![
+                Span::raw("ab"),
+                Span::styled("c", red),
+                Span::styled("…", red)
+            ])
+        );
+        assert_eq!(
+            truncate_line(&line, 4, "..."),
+            Spans::from(vec![Span::raw("a"), Span::raw("...")])
+        );
+        assert_eq!(
+            truncate_line_at(&line, 4, "…", TruncateAt::Start),
+            Spans::from(vec![Span::styled("…", red), Span::styled("def", red)])
+        );
+        // graphemes aren't split
+        let wide = Spans::from("日本e\u{301}");
+        assert_eq!(
+            truncate_line(&wide, 4, "…"),
+            Spans::from(vec![Span::raw("日"), Span::raw("…")])
+        );
+        assert_eq!(
+            truncate_line(&Spans::from("ae\u{301}b"), 2, ""),
+            Spans::from("ae\u{301}")
+        );
+    }
+}
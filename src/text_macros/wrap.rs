@@ -0,0 +1,171 @@
+use ratatui::{
+    style::Style,
+    text::{Span, Spans},
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Word wrap `line` into lines at most `width` columns wide, e.g. to make list items or to count
+/// the height text will take. This is what [`wrap!`](crate::wrap!) uses.
+///
+/// Lines are broken at whitespace, which is dropped at the breaks, and at newlines in the text.
+/// Words wider than `width` are broken between graphemes, so emoji and combining marks aren't
+/// split, and double width characters that would straddle the edge move to the next line. A
+/// single grapheme wider than `width` is put on its own line, overflowing it.
+///
+/// Spans keep their styles across the breaks. An empty line stays as one empty line, and a width
+/// of 0 gives no lines.
+pub fn wrap_line(line: &Spans, width: u16) -> Vec<Spans<'static>> {
+    if width == 0 {
+        return Vec::new();
+    }
+    let mut wrapper = Wrapper {
+        width: usize::from(width),
+        lines: vec![Vec::new()],
+        used: 0,
+    };
+    let mut space = Vec::new();
+    let mut word = Vec::new();
+    let graphemes = line.0.iter().flat_map(|span| {
+        span.content
+            .graphemes(true)
+            .map(move |grapheme| (grapheme, span.style))
+    });
+    for (grapheme, style) in graphemes {
+        if grapheme == "\n" || grapheme == "\r\n" {
+            wrapper.word(&space, &word);
+            wrapper.break_line();
+            space.clear();
+            word.clear();
+        } else if grapheme.chars().all(char::is_whitespace) {
+            if !word.is_empty() {
+                wrapper.word(&space, &word);
+                space.clear();
+                word.clear();
+            }
+            space.push((grapheme, style));
+        } else {
+            word.push((grapheme, style));
+        }
+    }
+    wrapper.word(&space, &word);
+    wrapper.lines.into_iter().map(Spans::from).collect()
+}
+
+/// The lines wrapped so far.
+struct Wrapper {
+    width: usize,
+    lines: Vec<Vec<Span<'static>>>,
+    /// The width of the last line.
+    used: usize,
+}
+
+impl Wrapper {
+    /// Add a word and the whitespace before it, on the last line if they fit.
+    fn word(&mut self, space: &[(&str, Style)], word: &[(&str, Style)]) {
+        if word.is_empty() {
+            return;
+        }
+        let space_width: usize = space.iter().map(|(grapheme, _)| grapheme.width()).sum();
+        let word_width: usize = word.iter().map(|(grapheme, _)| grapheme.width()).sum();
+        if self.used + space_width + word_width <= self.width {
+            space
+                .iter()
+                .chain(word)
+                .for_each(|(g, style)| self.push(g, *style));
+            return;
+        }
+        if self.used > 0 {
+            self.break_line();
+        }
+        for (grapheme, style) in word {
+            if self.used > 0 && self.used + grapheme.width() > self.width {
+                self.break_line();
+            }
+            self.push(grapheme, *style);
+        }
+    }
+
+    /// Add a grapheme to the last line, joining the last span if it has the same style.
+    fn push(&mut self, grapheme: &str, style: Style) {
+        self.used += grapheme.width();
+        let line = self.lines.last_mut().unwrap();
+        match line.last_mut() {
+            Some(span) if span.style == style => span.content.to_mut().push_str(grapheme),
+            _ => line.push(Span::styled(grapheme.to_string(), style)),
+        }
+    }
+
+    /// Start a new line.
+    fn break_line(&mut self) {
+        self.lines.push(Vec::new());
+        self.used = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Modifier;
+
+    use super::*;
+
+    fn wrap(text: &str, width: u16) -> Vec<String> {
+        wrap_line(&Spans::from(text), width)
+            .iter()
+            .map(|line| line.0.iter().map(|span| span.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn words() {
+        assert_eq!(wrap("the quick brown fox", 10), ["the quick", "brown fox"]);
+        assert_eq!(wrap("the quick brown fox", 9), ["the quick", "brown fox"]);
+        assert_eq!(wrap("the  quick", 20), ["the  quick"]);
+        assert_eq!(wrap("  indented text", 10), ["  indented", "text"]);
+        assert_eq!(wrap("a\nb c\r\nd", 10), ["a", "b c", "d"]);
+        assert_eq!(wrap("", 10), [""]);
+        assert_eq!(wrap("trailing   ", 10), ["trailing"]);
+        assert_eq!(wrap("any", 0), Vec::<String>::new());
+    }
+
+    #[test]
+    fn long_and_wide() {
+        assert_eq!(wrap("a abcdefgh", 3), ["a", "abc", "def", "gh"]);
+        // double width characters don't straddle the edge
+        assert_eq!(wrap("ab日本", 3), ["ab", "日", "本"]);
+        assert_eq!(wrap("日本語 です", 5), ["日本", "語", "です"]);
+        // graphemes stay whole, even when too wide
+        assert_eq!(
+            wrap("e\u{301}e\u{301}e\u{301}", 2),
+            ["e\u{301}e\u{301}", "e\u{301}"]
+        );
+        assert_eq!(wrap("日本", 1), ["日", "本"]);
+    }
+
+    #[test]
+    fn styles() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let line = Spans::from(vec![
+            Span::raw("one "),
+            Span::styled("two three", bold),
+            Span::raw(" four"),
+        ]);
+        assert_eq!(
+            wrap_line(&line, 8),
+            [
+                Spans::from(vec![Span::raw("one "), Span::styled("two", bold)]),
+                Spans::from(vec![Span::styled("three", bold)]),
+                Spans::from("four"),
+            ]
+        );
+        // a word split between styles is still one word
+        let line = Spans::from(vec![Span::raw("ab"), Span::styled("cd", bold)]);
+        assert_eq!(
+            wrap_line(&line, 3),
+            [
+                Spans::from(vec![Span::raw("ab"), Span::styled("c", bold)]),
+                Spans::from(vec![Span::styled("d", bold)]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use std::{borrow::Cow, ops::Range};
+
+use ratatui::text::{Span, Spans};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Control where text is removed from lines that are too wide, e.g. for the list.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TruncateAt {
+    /// Remove text from the start of the line, e.g. `…/widgets/viewport.rs`
+    Start,
+    /// Remove text from the middle of the line, e.g. `src/wi…/viewport.rs`
+    Middle,
+    /// Remove text from the end of the line, e.g. `src/widgets/vie…`
+    End,
+}
+
+/// A grapheme of a line, located by the span it is in and its byte range within that span.
+struct Grapheme {
+    span: usize,
+    bytes: Range<usize>,
+    width: usize,
+}
+
+/// Shorten `line` to at most `width` display columns. If the line is too long, text is removed
+/// from the position given by `at` and replaced with `ellipsis`. The ellipsis takes the style of
+/// the removed text next to it.
+///
+/// Widths are measured per grapheme, so wide characters are never split - if a wide character
+/// would straddle the cut, it is removed and the line comes out narrower than `width`. Spans
+/// that are entirely removed are dropped, the rest keep their styles.
+pub(crate) fn truncate<'a>(
+    line: Spans<'a>,
+    width: usize,
+    ellipsis: &str,
+    at: TruncateAt,
+) -> Spans<'a> {
+    if line.width() <= width {
+        return line;
+    }
+
+    // if even the ellipsis doesn't fit, just clip the line
+    let ellipsis_width = ellipsis.width();
+    let (budget, ellipsis) = if ellipsis_width <= width {
+        (width - ellipsis_width, ellipsis)
+    } else {
+        (width, "")
+    };
+
+    let graphemes: Vec<Grapheme> = line
+        .0
+        .iter()
+        .enumerate()
+        .flat_map(|(span, s)| {
+            s.content
+                .grapheme_indices(true)
+                .map(move |(i, g)| Grapheme {
+                    span,
+                    bytes: i..i + g.len(),
+                    width: g.width(),
+                })
+        })
+        .collect();
+
+    // how many columns to keep from the start and end of the line
+    let (head_budget, tail_budget) = match at {
+        TruncateAt::End => (budget, 0),
+        TruncateAt::Start => (0, budget),
+        TruncateAt::Middle => (budget - budget / 2, budget / 2),
+    };
+    let head = fit(graphemes.iter(), head_budget);
+    let tail = fit(graphemes[head..].iter().rev(), tail_budget);
+    let cut = head..graphemes.len() - tail;
+
+    // The ellipsis is styled like the removed grapheme next to the kept text
+    let cut_grapheme = match at {
+        TruncateAt::Start => cut.end - 1,
+        TruncateAt::Middle | TruncateAt::End => cut.start,
+    };
+    let cut_style = line.0[graphemes[cut_grapheme].span].style;
+
+    let mut res = Vec::with_capacity(line.0.len() + 1);
+    push_graphemes(&mut res, &line, &graphemes[..cut.start]);
+    if !ellipsis.is_empty() {
+        res.push(Span::styled(ellipsis.to_string(), cut_style));
+    }
+    push_graphemes(&mut res, &line, &graphemes[cut.end..]);
+    Spans(res)
+}
+
+/// Shorten `line` to at most `width` display columns, removing text from the end.
+pub(crate) fn truncate_end<'a>(line: Spans<'a>, width: usize, ellipsis: &str) -> Spans<'a> {
+    truncate(line, width, ellipsis, TruncateAt::End)
+}
+
+/// Count how many graphemes from the iterator fit within `width` columns.
+fn fit<'g>(graphemes: impl Iterator<Item = &'g Grapheme>, width: usize) -> usize {
+    let mut used = 0;
+    graphemes
+        .take_while(|g| {
+            used += g.width;
+            used <= width
+        })
+        .count()
+}
+
+/// Push spans for a run of graphemes, grouping consecutive graphemes of the same span.
+fn push_graphemes<'a>(res: &mut Vec<Span<'a>>, line: &Spans<'a>, graphemes: &[Grapheme]) {
+    let mut i = 0;
+    while i < graphemes.len() {
+        let span = graphemes[i].span;
+        let start = graphemes[i].bytes.start;
+        let mut end = graphemes[i].bytes.end;
+        i += 1;
+        while i < graphemes.len() && graphemes[i].span == span {
+            end = graphemes[i].bytes.end;
+            i += 1;
+        }
+
+        let source = &line.0[span];
+        let content = match &source.content {
+            Cow::Borrowed(s) => Cow::Borrowed(&s[start..end]),
+            Cow::Owned(s) => Cow::Owned(s[start..end].to_string()),
+        };
+        res.push(Span::styled(content, source.style));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Style};
+
+    use super::*;
+
+    fn red() -> Style {
+        Style::default().fg(Color::Red)
+    }
+
+    fn line<'a>() -> Spans<'a> {
+        Spans(vec![
+            Span::raw("abc"),
+            Span::styled("def", red()),
+            Span::raw("ghi"),
+        ])
+    }
+
+    #[test]
+    fn fits() {
+        for at in [TruncateAt::Start, TruncateAt::Middle, TruncateAt::End] {
+            assert_eq!(truncate(line(), 9, "…", at), line());
+            assert_eq!(truncate(line(), 10, "…", at), line());
+        }
+    }
+
+    #[test]
+    fn end() {
+        assert_eq!(
+            truncate(line(), 5, "…", TruncateAt::End),
+            Spans(vec![
+                Span::raw("abc"),
+                Span::styled("d", red()),
+                Span::styled("…", red())
+            ])
+        );
+        // the styled span and the last one are removed entirely
+        assert_eq!(
+            truncate(line(), 3, "…", TruncateAt::End),
+            Spans(vec![Span::raw("ab"), Span::raw("…")])
+        );
+        assert_eq!(truncate(line(), 0, "…", TruncateAt::End), Spans(vec![]));
+    }
+
+    #[test]
+    fn start() {
+        assert_eq!(
+            truncate(line(), 5, "…", TruncateAt::Start),
+            Spans(vec![
+                Span::styled("…", red()),
+                Span::styled("f", red()),
+                Span::raw("ghi")
+            ])
+        );
+        assert_eq!(
+            truncate(line(), 3, "..", TruncateAt::Start),
+            Spans(vec![Span::raw(".."), Span::raw("i")])
+        );
+    }
+
+    #[test]
+    fn middle() {
+        assert_eq!(
+            truncate(line(), 6, "…", TruncateAt::Middle),
+            Spans(vec![
+                Span::raw("abc"),
+                Span::styled("…", red()),
+                Span::raw("hi"),
+            ])
+        );
+        assert_eq!(
+            truncate(line(), 8, "…", TruncateAt::Middle),
+            Spans(vec![
+                Span::raw("abc"),
+                Span::styled("d", red()),
+                Span::styled("…", red()),
+                Span::raw("ghi"),
+            ])
+        );
+    }
+
+    #[test]
+    fn wide_chars() {
+        let wide = || Spans::from("日本語");
+        assert_eq!(
+            truncate(wide(), 4, "…", TruncateAt::End),
+            Spans(vec![Span::raw("日"), Span::raw("…")])
+        );
+        assert_eq!(
+            truncate(wide(), 4, "…", TruncateAt::Start),
+            Spans(vec![Span::raw("…"), Span::raw("語")])
+        );
+        // 3 columns before the ellipsis and 2 after, "語" straddles the cut so it is removed
+        assert_eq!(
+            truncate(Spans::from("a日本語b"), 6, "…", TruncateAt::Middle),
+            Spans(vec![Span::raw("a日"), Span::raw("…"), Span::raw("b")])
+        );
+    }
+}